@@ -0,0 +1,152 @@
+//! # bucket_queue
+//!
+//! Ordered work queues sharded into buckets, backing the request priority
+//! queue read by `get_request_queue`, the open-request deadlines read by
+//! `sweep_expired_requests` and the pending custody hops read by
+//! `sweep_expired_transfers`.
+//!
+//! Each entry lives in one bucket, numbered by the caller so that bucket
+//! order agrees with entry order. A bucket is one sorted
+//! `QueueKey::Bucket(queue, bucket)` ledger entry, and the non-empty bucket
+//! numbers are listed in ascending order under `QueueKey::Buckets(queue)`, as
+//! the expiry index lists its days. An insert or remove rewrites one bucket,
+//! and the list only when a bucket is created or emptied. Readers walk the
+//! buckets in order and stop once they have what they need, so no call reads
+//! or rewrites the whole queue.
+
+use soroban_sdk::{contracttype, Env, IntoVal, TryFromVal, Val, Vec};
+
+/// The sharded queues kept by this module
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Queue {
+    /// Open requests in priority order: `RequestQueueEntry`
+    Requests,
+    /// Open requests by deadline: `(required_by, request_id)`
+    Deadlines,
+    /// Pending custody hops by age: `(initiated_at, unit_id, event_id)`
+    Transfers,
+}
+
+/// Storage keys of the sharded queues
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QueueKey {
+    /// Non-empty bucket numbers of a queue, ascending: Vec<u64>
+    Buckets(Queue),
+    /// Entries of one bucket, ascending
+    Bucket(Queue, u64),
+}
+
+fn bucket_numbers(env: &Env, queue: Queue) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&QueueKey::Buckets(queue))
+        .unwrap_or(Vec::new(env))
+}
+
+fn put_bucket_numbers(env: &Env, queue: Queue, buckets: &Vec<u64>) {
+    if buckets.is_empty() {
+        env.storage().persistent().remove(&QueueKey::Buckets(queue));
+    } else {
+        env.storage()
+            .persistent()
+            .set(&QueueKey::Buckets(queue), buckets);
+    }
+}
+
+fn entries<T>(env: &Env, queue: Queue, bucket: u64) -> Vec<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    env.storage()
+        .persistent()
+        .get(&QueueKey::Bucket(queue, bucket))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add `entry` to `bucket` of `queue`, ignoring duplicates
+pub fn insert<T>(env: &Env, queue: Queue, bucket: u64, entry: T)
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let mut bucket_entries: Vec<T> = entries(env, queue, bucket);
+    let Err(pos) = bucket_entries.binary_search(&entry) else {
+        return;
+    };
+    bucket_entries.insert(pos, entry);
+    env.storage()
+        .persistent()
+        .set(&QueueKey::Bucket(queue, bucket), &bucket_entries);
+
+    if bucket_entries.len() == 1 {
+        let mut buckets = bucket_numbers(env, queue);
+        if let Err(pos) = buckets.binary_search(bucket) {
+            buckets.insert(pos, bucket);
+            put_bucket_numbers(env, queue, &buckets);
+        }
+    }
+}
+
+/// Remove `entry` from `bucket` of `queue`, if present; empty buckets are
+/// deleted and dropped from the bucket list
+pub fn remove<T>(env: &Env, queue: Queue, bucket: u64, entry: &T)
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    let mut bucket_entries: Vec<T> = entries(env, queue, bucket);
+    let Ok(pos) = bucket_entries.binary_search(entry.clone()) else {
+        return;
+    };
+    bucket_entries.remove(pos);
+    if !bucket_entries.is_empty() {
+        env.storage()
+            .persistent()
+            .set(&QueueKey::Bucket(queue, bucket), &bucket_entries);
+        return;
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&QueueKey::Bucket(queue, bucket));
+    let mut buckets = bucket_numbers(env, queue);
+    if let Ok(pos) = buckets.binary_search(bucket) {
+        buckets.remove(pos);
+        put_bucket_numbers(env, queue, &buckets);
+    }
+}
+
+/// Visit the entries of `queue` in order until `visit` returns `false`.
+///
+/// With `after` as `(bucket, entry)`, the walk starts strictly after that
+/// entry, whether or not it is still queued.
+pub fn scan<T>(env: &Env, queue: Queue, after: Option<(u64, T)>, mut visit: impl FnMut(T) -> bool)
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    let buckets = bucket_numbers(env, queue);
+    let first = match &after {
+        Some((bucket, _)) => match buckets.binary_search(*bucket) {
+            Ok(pos) | Err(pos) => pos,
+        },
+        None => 0,
+    };
+
+    for bucket in buckets.slice(first..buckets.len()).iter() {
+        let bucket_entries: Vec<T> = entries(env, queue, bucket);
+        let start = match &after {
+            Some((after_bucket, entry)) if *after_bucket == bucket => {
+                match bucket_entries.binary_search(entry.clone()) {
+                    Ok(pos) => pos + 1,
+                    Err(pos) => pos,
+                }
+            }
+            _ => 0,
+        };
+        for entry in bucket_entries.slice(start..bucket_entries.len()).iter() {
+            if !visit(entry) {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! # compatibility
+//!
+//! ABO/Rh and component compatibility between donor units and recipient
+//! requests, used by `approve_request` to reject unsafe units, by
+//! `propose_units` to suggest a unit set, and by `get_request_queue` to show a
+//! bank the requests its stock can serve.
+//!
+//! Red cells follow red-cell transfusion rules: a donor's A and B antigens
+//! must all be present in the recipient, and Rh-positive blood only goes to
+//! Rh-positive recipients. O-negative therefore serves every request and
+//! AB-positive receives from every type. Whole blood also carries plasma, so
+//! it must be ABO-identical. Plasma and cryoprecipitate reverse the ABO rule
+//! (AB plasma serves everyone) and ignore Rh; platelets use the plasma ABO
+//! rule with the red-cell Rh rule.
+//!
+//! A unit must be of the requested component, except that whole blood and
+//! red cells substitute for each other. Any unit that is not an exact type
+//! and component match is a substitute.
+
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{registry_read, BloodComponent, BloodRequest, BloodStatus, BloodType};
+
+const ALL_BLOOD_TYPES: [BloodType; 8] = [
+    BloodType::APositive,
+    BloodType::ANegative,
+    BloodType::BPositive,
+    BloodType::BNegative,
+    BloodType::ABPositive,
+    BloodType::ABNegative,
+    BloodType::OPositive,
+    BloodType::ONegative,
+];
+
+/// (has A antigen, has B antigen, Rh positive)
+fn antigens(blood_type: BloodType) -> (bool, bool, bool) {
+    match blood_type {
+        BloodType::APositive => (true, false, true),
+        BloodType::ANegative => (true, false, false),
+        BloodType::BPositive => (false, true, true),
+        BloodType::BNegative => (false, true, false),
+        BloodType::ABPositive => (true, true, true),
+        BloodType::ABNegative => (true, true, false),
+        BloodType::OPositive => (false, false, true),
+        BloodType::ONegative => (false, false, false),
+    }
+}
+
+/// Whether red cells of type `donor` can be given to a `recipient`
+pub fn can_donate_to(donor: BloodType, recipient: BloodType) -> bool {
+    let (donor_a, donor_b, donor_rh) = antigens(donor);
+    let (recipient_a, recipient_b, recipient_rh) = antigens(recipient);
+
+    (!donor_a || recipient_a) && (!donor_b || recipient_b) && (!donor_rh || recipient_rh)
+}
+
+/// Whether a `component` unit of type `donor` can be given to a `recipient`
+pub fn is_compatible(component: BloodComponent, donor: BloodType, recipient: BloodType) -> bool {
+    let (donor_a, donor_b, donor_rh) = antigens(donor);
+    let (recipient_a, recipient_b, recipient_rh) = antigens(recipient);
+    let plasma_abo = (!recipient_a || donor_a) && (!recipient_b || donor_b);
+    let red_cell_rh = !donor_rh || recipient_rh;
+
+    match component {
+        BloodComponent::WholeBlood => {
+            donor_a == recipient_a && donor_b == recipient_b && red_cell_rh
+        }
+        BloodComponent::RedBloodCells => can_donate_to(donor, recipient),
+        BloodComponent::Plasma | BloodComponent::Cryoprecipitate => plasma_abo,
+        BloodComponent::Platelets => plasma_abo && red_cell_rh,
+    }
+}
+
+/// Whether a unit of `component` can fill a request for `requested`
+pub fn component_serves(component: BloodComponent, requested: BloodComponent) -> bool {
+    let red_cells = |c| {
+        matches!(
+            c,
+            BloodComponent::WholeBlood | BloodComponent::RedBloodCells
+        )
+    };
+    component == requested || (red_cells(component) && red_cells(requested))
+}
+
+/// How far a unit strays from what a request asked for, or `None` if it is
+/// unsuitable. 0 is an exact match; each differing antigen or a substitute
+/// component adds 1, so universal donors are proposed last.
+pub fn substitution_rank(
+    component: BloodComponent,
+    donor: BloodType,
+    requested_component: BloodComponent,
+    recipient: BloodType,
+) -> Option<u32> {
+    if !component_serves(component, requested_component)
+        || !is_compatible(component, donor, recipient)
+    {
+        return None;
+    }
+
+    let (donor_a, donor_b, donor_rh) = antigens(donor);
+    let (recipient_a, recipient_b, recipient_rh) = antigens(recipient);
+    let rank = [
+        donor_a != recipient_a,
+        donor_b != recipient_b,
+        donor_rh != recipient_rh,
+        component != requested_component,
+    ]
+    .iter()
+    .filter(|differs| **differs)
+    .count();
+    Some(rank as u32)
+}
+
+/// Propose `bank_id`'s available units to cover what is left of `request`.
+///
+/// Candidates are ordered by [`substitution_rank`], then earliest expiry
+/// first, and taken until the uncommitted quantity is covered.
+pub fn propose(
+    env: &Env,
+    bank_id: &Address,
+    request: &BloodRequest,
+    requested_component: BloodComponent,
+) -> Vec<u64> {
+    let now = env.ledger().timestamp();
+
+    // (rank, expiration, unit_id), kept sorted
+    let mut candidates: Vec<(u32, u64, u64)> = Vec::new(env);
+    for donor in ALL_BLOOD_TYPES {
+        registry_read::scan_available(env, donor, now, None, |expiration, unit_id| {
+            let Ok(unit) = registry_read::get_unit(env, unit_id) else {
+                return true;
+            };
+            if unit.bank_id != *bank_id || unit.status != BloodStatus::Available {
+                return true;
+            }
+            if let Some(rank) = substitution_rank(
+                unit.component,
+                donor,
+                requested_component,
+                request.blood_type,
+            ) {
+                let candidate = (rank, expiration, unit_id);
+                if let Err(pos) = candidates.binary_search(candidate) {
+                    candidates.insert(pos, candidate);
+                }
+            }
+            true
+        });
+    }
+
+    let mut remaining = request
+        .quantity_ml
+        .saturating_sub(request.fulfilled_quantity_ml);
+    let mut proposal = Vec::new(env);
+    for (_, _, unit_id) in candidates.iter() {
+        if remaining == 0 {
+            break;
+        }
+        if let Ok(unit) = registry_read::get_unit(env, unit_id) {
+            remaining = remaining.saturating_sub(unit.quantity);
+            proposal.push_back(unit_id);
+        }
+    }
+    proposal
+}
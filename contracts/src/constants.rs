@@ -30,6 +30,24 @@ pub const MIN_SHELF_LIFE_DAYS: u64 = 1;
 /// red blood cell viability.
 pub const MAX_SHELF_LIFE_DAYS: u64 = 42;
 
+/// Maximum shelf life for platelets in days.
+///
+/// Platelets are stored at room temperature with agitation, which limits their
+/// viability to about five days after collection.
+pub const PLATELET_MAX_SHELF_LIFE_DAYS: u64 = 5;
+
+/// Maximum shelf life for frozen components (plasma, cryoprecipitate) in days.
+///
+/// Fresh frozen plasma and cryoprecipitate stored at or below -18 °C remain
+/// usable for up to one year.
+pub const FROZEN_MAX_SHELF_LIFE_DAYS: u64 = 365;
+
+/// Minimum quantity for a cryoprecipitate unit in milliliters.
+///
+/// A single cryoprecipitate unit is typically 10-20ml, well below the 50ml
+/// floor used for other components.
+pub const CRYO_MIN_QUANTITY_ML: u32 = 10;
+
 /// Seconds per day constant for timestamp calculations.
 ///
 /// Used to convert day-based shelf life limits into Unix timestamp offsets.
@@ -58,6 +76,12 @@ pub const MAX_REQUEST_ML: u32 = 5000;
 /// transaction timeouts and excessive gas costs.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Maximum number of child units a whole-blood unit can be split into.
+///
+/// Fractionation yields at most red cells, plasma, platelets and
+/// cryoprecipitate from a single donation.
+pub const MAX_SPLIT_CHILDREN: u32 = 4;
+
 /// Maximum number of units that can be batch-expired in a single contract call.
 ///
 /// Enforced to stay within Soroban's per-transaction compute unit budget.
@@ -65,6 +89,43 @@ pub const MAX_BATCH_SIZE: u32 = 100;
 /// storage writes required for status changes and history records.
 pub const MAX_BATCH_EXPIRY_SIZE: u32 = 50;
 
+// ── QUERY PAGINATION ──────────────────────────────────────────────────────────
+
+/// Maximum number of items returned by a single page of a list query.
+///
+/// Every list endpoint is cursor-paginated so a large bank's inventory can be
+/// walked across several calls without exceeding per-invocation read limits.
+/// A requested `limit` of 0 or above this value is clamped to it.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Number of consecutive unit IDs sharing one bucket of a secondary index.
+///
+/// The status, bank, hospital and donor indexes are split into buckets of
+/// this many IDs, so every index write rewrites at most one small ledger
+/// entry no matter how many units the registry has ever held.
+pub const INDEX_BUCKET_SIZE: u64 = 128;
+
+/// Maximum number of index buckets a single list query reads.
+///
+/// Sparse indexes (a hospital with a handful of units spread over the whole
+/// ID range) would otherwise need a read per empty bucket. Once this many
+/// buckets have been read the page is returned early with a cursor.
+pub const MAX_INDEX_BUCKETS_PER_PAGE: u32 = 16;
+
+/// Maximum number of index entries `query_by_blood_type` reads per page.
+///
+/// A selective `min_quantity` could otherwise read every available unit of a
+/// blood type to fill one page. Once this many entries have been read the
+/// page is returned early with a cursor, even if it holds fewer items.
+pub const MAX_UNITS_SCANNED_PER_PAGE: u32 = 200;
+
+/// Width in seconds of one bucket of the time-ordered work queues.
+///
+/// The request priority and deadline queues and the pending transfer queue
+/// keep the entries falling in the same hour together in one ledger entry,
+/// so an insert or removal rewrites only the entries sharing its hour.
+pub const QUEUE_BUCKET_SECONDS: u64 = 3_600;
+
 // ── CUSTODY TRANSFER SETTINGS ─────────────────────────────────────────────────
 
 /// Transfer expiry window in seconds (30 minutes).
@@ -96,6 +157,31 @@ pub const MAX_UNIT_ID_LENGTH: u32 = 64;
 /// Used for custody event IDs and other cryptographic identifiers.
 pub const HEX_HASH_LENGTH: usize = 64;
 
+/// Version byte prefixed to the custody event ID hash preimage.
+///
+/// Version 1 hashes the canonical XDR of both custodian addresses plus a
+/// per-unit nonce. Earlier IDs hashed host object handles and carry no version
+/// byte; they stay stored under their original strings.
+pub const EVENT_ID_VERSION: u8 = 1;
+
+// ── DISPUTE EVIDENCE ──────────────────────────────────────────────────────────
+
+/// Default dispute response window in seconds (48 hours).
+///
+/// After a dispute is raised the counterparty has this long to submit
+/// evidence. Resolution waits until the window closes or both parties have
+/// submitted. Admins can change the window for newly raised disputes.
+pub const DISPUTE_RESPONSE_SECONDS: u64 = 172_800;
+
+// ── QUARANTINE REVIEW ─────────────────────────────────────────────────────────
+
+/// Default quarantine decision window in seconds (72 hours).
+///
+/// A quarantine case not released or discarded within this window is escalated
+/// to discard by `escalate_overdue_quarantines`. Admins can change the window
+/// for newly opened cases.
+pub const QUARANTINE_REVIEW_SECONDS: u64 = 259_200;
+
 // ── SUPER ADMIN NOMINATION ────────────────────────────────────────────────────
 
 /// Nomination expiry window in seconds (24 hours).
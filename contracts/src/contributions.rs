@@ -0,0 +1,215 @@
+//! # contributions
+//!
+//! Per-bank shares of a blood request backing `approve_request`,
+//! `fulfill_request` and `get_request_contributions`.
+//!
+//! Several banks may commit units to the same request. Each bank's units,
+//! committed volume and delivery are kept as a [`BankContribution`] in the
+//! `DataKey::RequestContributions(request_id)` list, in the order banks first
+//! committed. The request's `reserved_unit_ids` and `fulfilled_quantity_ml`
+//! are aggregates over all contributions, and the request is fulfilled once
+//! every contributing bank has delivered. Each bank is paid from its own
+//! escrowed payment, keyed by `DataKey::RequestPayment(request_id, bank_id)`.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::{DataKey, Error};
+
+/// Units one bank committed to a request and what it has delivered
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BankContribution {
+    pub bank_id: Address,
+    pub unit_ids: Vec<u64>,
+    /// Volume of the committed units
+    pub quantity_ml: u32,
+    pub delivered_quantity_ml: u32,
+    pub delivered_at: Option<u64>,
+}
+
+/// Contributions to `request_id`, in commit order
+pub fn get_contributions(env: &Env, request_id: u64) -> Vec<BankContribution> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RequestContributions(request_id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn put_contributions(env: &Env, request_id: u64, contributions: &Vec<BankContribution>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::RequestContributions(request_id), contributions);
+}
+
+/// Position of `bank_id`'s contribution, if it has one
+fn position(contributions: &Vec<BankContribution>, bank_id: &Address) -> Option<u32> {
+    contributions
+        .iter()
+        .position(|contribution| contribution.bank_id == *bank_id)
+        .map(|index| index as u32)
+}
+
+/// Volume committed across all contributions
+pub fn committed_total(contributions: &Vec<BankContribution>) -> Result<u32, Error> {
+    contributions.iter().try_fold(0u32, |total, contribution| {
+        total
+            .checked_add(contribution.quantity_ml)
+            .ok_or(Error::ArithmeticError)
+    })
+}
+
+/// Volume delivered across all contributions
+pub fn delivered_total(contributions: &Vec<BankContribution>) -> Result<u32, Error> {
+    contributions.iter().try_fold(0u32, |total, contribution| {
+        total
+            .checked_add(contribution.delivered_quantity_ml)
+            .ok_or(Error::ArithmeticError)
+    })
+}
+
+/// Whether `bank_id` has delivered its contribution to `request_id`
+pub fn has_delivered(env: &Env, request_id: u64, bank_id: &Address) -> bool {
+    let contributions = get_contributions(env, request_id);
+    position(&contributions, bank_id)
+        .and_then(|index| contributions.get(index))
+        .is_some_and(|contribution| contribution.delivered_at.is_some())
+}
+
+/// Whether a bank other than `bank_id` has committed to `request_id`, in
+/// which case a dispute over `bank_id`'s payment leaves the request as it is
+pub fn has_other_banks(env: &Env, request_id: u64, bank_id: &Address) -> bool {
+    get_contributions(env, request_id)
+        .iter()
+        .any(|contribution| contribution.bank_id != *bank_id)
+}
+
+/// Fail with `InvalidQuantity` if `unit_ids` is empty and with
+/// `DuplicateRegistration` if it lists a unit twice, so a contribution's
+/// volume always matches the units behind it
+pub fn ensure_distinct_units(unit_ids: &Vec<u64>) -> Result<(), Error> {
+    if unit_ids.is_empty() {
+        return Err(Error::InvalidQuantity);
+    }
+    for i in 1..unit_ids.len() {
+        if unit_ids.slice(0..i).contains(unit_ids.get_unchecked(i)) {
+            return Err(Error::DuplicateRegistration);
+        }
+    }
+    Ok(())
+}
+
+/// Add `unit_ids` totalling `quantity_ml` to `bank_id`'s contribution and
+/// return the volume now committed across all banks.
+///
+/// Fails with `InvalidStatus` if the bank has already delivered.
+pub fn commit(
+    env: &Env,
+    request_id: u64,
+    bank_id: &Address,
+    unit_ids: &Vec<u64>,
+    quantity_ml: u32,
+) -> Result<u32, Error> {
+    let mut contributions = get_contributions(env, request_id);
+
+    match position(&contributions, bank_id) {
+        Some(index) => {
+            let mut contribution = contributions.get_unchecked(index);
+            if contribution.delivered_at.is_some() {
+                return Err(Error::InvalidStatus);
+            }
+            contribution.unit_ids.append(unit_ids);
+            contribution.quantity_ml = contribution
+                .quantity_ml
+                .checked_add(quantity_ml)
+                .ok_or(Error::ArithmeticError)?;
+            contributions.set(index, contribution);
+        }
+        None => contributions.push_back(BankContribution {
+            bank_id: bank_id.clone(),
+            unit_ids: unit_ids.clone(),
+            quantity_ml,
+            delivered_quantity_ml: 0,
+            delivered_at: None,
+        }),
+    }
+
+    let total = committed_total(&contributions)?;
+    put_contributions(env, request_id, &contributions);
+    Ok(total)
+}
+
+/// Record `bank_id`'s delivery of exactly its committed `unit_ids` and return
+/// the updated contributions.
+///
+/// Fails with `InvalidStatus` if the bank has no open contribution or
+/// `unit_ids` differs from what it committed.
+pub fn deliver(
+    env: &Env,
+    request_id: u64,
+    bank_id: &Address,
+    unit_ids: &Vec<u64>,
+    delivered_quantity_ml: u32,
+) -> Result<Vec<BankContribution>, Error> {
+    let mut contributions = get_contributions(env, request_id);
+    let index = position(&contributions, bank_id).ok_or(Error::InvalidStatus)?;
+    let mut contribution = contributions.get_unchecked(index);
+
+    if contribution.delivered_at.is_some() || contribution.unit_ids != *unit_ids {
+        return Err(Error::InvalidStatus);
+    }
+
+    contribution.delivered_quantity_ml = delivered_quantity_ml;
+    contribution.delivered_at = Some(env.ledger().timestamp());
+    contributions.set(index, contribution);
+
+    put_contributions(env, request_id, &contributions);
+    Ok(contributions)
+}
+
+/// Remove `unit_id` and its `quantity_ml` from whichever contribution holds
+/// it, dropping the contribution if no units remain, and return the volume
+/// still committed across all banks.
+pub fn withdraw(env: &Env, request_id: u64, unit_id: u64, quantity_ml: u32) -> Result<u32, Error> {
+    let mut contributions = get_contributions(env, request_id);
+
+    let held_by = contributions
+        .iter()
+        .position(|contribution| contribution.unit_ids.contains(unit_id));
+    if let Some(index) = held_by.map(|index| index as u32) {
+        let mut contribution = contributions.get_unchecked(index);
+        if let Some(pos) = contribution.unit_ids.first_index_of(unit_id) {
+            contribution.unit_ids.remove(pos);
+        }
+        contribution.quantity_ml = contribution.quantity_ml.saturating_sub(quantity_ml);
+        if contribution.unit_ids.is_empty() {
+            contributions.remove(index);
+        } else {
+            contributions.set(index, contribution);
+        }
+        put_contributions(env, request_id, &contributions);
+    }
+
+    committed_total(&contributions)
+}
+
+/// Drop the contributions of banks that have not delivered, once the request
+/// is cancelled or expired. Delivered shares stay on record.
+pub fn release_undelivered(env: &Env, request_id: u64) {
+    let contributions = get_contributions(env, request_id);
+    let mut delivered = Vec::new(env);
+    for contribution in contributions.iter() {
+        if contribution.delivered_at.is_some() {
+            delivered.push_back(contribution);
+        }
+    }
+    if delivered.len() == contributions.len() {
+        return;
+    }
+    if delivered.is_empty() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RequestContributions(request_id));
+    } else {
+        put_contributions(env, request_id, &delivered);
+    }
+}
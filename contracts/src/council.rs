@@ -0,0 +1,181 @@
+//! # council
+//!
+//! M-of-N admin council backing `set_admin_council`, `propose_action`,
+//! `approve_proposal` and `get_proposal`.
+//!
+//! Once a council is configured, sensitive admin operations (registration,
+//! suspension and deregistration of banks, hospitals and couriers, dispute
+//! resolution and arbitration settings, fee, crossmatch, suspension,
+//! component, stale-transfer and quarantine policies, unit storage
+//! migration, super admin nomination and council changes) no longer accept
+//! the single `ADMIN` key. A council member proposes a [`CouncilAction`], other members
+//! approve it, and it executes in the call that brings approvals up to the
+//! threshold. Proposals not executed within the council's `proposal_ttl`
+//! expire.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Vec};
+
+use crate::crossmatch::CrossmatchPolicy;
+use crate::institutions::SuspensionPolicy;
+use crate::payments::{DisputeStatus, FeeStructure};
+use crate::{BloodComponent, ComponentPolicy, DataKey, Error, StaleTransferPolicy};
+
+/// Council members and the approvals needed to execute a proposal
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdminCouncil {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+    /// Seconds a proposal stays open for approval
+    pub proposal_ttl: u64,
+}
+
+/// Privileged operation a proposal executes
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CouncilAction {
+    RegisterBloodBank(Address),
+    RegisterHospital(Address),
+    /// Resolve a dispute with a whole outcome: (dispute_id, resolution)
+    ResolveDispute(u64, DisputeStatus),
+    /// Migrate up to this many legacy units
+    MigrateUnitStorage(u32),
+    /// Replace the council: (members, threshold, proposal_ttl); no members
+    /// hands control back to the single admin key
+    SetCouncil(Vec<Address>, u32, u64),
+    /// Suspend a blood bank or hospital: (institution, reason)
+    SuspendInstitution(Address, String),
+    /// Lift a suspension: (institution, reason)
+    ReinstateInstitution(Address, String),
+    /// Remove a blood bank or hospital: (institution, reason)
+    DeregisterInstitution(Address, String),
+    /// Fee schedule for new payments: (fee_structure, treasury)
+    SetFeePolicy(FeeStructure, Address),
+    /// Arbitration panel for new disputes: (arbiters, threshold, vote_window,
+    /// default_outcome); no arbiters returns disputes to the council
+    SetArbitrationPanel(Vec<Address>, u32, u64, DisputeStatus),
+    /// Evidence response window for new disputes, in seconds
+    SetDisputeResponseWindow(u64),
+    SetCrossmatchPolicy(CrossmatchPolicy),
+    SetSuspensionPolicy(SuspensionPolicy),
+    SetComponentPolicy(BloodComponent, ComponentPolicy),
+    SetStaleTransferPolicy(StaleTransferPolicy),
+    /// Decision window for new quarantine cases, in seconds
+    SetQuarantineReviewWindow(u64),
+    RegisterCourier(Address),
+    /// Start the two-step hand-over of the `ADMIN` key
+    NominateSuperAdmin(Address),
+}
+
+/// Council proposal awaiting or past execution
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub action: CouncilAction,
+    /// Members who approved, proposer first
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub executed: bool,
+}
+
+/// The configured council, if any
+pub fn get_council(env: &Env) -> Option<AdminCouncil> {
+    env.storage().instance().get(&DataKey::AdminCouncil)
+}
+
+/// Fail with `CouncilApprovalRequired` while a council governs sensitive
+/// operations, so the single admin key cannot perform them directly
+pub fn ensure_no_council(env: &Env) -> Result<(), Error> {
+    if get_council(env).is_some() {
+        return Err(Error::CouncilApprovalRequired);
+    }
+    Ok(())
+}
+
+/// Validate and store a council; an empty member list removes it
+pub fn set_council(
+    env: &Env,
+    members: Vec<Address>,
+    threshold: u32,
+    proposal_ttl: u64,
+) -> Result<(), Error> {
+    if members.is_empty() {
+        env.storage().instance().remove(&DataKey::AdminCouncil);
+    } else {
+        if threshold == 0 || threshold > members.len() || proposal_ttl == 0 {
+            return Err(Error::InvalidQuantity);
+        }
+        let council = AdminCouncil {
+            members,
+            threshold,
+            proposal_ttl,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::AdminCouncil, &council);
+    }
+
+    env.events().publish(
+        (symbol_short!("council"), symbol_short!("set")),
+        get_council(env),
+    );
+
+    Ok(())
+}
+
+/// Load a proposal by ID
+pub fn get_proposal(env: &Env, proposal_id: u64) -> Result<Proposal, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .ok_or(Error::RecordNotFound)
+}
+
+/// Store a new proposal approved by its proposer and return it
+pub fn create_proposal(
+    env: &Env,
+    council: &AdminCouncil,
+    proposer: Address,
+    action: CouncilAction,
+) -> Proposal {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextProposalId)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextProposalId, &(id + 1));
+
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let proposal = Proposal {
+        id,
+        proposer,
+        action,
+        approvals,
+        created_at: now,
+        expires_at: now.saturating_add(council.proposal_ttl),
+        executed: false,
+    };
+    put_proposal(env, &proposal);
+
+    env.events().publish(
+        (symbol_short!("council"), symbol_short!("propose")),
+        proposal.clone(),
+    );
+
+    proposal
+}
+
+/// Persist `proposal`
+pub fn put_proposal(env: &Env, proposal: &Proposal) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Proposal(proposal.id), proposal);
+}
@@ -0,0 +1,129 @@
+//! # crossmatch
+//!
+//! Pre-transfusion crossmatch attestations backing `attest_crossmatch`,
+//! `get_crossmatch` and `set_crossmatch_policy`.
+//!
+//! The requesting hospital, or a lab delegate holding
+//! `DelegatePermission::AttestCrossmatch`, records one
+//! [`CrossmatchAttestation`] per unit per request while the unit is still
+//! reserved at the bank. An incompatible result returns the unit to the
+//! bank's available stock. The [`CrossmatchPolicy`] decides whether
+//! `confirm_transfer` and `fulfill_request` refuse units of `Critical` or
+//! `Urgent` requests that lack a compatible attestation. `approve_request`
+//! links each reserved unit to its request so `confirm_transfer`, which only
+//! sees the unit, can find it; cancelling or expiring the request drops the
+//! links again.
+
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map};
+
+use crate::{BloodRequest, DataKey, Error, RequestStatus, UrgencyLevel, REQUESTS};
+
+/// Outcome of a crossmatch between a unit and the patient's sample
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrossmatchResult {
+    Compatible,
+    Incompatible,
+}
+
+/// Lab record of a crossmatch for one unit against one request
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrossmatchAttestation {
+    pub request_id: u64,
+    pub unit_id: u64,
+    pub result: CrossmatchResult,
+    /// Hospital or lab delegate that attested
+    pub lab: Address,
+    pub tested_at: u64,
+    /// SHA-256 of the lab report kept off-chain
+    pub report_digest: BytesN<32>,
+    pub recorded_at: u64,
+}
+
+/// Where a compatible crossmatch is required for Critical and Urgent requests
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CrossmatchPolicy {
+    pub require_on_transfer: bool,
+    pub require_on_fulfillment: bool,
+}
+
+/// Attestation for `unit_id` against `request_id`, if any
+pub fn get_attestation(env: &Env, request_id: u64, unit_id: u64) -> Option<CrossmatchAttestation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Crossmatch(request_id, unit_id))
+}
+
+pub fn put_attestation(env: &Env, attestation: &CrossmatchAttestation) {
+    env.storage().persistent().set(
+        &DataKey::Crossmatch(attestation.request_id, attestation.unit_id),
+        attestation,
+    );
+}
+
+/// Current policy; nothing is required by default
+pub fn policy(env: &Env) -> CrossmatchPolicy {
+    env.storage()
+        .instance()
+        .get(&DataKey::CrossmatchPolicy)
+        .unwrap_or_default()
+}
+
+/// Record that `unit_id` is reserved for `request_id`
+pub fn link_unit(env: &Env, unit_id: u64, request_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::UnitRequest(unit_id), &request_id);
+}
+
+pub fn unlink_unit(env: &Env, unit_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::UnitRequest(unit_id));
+}
+
+/// Drop the links of `request`'s reserved units that still point at it,
+/// once the request is cancelled or expired
+pub fn unlink_request(env: &Env, request: &BloodRequest) {
+    for unit_id in request.reserved_unit_ids.iter() {
+        if linked_request_id(env, unit_id) == Some(request.id) {
+            unlink_unit(env, unit_id);
+        }
+    }
+}
+
+/// Request `unit_id` was reserved for through `approve_request`, if any
+pub fn linked_request_id(env: &Env, unit_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitRequest(unit_id))
+}
+
+/// Open request `unit_id` is currently reserved for, if any
+pub fn linked_request(env: &Env, unit_id: u64) -> Option<BloodRequest> {
+    let request_id = linked_request_id(env, unit_id)?;
+    let requests: Map<u64, BloodRequest> = env.storage().persistent().get(&REQUESTS)?;
+    requests.get(request_id).filter(|request| {
+        matches!(
+            request.status,
+            RequestStatus::Approved | RequestStatus::InProgress
+        ) && request.reserved_unit_ids.contains(unit_id)
+    })
+}
+
+/// Fail with `CrossmatchRequired` if `request` is Critical or Urgent and
+/// `unit_id` has no compatible attestation against it
+pub fn ensure_passed(env: &Env, request: &BloodRequest, unit_id: u64) -> Result<(), Error> {
+    if !matches!(
+        request.urgency,
+        UrgencyLevel::Critical | UrgencyLevel::Urgent
+    ) {
+        return Ok(());
+    }
+    match get_attestation(env, request.id, unit_id) {
+        Some(attestation) if attestation.result == CrossmatchResult::Compatible => Ok(()),
+        _ => Err(Error::CrossmatchRequired),
+    }
+}
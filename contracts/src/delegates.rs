@@ -0,0 +1,188 @@
+//! # delegates
+//!
+//! Staff delegates backing `add_delegate`, `revoke_delegate`, `get_delegate`
+//! and `get_delegates`.
+//!
+//! A registered blood bank or hospital grants individual staff addresses a
+//! [`Delegation`] with a set of [`DelegatePermission`]s and an optional
+//! expiry. Entry points taking a bank or hospital address also accept one of
+//! its delegates: [`acting_for`] resolves the signer to its institution, and
+//! the delegate itself is recorded as the actor in status history. A delegate
+//! serves a single institution until revoked, and a registered institution is
+//! never a delegate: grants to one are rejected, and registering an address
+//! revokes any delegation it held.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+use crate::{institutions, DataKey, Error};
+
+/// Action a delegate may take on behalf of its institution
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DelegatePermission {
+    /// `register_blood`, `batch_register_blood`, `split_unit`
+    RegisterUnits,
+    /// `allocate_blood`, `batch_allocate_blood`, `cancel_allocation`,
+    /// `approve_request`
+    Allocate,
+    /// `confirm_transfer`, `confirm_delivery`, `attach_proof_bundle`
+    ConfirmDelivery,
+    /// `raise_dispute`
+    RaiseDispute,
+    /// `attest_crossmatch`
+    AttestCrossmatch,
+}
+
+/// Permissions granted to a staff address by an institution
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Delegation {
+    pub delegate: Address,
+    /// Blood bank or hospital the delegate acts for
+    pub institution: Address,
+    pub permissions: Vec<DelegatePermission>,
+    pub granted_at: u64,
+    /// Ledger timestamp from which the delegation no longer applies
+    pub expires_at: Option<u64>,
+}
+
+impl Delegation {
+    /// Whether the delegation allows `permission` at `now`
+    pub fn allows(&self, permission: DelegatePermission, now: u64) -> bool {
+        self.expires_at.is_none_or(|expiry| now < expiry) && self.permissions.contains(permission)
+    }
+}
+
+/// Load the delegation held by `delegate`, if any
+pub fn get_delegation(env: &Env, delegate: &Address) -> Option<Delegation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Delegate(delegate.clone()))
+}
+
+/// Delegate addresses registered by `institution`, revoked ones excluded
+pub fn get_delegates(env: &Env, institution: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::InstitutionDelegates(institution.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Resolve the institution `caller` acts for when exercising `permission`.
+///
+/// A delegate acts for its institution and fails with `Unauthorized` when the
+/// delegation has expired or lacks `permission`; any other address acts for
+/// itself. The caller must have authorized the enclosing call.
+pub fn acting_for(
+    env: &Env,
+    caller: &Address,
+    permission: DelegatePermission,
+) -> Result<Address, Error> {
+    match get_delegation(env, caller) {
+        Some(delegation) => {
+            if !delegation.allows(permission, env.ledger().timestamp()) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(delegation.institution)
+        }
+        None => Ok(caller.clone()),
+    }
+}
+
+/// Grant or replace `delegate`'s permissions for `institution`.
+///
+/// Fails with `DuplicateRegistration` if the delegate is itself a registered
+/// blood bank or hospital, or already acts for a different institution.
+pub fn grant(
+    env: &Env,
+    institution: Address,
+    delegate: Address,
+    permissions: Vec<DelegatePermission>,
+    expires_at: Option<u64>,
+) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+    if permissions.is_empty() {
+        return Err(Error::InvalidQuantity);
+    }
+    if expires_at.is_some_and(|expiry| expiry <= now) {
+        return Err(Error::InvalidExpiration);
+    }
+    // Registered institutions act under their own authority only
+    if institutions::is_registered(env, &delegate) {
+        return Err(Error::DuplicateRegistration);
+    }
+
+    match get_delegation(env, &delegate) {
+        Some(existing) if existing.institution != institution => {
+            return Err(Error::DuplicateRegistration);
+        }
+        Some(_) => {}
+        None => {
+            let mut delegates = get_delegates(env, &institution);
+            delegates.push_back(delegate.clone());
+            env.storage().persistent().set(
+                &DataKey::InstitutionDelegates(institution.clone()),
+                &delegates,
+            );
+        }
+    }
+
+    let delegation = Delegation {
+        delegate: delegate.clone(),
+        institution,
+        permissions,
+        granted_at: now,
+        expires_at,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Delegate(delegate), &delegation);
+
+    env.events().publish(
+        (symbol_short!("delegate"), symbol_short!("grant")),
+        delegation,
+    );
+
+    Ok(())
+}
+
+/// Revoke `delegate`'s delegation from `institution`.
+///
+/// Fails with `RecordNotFound` if the delegate does not act for `institution`.
+pub fn revoke(env: &Env, institution: Address, delegate: Address) -> Result<(), Error> {
+    match get_delegation(env, &delegate) {
+        Some(existing) if existing.institution == institution => {}
+        _ => return Err(Error::RecordNotFound),
+    }
+
+    remove(env, institution, delegate);
+    Ok(())
+}
+
+/// Revoke whatever delegation `delegate` holds, called when it registers as
+/// a blood bank or hospital in its own right
+pub(crate) fn revoke_on_registration(env: &Env, delegate: &Address) {
+    if let Some(existing) = get_delegation(env, delegate) {
+        remove(env, existing.institution, delegate.clone());
+    }
+}
+
+fn remove(env: &Env, institution: Address, delegate: Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Delegate(delegate.clone()));
+
+    let mut delegates = get_delegates(env, &institution);
+    if let Some(index) = delegates.first_index_of(&delegate) {
+        delegates.remove(index);
+    }
+    env.storage().persistent().set(
+        &DataKey::InstitutionDelegates(institution.clone()),
+        &delegates,
+    );
+
+    env.events().publish(
+        (symbol_short!("delegate"), symbol_short!("revoke")),
+        (institution, delegate, env.ledger().timestamp()),
+    );
+}
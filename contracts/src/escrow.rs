@@ -0,0 +1,235 @@
+//! # escrow
+//!
+//! Token custody backing `create_payment`, `release_escrow`, `resolve_dispute`
+//! and `fulfill_request`.
+//!
+//! Funds move through the Stellar token interface on the payment's `asset`.
+//! At creation the payer's gross amount is transferred into the contract and
+//! an [`EscrowAccount`] is stored under `DataKey::Escrow(payment_id)`. A payout
+//! sends the net `Payment::amount` to the payee and the `FeeStructure` total to
+//! the configured treasury; a refund returns everything still locked to the
+//! payer. A settlement splits the escrow: the payee's share of the net amount,
+//! the same proportion of the fees to the treasury, and the rest back to the
+//! payer. The escrow record is kept after release with `locked_amount` at zero.
+
+use soroban_sdk::{symbol_short, token, Address, Env, Map, Symbol};
+
+use crate::payments::{
+    EscrowAccount, FeeStructure, Payment, PaymentError, PaymentStatus, ProofBundle,
+};
+use crate::{DataKey, Error, PAYMENTS};
+
+/// Fee schedule applied to new payments; all zero until configured
+pub fn fee_structure(env: &Env) -> FeeStructure {
+    env.storage()
+        .instance()
+        .get(&DataKey::FeeStructure)
+        .unwrap_or(FeeStructure {
+            policy_id: Symbol::new(env, "default_fee_policy"),
+            service_fee: 0,
+            network_fee: 0,
+            performance_bonus: 0,
+            fixed_fee: 0,
+        })
+}
+
+/// Address receiving fee deductions, if configured
+pub fn treasury(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Treasury)
+}
+
+/// Load a payment by ID
+pub fn get_payment(env: &Env, payment_id: u64) -> Result<Payment, Error> {
+    let payments: Map<u64, Payment> = env
+        .storage()
+        .persistent()
+        .get(&PAYMENTS)
+        .ok_or(Error::PaymentNotFound)?;
+    payments.get(payment_id).ok_or(Error::PaymentNotFound)
+}
+
+/// Persist `payment` into the payments map
+pub fn put_payment(env: &Env, payment: &Payment) {
+    let mut payments: Map<u64, Payment> = env
+        .storage()
+        .persistent()
+        .get(&PAYMENTS)
+        .unwrap_or(Map::new(env));
+    payments.set(payment.id, payment.clone());
+    env.storage().persistent().set(&PAYMENTS, &payments);
+}
+
+/// Load the escrow account for a payment
+pub fn get_escrow(env: &Env, payment_id: u64) -> Result<EscrowAccount, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Escrow(payment_id))
+        .ok_or(Error::PaymentNotFound)
+}
+
+/// Load the proof bundle attached to a payment, if any
+pub fn get_proof_bundle(env: &Env, payment_id: u64) -> Option<ProofBundle> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProofBundle(payment_id))
+}
+
+/// Check `payment`'s release conditions, mapping the first unmet one to its
+/// contract error.
+pub fn check_release(env: &Env, payment_id: u64, approver: Option<&Address>) -> Result<(), Error> {
+    let escrow = get_escrow(env, payment_id)?;
+    let bundle = get_proof_bundle(env, payment_id);
+    escrow
+        .check_release(env.ledger().timestamp(), approver, bundle.as_ref())
+        .map_err(|err| match err {
+            PaymentError::ReleaseTooEarly => Error::ReleaseTooEarly,
+            PaymentError::MedicalRecordsUnverified => Error::MedicalRecordsUnverified,
+            PaymentError::ApproverMismatch => Error::ApproverMismatch,
+            PaymentError::ProofBundleMissing => Error::ProofBundleMissing,
+            PaymentError::ProofBundleInvalid => Error::ProofBundleInvalid,
+            _ => Error::InvalidPaymentStatus,
+        })
+}
+
+/// Transfer `escrow.locked_amount` from the payer into contract custody.
+///
+/// The payer must have authorized the enclosing call.
+pub fn lock(env: &Env, payment: &Payment, escrow: &EscrowAccount) {
+    token::Client::new(env, &payment.asset).transfer(
+        &payment.payer,
+        &env.current_contract_address(),
+        &escrow.locked_amount,
+    );
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(payment.id), escrow);
+
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("locked")),
+        (payment.id, payment.payer.clone(), escrow.locked_amount),
+    );
+}
+
+/// Pay the payee its net amount and the treasury its fees, completing `payment`.
+pub fn payout(env: &Env, payment: &mut Payment) -> Result<(), Error> {
+    if !payment.can_transition_to(PaymentStatus::Completed) {
+        return Err(Error::InvalidPaymentStatus);
+    }
+
+    let mut escrow = get_escrow(env, payment.id)?;
+    let fees = payment.fee_structure.total();
+    let client = token::Client::new(env, &payment.asset);
+    let contract = env.current_contract_address();
+
+    client.transfer(&contract, &payment.payee, &payment.amount);
+    if fees > 0 {
+        let treasury = treasury(env).ok_or(Error::StorageError)?;
+        client.transfer(&contract, &treasury, &fees);
+    }
+
+    escrow.locked_amount = 0;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(payment.id), &escrow);
+
+    payment.status = PaymentStatus::Completed;
+    payment.escrow_released_at = Some(env.ledger().timestamp());
+    put_payment(env, payment);
+
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("payout")),
+        (payment.id, payment.payee.clone(), payment.amount, fees),
+    );
+
+    Ok(())
+}
+
+/// Split the escrow for `payment`, paying `payee_amount` of the net amount to
+/// the payee and returning the remainder to the payer.
+///
+/// Fees are charged in proportion to the payee's share. Returns the amount
+/// refunded to the payer.
+pub fn settle(env: &Env, payment: &mut Payment, payee_amount: i128) -> Result<i128, Error> {
+    if !payment.can_transition_to(PaymentStatus::Settled) {
+        return Err(Error::InvalidPaymentStatus);
+    }
+
+    let mut escrow = get_escrow(env, payment.id)?;
+    let fee_share = if payment.amount > 0 {
+        payment
+            .fee_structure
+            .total()
+            .checked_mul(payee_amount)
+            .ok_or(Error::ArithmeticError)?
+            / payment.amount
+    } else {
+        0
+    };
+    let payer_amount = escrow
+        .locked_amount
+        .checked_sub(payee_amount)
+        .and_then(|rest| rest.checked_sub(fee_share))
+        .ok_or(Error::ArithmeticError)?;
+
+    let client = token::Client::new(env, &payment.asset);
+    let contract = env.current_contract_address();
+    if payee_amount > 0 {
+        client.transfer(&contract, &payment.payee, &payee_amount);
+    }
+    if fee_share > 0 {
+        let treasury = treasury(env).ok_or(Error::StorageError)?;
+        client.transfer(&contract, &treasury, &fee_share);
+    }
+    if payer_amount > 0 {
+        client.transfer(&contract, &payment.payer, &payer_amount);
+    }
+
+    escrow.locked_amount = 0;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(payment.id), &escrow);
+
+    payment.status = PaymentStatus::Settled;
+    payment.escrow_released_at = Some(env.ledger().timestamp());
+    put_payment(env, payment);
+
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("settle")),
+        (payment.id, payee_amount, payer_amount, fee_share),
+    );
+
+    Ok(payer_amount)
+}
+
+/// Return everything still locked for `payment` to the payer.
+///
+/// Returns the amount refunded.
+pub fn refund(env: &Env, payment: &mut Payment) -> Result<i128, Error> {
+    if !payment.can_transition_to(PaymentStatus::Refunded) {
+        return Err(Error::InvalidPaymentStatus);
+    }
+
+    let mut escrow = get_escrow(env, payment.id)?;
+    let refunded = escrow.locked_amount;
+    token::Client::new(env, &payment.asset).transfer(
+        &env.current_contract_address(),
+        &payment.payer,
+        &refunded,
+    );
+
+    escrow.locked_amount = 0;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(payment.id), &escrow);
+
+    payment.status = PaymentStatus::Refunded;
+    payment.escrow_released_at = Some(env.ledger().timestamp());
+    put_payment(env, payment);
+
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("refund")),
+        (payment.id, payment.payer.clone(), refunded),
+    );
+
+    Ok(refunded)
+}
@@ -0,0 +1,171 @@
+//! # institutions
+//!
+//! Standing of blood banks and hospitals backing `suspend_institution`,
+//! `reinstate_institution`, `deregister_institution` and
+//! `get_institution_standing`.
+//!
+//! An institution with no [`InstitutionStanding`] record is active. A
+//! suspended institution stays registered but fails [`ensure_active`], which
+//! guards every entry point starting new work (registering or allocating
+//! units, opening requests and custody hops). Custody hops already in flight
+//! are governed by the [`SuspensionPolicy`]: frozen hops cannot be accepted,
+//! handed off or confirmed until the institution is reinstated. Deregistration
+//! removes the institution from `BLOOD_BANKS` / `HOSPITALS`; registering it
+//! again clears the deregistered standing.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, String};
+
+use crate::{DataKey, Error, BLOOD_BANKS, HOSPITALS};
+
+/// Current standing of a blood bank or hospital
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstitutionStatus {
+    Active,
+    Suspended,
+    Deregistered,
+}
+
+/// Latest standing change of an institution
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstitutionStanding {
+    pub institution: Address,
+    pub status: InstitutionStatus,
+    pub reason: String,
+    pub changed_at: u64,
+}
+
+/// What happens to custody hops involving a suspended institution
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SuspensionPolicy {
+    /// Hops cannot be accepted, handed off or confirmed while suspended
+    FreezeTransfers,
+    /// Hops opened before the suspension may complete
+    AllowInFlight,
+}
+
+/// Latest standing change of `institution`, if any
+pub fn get_standing(env: &Env, institution: &Address) -> Option<InstitutionStanding> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::InstitutionStanding(institution.clone()))
+}
+
+/// Policy applied to in-flight hops; defaults to `FreezeTransfers`
+pub fn suspension_policy(env: &Env) -> SuspensionPolicy {
+    env.storage()
+        .instance()
+        .get(&DataKey::SuspensionPolicy)
+        .unwrap_or(SuspensionPolicy::FreezeTransfers)
+}
+
+fn is_suspended(env: &Env, institution: &Address) -> bool {
+    get_standing(env, institution)
+        .is_some_and(|standing| standing.status == InstitutionStatus::Suspended)
+}
+
+/// Fail with `InstitutionSuspended` if `institution` is suspended
+pub fn ensure_active(env: &Env, institution: &Address) -> Result<(), Error> {
+    if is_suspended(env, institution) {
+        return Err(Error::InstitutionSuspended);
+    }
+    Ok(())
+}
+
+/// Fail with `InstitutionSuspended` if any of `parties` is suspended and the
+/// suspension policy freezes in-flight transfers
+pub fn ensure_transfer_allowed(env: &Env, parties: &[&Address]) -> Result<(), Error> {
+    if suspension_policy(env) == SuspensionPolicy::AllowInFlight {
+        return Ok(());
+    }
+    if parties.iter().any(|party| is_suspended(env, party)) {
+        return Err(Error::InstitutionSuspended);
+    }
+    Ok(())
+}
+
+/// Whether `institution` is a registered blood bank or hospital
+pub(crate) fn is_registered(env: &Env, institution: &Address) -> bool {
+    [BLOOD_BANKS, HOSPITALS].iter().any(|key| {
+        env.storage()
+            .persistent()
+            .get::<_, Map<Address, bool>>(key)
+            .is_some_and(|registry| registry.get(institution.clone()).unwrap_or(false))
+    })
+}
+
+fn set_standing(env: &Env, institution: Address, status: InstitutionStatus, reason: String) {
+    let standing = InstitutionStanding {
+        institution: institution.clone(),
+        status,
+        reason,
+        changed_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::InstitutionStanding(institution), &standing);
+
+    let action = match status {
+        InstitutionStatus::Active => symbol_short!("reinstate"),
+        InstitutionStatus::Suspended => symbol_short!("suspend"),
+        InstitutionStatus::Deregistered => symbol_short!("dereg"),
+    };
+    env.events()
+        .publish((symbol_short!("inst"), action), standing);
+}
+
+/// Suspend an active, registered institution
+pub fn suspend(env: &Env, institution: Address, reason: String) -> Result<(), Error> {
+    if !is_registered(env, &institution) {
+        return Err(Error::OrganizationNotFound);
+    }
+    if is_suspended(env, &institution) {
+        return Err(Error::InvalidStatus);
+    }
+
+    set_standing(env, institution, InstitutionStatus::Suspended, reason);
+    Ok(())
+}
+
+/// Lift the suspension of `institution`
+pub fn reinstate(env: &Env, institution: Address, reason: String) -> Result<(), Error> {
+    if !is_suspended(env, &institution) {
+        return Err(Error::InvalidStatus);
+    }
+
+    set_standing(env, institution, InstitutionStatus::Active, reason);
+    Ok(())
+}
+
+/// Remove `institution` from the bank and hospital registries
+pub fn deregister(env: &Env, institution: Address, reason: String) -> Result<(), Error> {
+    if !is_registered(env, &institution) {
+        return Err(Error::OrganizationNotFound);
+    }
+
+    for key in [BLOOD_BANKS, HOSPITALS] {
+        let registry: Option<Map<Address, bool>> = env.storage().persistent().get(&key);
+        if let Some(mut registry) = registry {
+            if registry.contains_key(institution.clone()) {
+                registry.remove(institution.clone());
+                env.storage().persistent().set(&key, &registry);
+            }
+        }
+    }
+
+    set_standing(env, institution, InstitutionStatus::Deregistered, reason);
+    Ok(())
+}
+
+/// Clear a deregistered standing when `institution` is registered again
+pub fn clear_deregistration(env: &Env, institution: &Address) {
+    if get_standing(env, institution)
+        .is_some_and(|standing| standing.status == InstitutionStatus::Deregistered)
+    {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::InstitutionStanding(institution.clone()));
+    }
+}
@@ -1,18 +1,69 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Bytes, Env,
-    Map, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Bytes, BytesN,
+    Env, Map, String, Symbol, Vec,
 };
 
+pub mod bucket_queue;
+use crate::bucket_queue::Queue;
+pub mod compatibility;
 pub mod constants;
+pub mod contributions;
+use crate::contributions::BankContribution;
+
+pub mod council;
+use crate::council::{AdminCouncil, CouncilAction, Proposal};
+
+pub mod crossmatch;
+use crate::crossmatch::{CrossmatchAttestation, CrossmatchPolicy, CrossmatchResult};
+
+pub mod delegates;
+use crate::delegates::{DelegatePermission, Delegation};
+
+pub mod escrow;
+pub mod institutions;
+use crate::institutions::{InstitutionStanding, SuspensionPolicy};
+
 pub mod payments;
 use crate::payments::*;
 
+pub mod quarantine;
+use crate::quarantine::{
+    QuarantineCase, QuarantineCasePage, QuarantineCaseStatus, QuarantineEscalationPage,
+};
+
+pub mod records;
+use crate::records::{AccessGrant, PatientRecord};
+
 pub mod registry_read;
+use crate::registry_read::UnitIndex;
 pub mod registry_write;
+
+pub mod request_queue;
+use crate::request_queue::RequestQueuePage;
+#[cfg(test)]
+mod test_compatibility;
+#[cfg(test)]
+mod test_contributions;
+#[cfg(test)]
+mod test_council;
+#[cfg(test)]
+mod test_crossmatch;
+#[cfg(test)]
+mod test_delegates;
+#[cfg(test)]
+mod test_escrow;
+#[cfg(test)]
+mod test_institutions;
 #[cfg(test)]
 mod test_payments;
 #[cfg(test)]
+mod test_quarantine;
+#[cfg(test)]
+mod test_records;
+#[cfg(test)]
+mod test_request_queue;
+#[cfg(test)]
 mod test_storage_layout;
 
 /// Error types for blood registration and transfer
@@ -59,6 +110,48 @@ pub enum Error {
     AlreadyVerified = 27,
     /// Caller is an authorized actor but is not the current custodian of the unit.
     NotCurrentCustodian = 29,
+    /// No health record (or record version) exists for the patient.
+    RecordNotFound = 30,
+    /// The patient has not granted this provider access.
+    AccessGrantNotFound = 31,
+    /// Operation is not valid for this blood component.
+    InvalidComponent = 32,
+    /// Donor has been flagged and is deferred from further donations.
+    DonorDeferred = 33,
+    /// Request already has an escrowed payment.
+    PaymentAlreadyExists = 34,
+    /// Configured fees exceed the payment amount.
+    FeesExceedAmount = 35,
+    /// Escrow release attempted before its minimum timestamp.
+    ReleaseTooEarly = 36,
+    /// Escrow release requires verified medical records.
+    MedicalRecordsUnverified = 37,
+    /// Release approver is missing or not the authorized approver.
+    ApproverMismatch = 38,
+    /// Escrow release requires a proof bundle but none is attached.
+    ProofBundleMissing = 39,
+    /// Attached proof bundle failed validation.
+    ProofBundleInvalid = 40,
+    /// Dispute split is missing, out of range, or given for a non-split outcome.
+    InvalidSplit = 41,
+    /// Arbiter or council member has already voted.
+    AlreadyVoted = 42,
+    /// Dispute vote deadline has passed.
+    VotingClosed = 43,
+    /// Dispute vote deadline has not passed yet.
+    VotingOpen = 44,
+    /// Dispute response window has closed.
+    ResponseWindowClosed = 45,
+    /// Dispute is still collecting evidence from both parties.
+    EvidenceWindowOpen = 46,
+    /// Operation must go through an admin council proposal.
+    CouncilApprovalRequired = 47,
+    /// Council proposal passed its expiry without executing.
+    ProposalExpired = 48,
+    /// Blood bank or hospital is suspended.
+    InstitutionSuspended = 49,
+    /// Unit lacks a compatible crossmatch for its Critical or Urgent request.
+    CrossmatchRequired = 50,
 }
 
 // Alias for issue/docs terminology.
@@ -75,6 +168,32 @@ pub enum BloodComponent {
     Cryoprecipitate,
 }
 
+/// Storage temperature class a blood component must be kept in
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StorageTempClass {
+    /// 2-6 °C (whole blood, red cells)
+    Refrigerated,
+    /// 20-24 °C with agitation (platelets)
+    RoomTemperature,
+    /// -18 °C or colder (plasma, cryoprecipitate)
+    Frozen,
+}
+
+/// Registration rules for one blood component
+///
+/// Defaults come from `registry_read::default_component_policy` until an admin
+/// overrides them with `set_component_policy`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentPolicy {
+    pub min_shelf_life_days: u64,
+    pub max_shelf_life_days: u64,
+    pub min_volume_ml: u32,
+    pub max_volume_ml: u32,
+    pub storage_temp: StorageTempClass,
+}
+
 /// Blood type enumeration
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -100,6 +219,8 @@ pub enum BloodStatus {
     Quarantined,
     Expired,
     Discarded,
+    /// Delivered unit on its way back to its bank or on to another hospital
+    InRetransfer,
 }
 
 /// Quarantine reason categories for explicit on-chain lifecycle records.
@@ -223,6 +344,19 @@ pub enum CustodyStatus {
     Pending,
     Confirmed,
     Cancelled,
+    /// Receiver refused the hop because the cold chain was broken
+    Rejected,
+}
+
+/// What `sweep_expired_transfers` does with the unit of a timed-out hop
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StaleTransferPolicy {
+    /// Revert as `cancel_transfer` would: a bank's hop returns the unit to
+    /// `Reserved`, a courier keeps the unit, a re-transfer returns to `Delivered`
+    Revert,
+    /// Quarantine the unit since its cold chain can no longer be vouched for
+    Quarantine,
 }
 
 /// Request status enumeration
@@ -237,6 +371,8 @@ pub enum RequestStatus {
     Resolved,
     Cancelled,
     Rejected,
+    /// `required_by` passed before the request was fulfilled
+    Expired,
 }
 
 /// Blood request record
@@ -282,6 +418,42 @@ pub struct BloodRegisteredEvent {
     pub registration_timestamp: u64,
 }
 
+/// Deferral recorded when a donor is flagged for a look-back recall
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DonorDeferral {
+    pub bank_id: Address,
+    pub donor_id: Symbol,
+    pub reason: String,
+    pub flagged_by: Address,
+    pub flagged_at: u64,
+    /// Units registered at or after `flagged_at - lookback_window` were recalled
+    pub lookback_window: u64,
+}
+
+/// One batch of a donor look-back recall.
+///
+/// `next_cursor` is `None` once every unit of the donor has been checked;
+/// otherwise pass it to `resume_donor_recall` to continue.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DonorRecallPage {
+    pub quarantined: Vec<u64>,
+    pub next_cursor: Option<u64>,
+}
+
+/// Event data listing a donor's units already delivered to one hospital
+#[contracttype]
+#[derive(Clone)]
+pub struct DonorRecallEvent {
+    pub bank_id: Address,
+    pub donor_id: Symbol,
+    pub hospital: Address,
+    pub unit_ids: Vec<u64>,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
 /// Event data for blood request creation
 #[contracttype]
 #[derive(Clone)]
@@ -329,6 +501,8 @@ pub struct RequestApprovedEvent {
     pub total_quantity_ml: u32,
     pub fulfillment_percentage: u32,
     pub status: RequestStatus,
+    /// Assigned units that differ from the requested type or component
+    pub substitute_unit_ids: Vec<u64>,
 }
 
 /// Event data for request fulfillment
@@ -361,6 +535,8 @@ pub struct DisputeResolvedEvent {
     pub dispute_id: u64,
     pub payment_id: u64,
     pub status: DisputeStatus,
+    pub payee_amount: i128,
+    pub payer_amount: i128,
     pub resolved_at: u64,
 }
 
@@ -373,7 +549,6 @@ const ADMIN_KEY: &str = "ADMIN";
 const REQUESTS_KEY: &str = "REQUESTS";
 const NEXT_REQUEST_ID_KEY: &str = "NEXT_REQ";
 const REQUEST_KEYS_KEY: &str = "REQ_KEYS";
-const BLOOD_REQUESTS_KEY: &str = "REQS";
 const PAYMENTS_KEY: &str = "PAY_RECS";
 const NEXT_PAYMENT_ID_KEY: &str = "NPAY_ID";
 const DISPUTES_KEY: &str = "DISP_REC";
@@ -389,7 +564,6 @@ const _: () = assert!(ADMIN_KEY.len() <= 9);
 const _: () = assert!(REQUESTS_KEY.len() <= 9);
 const _: () = assert!(NEXT_REQUEST_ID_KEY.len() <= 9);
 const _: () = assert!(REQUEST_KEYS_KEY.len() <= 9);
-const _: () = assert!(BLOOD_REQUESTS_KEY.len() <= 9);
 const _: () = assert!(PAYMENTS_KEY.len() <= 9);
 const _: () = assert!(NEXT_PAYMENT_ID_KEY.len() <= 9);
 const _: () = assert!(DISPUTES_KEY.len() <= 9);
@@ -398,6 +572,9 @@ const _: () = assert!(CUSTODY_EVENTS_KEY.len() <= 9);
 const _: () = assert!(HISTORY_KEY.len() <= 9);
 
 /// Storage keys (single source of truth)
+///
+/// `BLOOD_UNITS` is the legacy `Map<u64, BloodUnit>` layout. Units now live under
+/// `DataKey::BloodUnit(id)`; the key is only read by `migrate_unit_storage`.
 pub(crate) const BLOOD_UNITS: Symbol = symbol_short!("UNITS");
 pub(crate) const NEXT_ID: Symbol = symbol_short!("NEXT_ID");
 pub(crate) const BLOOD_BANKS: Symbol = symbol_short!("BANKS");
@@ -406,7 +583,6 @@ pub(crate) const ADMIN: Symbol = symbol_short!("ADMIN");
 pub(crate) const REQUESTS: Symbol = symbol_short!("REQUESTS");
 pub(crate) const NEXT_REQUEST_ID: Symbol = symbol_short!("NEXT_REQ");
 pub(crate) const REQUEST_KEYS: Symbol = symbol_short!("REQ_KEYS");
-pub(crate) const BLOOD_REQUESTS: Symbol = symbol_short!("REQS");
 pub(crate) const PAYMENTS: Symbol = symbol_short!("PAY_RECS");
 pub(crate) const NEXT_PAYMENT_ID: Symbol = symbol_short!("NPAY_ID");
 pub(crate) const DISPUTES: Symbol = symbol_short!("DISP_REC");
@@ -417,14 +593,126 @@ pub(crate) const HISTORY: Symbol = symbol_short!("HISTORY");
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DataKey {
-    /// Donor units index: (bank_id, donor_id) -> Vec<u64>
-    DonorUnits(Address, Symbol),
+    /// Blood unit record: unit_id -> BloodUnit
+    BloodUnit(u64),
+    /// Donor units index bucket: (bank_id, donor_id, bucket) -> Vec<u64>
+    DonorUnits(Address, Symbol, u64),
+    /// Donor units index bucket across all banks: (donor_id, bucket) -> Vec<u64>
+    DonorAllUnits(Symbol, u64),
+    /// Status index bucket: (status, bucket) -> Vec<u64> (ascending unit IDs)
+    StatusUnits(BloodStatus, u64),
+    /// Bank index bucket: (bank_id, bucket) -> Vec<u64> (ascending unit IDs)
+    BankUnits(Address, u64),
+    /// Hospital index bucket: (recipient hospital, bucket) -> Vec<u64> (ascending unit IDs)
+    HospitalUnits(Address, u64),
+    /// Available units per blood type expiring on one day, ordered for FIFO:
+    /// (blood_type, expiry day) -> Vec<(expiration, unit_id)>
+    AvailableByExpiry(BloodType, u64),
+    /// Days with a non-empty `AvailableByExpiry` bucket: blood_type -> Vec<u64> (ascending)
+    AvailableExpiryDays(BloodType),
     /// Custody trail page: (unit_id, page_number) -> Vec<String> (max 20 event IDs)
     UnitTrailPage(u64, u32),
     /// Custody trail metadata: unit_id -> TrailMetadata
     UnitTrailMeta(u64),
     /// Pending SuperAdmin nomination
     PendingNominee,
+    /// Next custody nonce per unit, mixed into derived event IDs: unit_id -> u64
+    CustodyNonce(u64),
+    /// Registered courier flag: courier -> bool
+    Courier(Address),
+    /// Courier currently holding an in-transit unit: unit_id -> Address
+    UnitCustodian(u64),
+    /// Open (Pending) custody hop for a unit: unit_id -> event_id
+    PendingCustody(u64),
+    /// Policy applied by `sweep_expired_transfers` (instance storage)
+    StaleTransferPolicy,
+    /// Admin override of a component's registration rules: component -> ComponentPolicy
+    ComponentPolicy(BloodComponent),
+    /// Whole-blood unit a component was split from: child unit_id -> parent unit_id
+    UnitParent(u64),
+    /// Components split from a whole-blood unit: parent unit_id -> Vec<u64>
+    UnitChildren(u64),
+    /// Flagged donor barred from further registrations: (bank_id, donor_id) -> DonorDeferral
+    DonorDeferral(Address, Symbol),
+    /// Latest quarantine case for a unit: unit_id -> QuarantineCase
+    QuarantineCase(u64),
+    /// Open quarantine index bucket: bucket -> Vec<u64> (ascending unit IDs)
+    OpenQuarantines(u64),
+    /// Seconds reviewers have to decide a quarantine case
+    QuarantineReviewWindow,
+    /// Funds held for a payment: payment_id -> EscrowAccount
+    Escrow(u64),
+    /// Escrowed payment funding one bank's share of a request:
+    /// (request_id, payee) -> payment_id
+    RequestPayment(u64, Address),
+    /// All payments linked to a blood request
+    RequestPayments(u64),
+    /// Per-bank shares of a blood request
+    RequestContributions(u64),
+    /// Component a blood request asks for; absent means whole blood
+    RequestComponent(u64),
+    /// Crossmatch attestation: (request_id, unit_id) -> CrossmatchAttestation
+    Crossmatch(u64, u64),
+    /// Where crossmatches are required before delivery
+    CrossmatchPolicy,
+    /// Request a unit is reserved for through `approve_request`
+    UnitRequest(u64),
+    /// Fee schedule applied to new payments
+    FeeStructure,
+    /// Address receiving payment fees
+    Treasury,
+    /// Delivery proof anchored for escrow release: payment_id -> ProofBundle
+    ProofBundle(u64),
+    /// Arbiters voting on newly raised disputes
+    ArbitrationPanel,
+    /// Seconds the counterparty has to answer a dispute
+    DisputeResponseWindow,
+    /// Dispute evidence log entry: (dispute_id, index) -> DisputeEvidence
+    DisputeEvidence(u64, u64),
+    /// Number of entries in a dispute's evidence log
+    DisputeEvidenceCount(u64),
+    /// M-of-N council governing sensitive admin operations
+    AdminCouncil,
+    /// Council proposal: proposal_id -> Proposal
+    Proposal(u64),
+    /// Next council proposal ID
+    NextProposalId,
+    /// Staff delegation: delegate -> Delegation
+    Delegate(Address),
+    /// Active delegates of a blood bank or hospital
+    InstitutionDelegates(Address),
+    /// Latest suspension / deregistration of a blood bank or hospital
+    InstitutionStanding(Address),
+    /// Whether in-flight transfers of suspended institutions may complete
+    SuspensionPolicy,
+    /// Patient health record version: (patient, version) -> PatientRecord
+    PatientRecord(Address, u32),
+    /// Latest record version per patient: patient -> u32
+    PatientRecordCount(Address),
+    /// Provider access grant: (patient, provider) -> AccessGrant
+    AccessGrant(Address, Address),
+}
+
+/// One page of blood units returned by a list query.
+///
+/// `next_cursor` is `None` once the result set is exhausted; otherwise pass it
+/// back unchanged to fetch the following page.
+#[contracttype]
+#[derive(Clone)]
+pub struct UnitPage {
+    pub items: Vec<BloodUnit>,
+    pub next_cursor: Option<u64>,
+}
+
+/// One page of a unit's status-change history, oldest first.
+///
+/// `next_cursor` is `None` once the history is exhausted; otherwise pass it
+/// back unchanged to fetch the following page.
+#[contracttype]
+#[derive(Clone)]
+pub struct StatusChangePage {
+    pub items: Vec<StatusChangeEvent>,
+    pub next_cursor: Option<u64>,
 }
 
 /// Metadata for paginated custody trail
@@ -437,9 +725,10 @@ pub struct TrailMetadata {
 
 // Re-export constants for internal use
 pub(crate) use constants::{
-    HEX_HASH_LENGTH, MAX_BATCH_EXPIRY_SIZE, MAX_BATCH_SIZE, MAX_EVENTS_PER_PAGE, MAX_QUANTITY_ML,
-    MAX_REQUEST_ML, MAX_SHELF_LIFE_DAYS, MAX_UNIT_ID_LENGTH, MIN_QUANTITY_ML, MIN_REQUEST_ML,
-    MIN_SHELF_LIFE_DAYS, NOMINATION_EXPIRY_SECONDS, SECONDS_PER_DAY, TRANSFER_EXPIRY_SECONDS,
+    DISPUTE_RESPONSE_SECONDS, EVENT_ID_VERSION, HEX_HASH_LENGTH, MAX_BATCH_EXPIRY_SIZE,
+    MAX_BATCH_SIZE, MAX_EVENTS_PER_PAGE, MAX_PAGE_SIZE, MAX_REQUEST_ML, MAX_UNITS_SCANNED_PER_PAGE,
+    MAX_UNIT_ID_LENGTH, MIN_REQUEST_ML, NOMINATION_EXPIRY_SECONDS, QUEUE_BUCKET_SECONDS,
+    TRANSFER_EXPIRY_SECONDS,
 };
 
 /// Pending SuperAdmin nomination entry.
@@ -517,7 +806,7 @@ impl HealthChainContract {
         features.contains(feature)
     }
 
-    /// Register a blood bank (admin only)
+    /// Register a blood bank (admin only; council proposal once a council is set)
     pub fn register_blood_bank(env: Env, bank_id: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
@@ -525,44 +814,276 @@ impl HealthChainContract {
             .get(&ADMIN)
             .ok_or(Error::Unauthorized)?;
         admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_blood_bank(&env, bank_id);
+
+        Ok(())
+    }
 
-        let mut banks: Map<Address, bool> = env
+    /// Register a hospital (admin only; council proposal once a council is set)
+    pub fn register_hospital(env: Env, hospital_id: Address) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&BLOOD_BANKS)
-            .unwrap_or(Map::new(&env));
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
 
-        banks.set(bank_id.clone(), true);
-        env.storage().persistent().set(&BLOOD_BANKS, &banks);
+        store_hospital(&env, hospital_id);
 
         Ok(())
     }
 
-    /// Register a hospital (admin only)
-    pub fn register_hospital(env: Env, hospital_id: Address) -> Result<(), Error> {
+    /// Suspend a blood bank or hospital (admin only; council proposal once a
+    /// council is set).
+    ///
+    /// The institution stays registered but cannot start new work until
+    /// reinstated; its in-flight transfers follow the `SuspensionPolicy`.
+    pub fn suspend_institution(
+        env: Env,
+        institution: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        institutions::suspend(&env, institution, reason)
+    }
+
+    /// Lift a suspension (admin only; council proposal once a council is set)
+    pub fn reinstate_institution(
+        env: Env,
+        institution: Address,
+        reason: String,
+    ) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&ADMIN)
             .ok_or(Error::Unauthorized)?;
         admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        institutions::reinstate(&env, institution, reason)
+    }
 
-        let mut hospitals: Map<Address, bool> = env
+    /// Remove a blood bank or hospital from the registry (admin only; council
+    /// proposal once a council is set)
+    pub fn deregister_institution(
+        env: Env,
+        institution: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&HOSPITALS)
-            .unwrap_or(Map::new(&env));
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        institutions::deregister(&env, institution, reason)
+    }
+
+    /// Get the latest suspension, reinstatement or deregistration of an
+    /// institution, if any
+    pub fn get_institution_standing(env: Env, institution: Address) -> Option<InstitutionStanding> {
+        institutions::get_standing(&env, &institution)
+    }
+
+    /// Set whether in-flight transfers of suspended institutions are frozen
+    /// (admin only; council proposal once a council is set)
+    pub fn set_suspension_policy(env: Env, policy: SuspensionPolicy) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_suspension_policy(&env, policy);
+
+        Ok(())
+    }
+
+    /// Get the policy applied to in-flight transfers of suspended institutions
+    /// (defaults to `FreezeTransfers`)
+    pub fn get_suspension_policy(env: Env) -> SuspensionPolicy {
+        institutions::suspension_policy(&env)
+    }
+
+    // ── ADMIN COUNCIL ─────────────────────────────────────────────────────────
+
+    /// Hand sensitive admin operations to an M-of-N council (admin only).
+    ///
+    /// Only callable while no council exists; afterwards the council changes
+    /// itself through a `SetCouncil` proposal.
+    pub fn set_admin_council(
+        env: Env,
+        members: Vec<Address>,
+        threshold: u32,
+        proposal_ttl: u64,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
 
-        hospitals.set(hospital_id.clone(), true);
-        env.storage().persistent().set(&HOSPITALS, &hospitals);
+        council::set_council(&env, members, threshold, proposal_ttl)
+    }
+
+    /// Get the admin council, if one is configured
+    pub fn get_admin_council(env: Env) -> Option<AdminCouncil> {
+        council::get_council(&env)
+    }
+
+    /// Propose a sensitive admin action (council members only).
+    ///
+    /// The proposer's approval is counted immediately, so a 1-of-N council
+    /// executes the action in this call. Returns the proposal ID.
+    pub fn propose_action(
+        env: Env,
+        proposer: Address,
+        action: CouncilAction,
+    ) -> Result<u64, Error> {
+        proposer.require_auth();
+
+        let admin_council = council::get_council(&env).ok_or(Error::Unauthorized)?;
+        if !admin_council.members.contains(&proposer) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut proposal = council::create_proposal(&env, &admin_council, proposer, action);
+        if proposal.approvals.len() >= admin_council.threshold {
+            execute_proposal(&env, &mut proposal)?;
+        }
+
+        Ok(proposal.id)
+    }
+
+    /// Approve a pending proposal (council members only).
+    ///
+    /// Executes the proposal once approvals reach the council threshold and
+    /// returns whether it executed.
+    pub fn approve_proposal(env: Env, member: Address, proposal_id: u64) -> Result<bool, Error> {
+        member.require_auth();
+
+        let admin_council = council::get_council(&env).ok_or(Error::Unauthorized)?;
+        if !admin_council.members.contains(&member) {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut proposal = council::get_proposal(&env, proposal_id)?;
+        if proposal.executed {
+            return Err(Error::InvalidStatus);
+        }
+        if env.ledger().timestamp() >= proposal.expires_at {
+            return Err(Error::ProposalExpired);
+        }
+        if proposal.approvals.contains(&member) {
+            return Err(Error::AlreadyVoted);
+        }
+
+        proposal.approvals.push_back(member.clone());
+        council::put_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("council"), symbol_short!("approve")),
+            (proposal_id, member),
+        );
+
+        if proposal.approvals.len() >= admin_council.threshold {
+            execute_proposal(&env, &mut proposal)?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Get a council proposal by ID
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, Error> {
+        council::get_proposal(&env, proposal_id)
+    }
+
+    /// Register a courier that may hold units between bank and hospital (admin
+    /// only; council proposal once a council is set)
+    pub fn register_courier(env: Env, courier: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_courier(&env, courier);
 
         Ok(())
     }
 
+    /// Check if an address is a registered courier
+    pub fn is_courier(env: Env, courier: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Courier(courier))
+            .unwrap_or(false)
+    }
+
+    /// Grant a staff address permissions to act for a blood bank or hospital.
+    ///
+    /// Replaces the delegate's existing permissions and expiry when it already
+    /// acts for `institution`. Delegates to [`delegates::grant`].
+    pub fn add_delegate(
+        env: Env,
+        institution: Address,
+        delegate: Address,
+        permissions: Vec<DelegatePermission>,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        institution.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), institution.clone())
+            && !Self::is_hospital(env.clone(), institution.clone())
+        {
+            return Err(Error::Unauthorized);
+        }
+        institutions::ensure_active(&env, &institution)?;
+
+        delegates::grant(&env, institution, delegate, permissions, expires_at)
+    }
+
+    /// Revoke a delegate's permissions to act for `institution`
+    pub fn revoke_delegate(env: Env, institution: Address, delegate: Address) -> Result<(), Error> {
+        institution.require_auth();
+        delegates::revoke(&env, institution, delegate)
+    }
+
+    /// Get the delegation held by a staff address, if any
+    pub fn get_delegate(env: Env, delegate: Address) -> Option<Delegation> {
+        delegates::get_delegation(&env, &delegate)
+    }
+
+    /// Get the active delegates of a blood bank or hospital
+    pub fn get_delegates(env: Env, institution: Address) -> Vec<Address> {
+        delegates::get_delegates(&env, &institution)
+    }
+
     // ── WRITE ─────────────────────────────────────────────────────────────────
 
     /// Register blood donation into inventory.
     ///
+    /// `bank_id` may be the bank or one of its delegates.
     /// Delegates to [`registry_write::register_unit`].
     pub fn register_blood(
         env: Env,
@@ -575,6 +1096,8 @@ impl HealthChainContract {
     ) -> Result<u64, Error> {
         // Authenticate and verify blood bank
         bank_id.require_auth();
+        let actor = bank_id.clone();
+        let bank_id = delegates::acting_for(&env, &actor, DelegatePermission::RegisterUnits)?;
 
         let banks: Map<Address, bool> = env
             .storage()
@@ -585,10 +1108,12 @@ impl HealthChainContract {
         if !banks.get(bank_id.clone()).unwrap_or(false) {
             return Err(Error::Unauthorized);
         }
+        institutions::ensure_active(&env, &bank_id)?;
 
         registry_write::register_unit(
             &env,
             bank_id,
+            actor,
             blood_type,
             component,
             quantity_ml,
@@ -604,6 +1129,8 @@ impl HealthChainContract {
         units: Vec<(BloodType, BloodComponent, u32, u64, Option<Symbol>)>,
     ) -> Result<Vec<u64>, Error> {
         bank_id.require_auth();
+        let actor = bank_id.clone();
+        let bank_id = delegates::acting_for(&env, &actor, DelegatePermission::RegisterUnits)?;
 
         let banks: Map<Address, bool> = env
             .storage()
@@ -614,6 +1141,7 @@ impl HealthChainContract {
         if !banks.get(bank_id.clone()).unwrap_or(false) {
             return Err(Error::Unauthorized);
         }
+        institutions::ensure_active(&env, &bank_id)?;
 
         if units.len() > MAX_BATCH_SIZE {
             return Err(Error::BatchSizeExceeded);
@@ -626,6 +1154,7 @@ impl HealthChainContract {
             let unit_id = registry_write::register_unit(
                 &env,
                 bank_id.clone(),
+                actor.clone(),
                 blood_type,
                 component,
                 quantity_ml,
@@ -638,6 +1167,69 @@ impl HealthChainContract {
         Ok(registered_ids)
     }
 
+    /// Override the registration rules for a blood component (admin only;
+    /// council proposal once a council is set)
+    ///
+    /// Applies to `register_blood` and `batch_register_blood` from then on;
+    /// already registered units are unaffected.
+    pub fn set_component_policy(
+        env: Env,
+        component: BloodComponent,
+        policy: ComponentPolicy,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_component_policy(&env, component, policy)
+    }
+
+    /// Get the registration rules currently applied to a blood component
+    pub fn get_component_policy(env: Env, component: BloodComponent) -> ComponentPolicy {
+        registry_read::get_component_policy(&env, component)
+    }
+
+    /// Split an available whole-blood unit into component units.
+    ///
+    /// Each child is `(component, quantity_ml, expiration_timestamp)`.
+    /// Delegates to [`registry_write::split_unit`].
+    pub fn split_unit(
+        env: Env,
+        bank_id: Address,
+        parent_id: u64,
+        children: Vec<(BloodComponent, u32, u64)>,
+    ) -> Result<Vec<u64>, Error> {
+        bank_id.require_auth();
+        let actor = bank_id.clone();
+        let bank_id = delegates::acting_for(&env, &actor, DelegatePermission::RegisterUnits)?;
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        institutions::ensure_active(&env, &bank_id)?;
+
+        registry_write::split_unit(&env, bank_id, actor, parent_id, children)
+    }
+
+    /// Get the whole-blood unit a component unit was split from, if any
+    pub fn get_parent_unit(env: Env, unit_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UnitParent(unit_id))
+    }
+
+    /// Get the component units split from a whole-blood unit
+    pub fn get_child_units(env: Env, unit_id: u64) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UnitChildren(unit_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
     /// Check if an address is an authorized blood bank
     pub fn is_blood_bank(env: Env, bank_id: Address) -> bool {
         let banks: Map<Address, bool> = env
@@ -650,6 +1242,8 @@ impl HealthChainContract {
     }
 
     /// Allocate blood unit to a hospital
+    ///
+    /// `bank_id` may be the bank or one of its delegates.
     pub fn allocate_blood(
         env: Env,
         bank_id: Address,
@@ -657,6 +1251,8 @@ impl HealthChainContract {
         hospital: Address,
     ) -> Result<(), Error> {
         bank_id.require_auth();
+        let actor = bank_id.clone();
+        let bank_id = delegates::acting_for(&env, &actor, DelegatePermission::Allocate)?;
 
         if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
             return Err(Error::Unauthorized);
@@ -665,14 +1261,10 @@ impl HealthChainContract {
         if !Self::is_hospital(env.clone(), hospital.clone()) {
             return Err(Error::UnauthorizedHospital);
         }
+        institutions::ensure_active(&env, &bank_id)?;
+        institutions::ensure_active(&env, &hospital)?;
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
 
         // --- NEW: REQUIREMENT #67 GUARD ---
         if unit.status == BloodStatus::Expired {
@@ -694,16 +1286,9 @@ impl HealthChainContract {
         unit.recipient_hospital = Some(hospital.clone());
         unit.allocation_timestamp = Some(current_time);
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        registry_write::put_unit(&env, &unit);
 
-        record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            BloodStatus::Reserved,
-            bank_id.clone(),
-        );
+        record_status_change(&env, unit_id, old_status, BloodStatus::Reserved, actor);
 
         env.events().publish(
             (symbol_short!("blood"), symbol_short!("allocate")),
@@ -721,6 +1306,8 @@ impl HealthChainContract {
         hospital: Address,
     ) -> Result<Vec<u64>, Error> {
         bank_id.require_auth();
+        let actor = bank_id.clone();
+        let bank_id = delegates::acting_for(&env, &actor, DelegatePermission::Allocate)?;
 
         // Check batch size
         if unit_ids.len() > MAX_BATCH_SIZE {
@@ -736,20 +1323,16 @@ impl HealthChainContract {
         if !Self::is_hospital(env.clone(), hospital.clone()) {
             return Err(Error::UnauthorizedHospital);
         }
+        institutions::ensure_active(&env, &bank_id)?;
+        institutions::ensure_active(&env, &hospital)?;
 
         let mut allocated = vec![&env];
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
         let current_time = env.ledger().timestamp();
 
         // Process all units
         for i in 0..unit_ids.len() {
             let unit_id = unit_ids.get(i).unwrap();
-            let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+            let mut unit = registry_read::get_unit(&env, unit_id)?;
 
             // Check if expired
             if unit.expiration_date <= current_time {
@@ -769,7 +1352,7 @@ impl HealthChainContract {
             unit.recipient_hospital = Some(hospital.clone());
             unit.allocation_timestamp = Some(current_time);
 
-            units.set(unit_id, unit.clone());
+            registry_write::put_unit(&env, &unit);
 
             // Record status change
             record_status_change(
@@ -777,7 +1360,7 @@ impl HealthChainContract {
                 unit_id,
                 old_status,
                 BloodStatus::Reserved,
-                bank_id.clone(),
+                actor.clone(),
             );
 
             // Emit event
@@ -789,15 +1372,14 @@ impl HealthChainContract {
             allocated.push_back(unit_id);
         }
 
-        // Save all changes
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
-
         Ok(allocated)
     }
 
     /// Cancel blood allocation
     pub fn cancel_allocation(env: Env, bank_id: Address, unit_id: u64) -> Result<(), Error> {
         bank_id.require_auth();
+        let actor = bank_id.clone();
+        let bank_id = delegates::acting_for(&env, &actor, DelegatePermission::Allocate)?;
 
         // Verify blood bank is authorized
         if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
@@ -805,13 +1387,7 @@ impl HealthChainContract {
         }
 
         // Get blood unit
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
 
         // Check status - can only cancel if Reserved
         if unit.status != BloodStatus::Reserved {
@@ -825,17 +1401,10 @@ impl HealthChainContract {
         unit.recipient_hospital = None;
         unit.allocation_timestamp = None;
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        registry_write::put_unit(&env, &unit);
 
         // Record status change
-        record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            BloodStatus::Available,
-            bank_id.clone(),
-        );
+        record_status_change(&env, unit_id, old_status, BloodStatus::Available, actor);
 
         // Emit event
         env.events()
@@ -853,13 +1422,7 @@ impl HealthChainContract {
             return Err(Error::Unauthorized);
         }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
 
         // Verify the caller is the current custodian of this specific unit.
         if unit.bank_id != bank_id {
@@ -883,44 +1446,78 @@ impl HealthChainContract {
 
         // Get the recipient hospital (to_custodian)
         let to_custodian = unit.recipient_hospital.clone().ok_or(Error::StorageError)?;
+        institutions::ensure_active(&env, &bank_id)?;
+        institutions::ensure_active(&env, &to_custodian)?;
 
-        // Derive deterministic event_id
-        let event_id = Self::derive_event_id(&env, unit_id, &bank_id, &to_custodian);
+        let custody_event = open_custody_hop(&env, unit_id, &bank_id, &to_custodian)?;
+        let event_id = custody_event.event_id.clone();
 
-        // Validate event_id length (should always be HEX_HASH_LENGTH, but check for safety)
-        if event_id.len() > MAX_UNIT_ID_LENGTH {
-            return Err(Error::UnitIdTooLong);
-        }
+        let old_status = unit.status;
+        unit.status = BloodStatus::InTransit;
+        unit.transfer_timestamp = Some(current_time);
 
-        // Create custody event
-        let custody_event = CustodyEvent {
-            event_id: event_id.clone(),
+        registry_write::put_unit(&env, &unit);
+
+        record_status_change(
+            &env,
             unit_id,
-            from_custodian: bank_id.clone(),
-            to_custodian: to_custodian.clone(),
-            initiated_at: current_time,
-            ledger_sequence: env.ledger().sequence(),
-            status: CustodyStatus::Pending,
-        };
+            old_status,
+            BloodStatus::InTransit,
+            bank_id.clone(),
+        );
 
-        // Store custody event
-        let mut custody_events: Map<String, CustodyEvent> = env
-            .storage()
-            .persistent()
-            .get(&CUSTODY_EVENTS)
-            .unwrap_or(Map::new(&env));
+        env.events().publish(
+            (symbol_short!("custody"), symbol_short!("initiate")),
+            custody_event,
+        );
 
-        custody_events.set(event_id.clone(), custody_event.clone());
-        env.storage()
-            .persistent()
-            .set(&CUSTODY_EVENTS, &custody_events);
+        Ok(event_id)
+    }
+
+    /// Dispatch a reserved unit to a registered courier instead of directly
+    /// to the hospital.
+    ///
+    /// Opens the first hop (bank → courier). The courier takes custody with
+    /// `accept_custody` and passes the unit on with `handoff_custody`; the
+    /// recipient hospital closes the chain with `confirm_transfer`.
+    pub fn dispatch_to_courier(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+        courier: Address,
+    ) -> Result<String, Error> {
+        bank_id.require_auth();
+
+        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if !Self::is_courier(env.clone(), courier.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        institutions::ensure_active(&env, &bank_id)?;
+
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
+
+        if unit.bank_id != bank_id {
+            return Err(Error::NotCurrentCustodian);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if unit.status == BloodStatus::Expired || unit.expiration_date <= current_time {
+            return Err(Error::UnitExpired);
+        }
+        if unit.status != BloodStatus::Reserved {
+            return Err(Error::InvalidStatus);
+        }
+
+        let custody_event = open_custody_hop(&env, unit_id, &bank_id, &courier)?;
+        let event_id = custody_event.event_id.clone();
 
         let old_status = unit.status;
         unit.status = BloodStatus::InTransit;
         unit.transfer_timestamp = Some(current_time);
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        registry_write::put_unit(&env, &unit);
 
         record_status_change(
             &env,
@@ -938,35 +1535,181 @@ impl HealthChainContract {
         Ok(event_id)
     }
 
+    /// Courier takes custody of a unit for a pending hop addressed to them.
+    ///
+    /// The hop is confirmed and appended to the unit's custody trail; the unit
+    /// stays `InTransit` with the courier as its current custodian.
+    pub fn accept_custody(env: Env, courier: Address, event_id: String) -> Result<(), Error> {
+        if event_id.len() > MAX_UNIT_ID_LENGTH {
+            return Err(Error::UnitIdTooLong);
+        }
+
+        courier.require_auth();
+
+        if !Self::is_courier(env.clone(), courier.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        let custody_event = Self::get_custody_event(env.clone(), event_id.clone())?;
+
+        if custody_event.to_custodian != courier {
+            return Err(Error::Unauthorized);
+        }
+        if custody_event.status != CustodyStatus::Pending {
+            return Err(Error::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time
+            >= custody_event
+                .initiated_at
+                .saturating_add(TRANSFER_EXPIRY_SECONDS)
+        {
+            return Err(Error::TransferExpired);
+        }
+
+        let unit_id = custody_event.unit_id;
+        let unit = registry_read::get_unit(&env, unit_id)?;
+        if unit.status != BloodStatus::InTransit {
+            return Err(Error::InvalidStatus);
+        }
+        institutions::ensure_transfer_allowed(
+            &env,
+            &[&custody_event.from_custodian, &unit.bank_id],
+        )?;
+
+        let custody_event = close_custody_hop(&env, custody_event, CustodyStatus::Confirmed);
+        append_to_custody_trail(&env, unit_id, event_id);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::UnitCustodian(unit_id), &courier);
+
+        env.events().publish(
+            (symbol_short!("custody"), symbol_short!("accept")),
+            custody_event,
+        );
+
+        Ok(())
+    }
+
+    /// Hand an in-transit unit from its current courier to the next custodian.
+    ///
+    /// `to_custodian` must be another registered courier or the unit's
+    /// recipient hospital. Only the courier currently holding the unit may
+    /// hand it off, and only once the previous hop has been accepted.
+    pub fn handoff_custody(
+        env: Env,
+        courier: Address,
+        unit_id: u64,
+        to_custodian: Address,
+    ) -> Result<String, Error> {
+        courier.require_auth();
+
+        let unit = registry_read::get_unit(&env, unit_id)?;
+        if unit.status != BloodStatus::InTransit {
+            return Err(Error::InvalidStatus);
+        }
+
+        let holder: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UnitCustodian(unit_id));
+        if holder != Some(courier.clone()) {
+            return Err(Error::NotCurrentCustodian);
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PendingCustody(unit_id))
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        if unit.recipient_hospital != Some(to_custodian.clone())
+            && !Self::is_courier(env.clone(), to_custodian.clone())
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        if unit.expiration_date <= env.ledger().timestamp() {
+            return Err(Error::UnitExpired);
+        }
+
+        institutions::ensure_transfer_allowed(&env, &[&unit.bank_id, &to_custodian])?;
+
+        let custody_event = open_custody_hop(&env, unit_id, &courier, &to_custodian)?;
+        let event_id = custody_event.event_id.clone();
+
+        env.events().publish(
+            (symbol_short!("custody"), symbol_short!("handoff")),
+            custody_event,
+        );
+
+        Ok(event_id)
+    }
+
+    /// Get the address currently responsible for a unit.
+    ///
+    /// This is the courier holding it while in a multi-hop transfer, the
+    /// recipient hospital once delivered, and the registering bank otherwise.
+    pub fn get_current_custodian(env: Env, unit_id: u64) -> Result<Address, Error> {
+        let unit = registry_read::get_unit(&env, unit_id)?;
+
+        if let Some(courier) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UnitCustodian(unit_id))
+        {
+            return Ok(courier);
+        }
+
+        match (unit.status, unit.recipient_hospital) {
+            (BloodStatus::Delivered | BloodStatus::InRetransfer, Some(hospital)) => Ok(hospital),
+            _ => Ok(unit.bank_id),
+        }
+    }
+
     /// Confirm blood delivery
     ///
     /// This is kept for backwards-compatibility and delegates to `confirm_transfer`.
     /// Note: This function looks up the pending custody event by unit_id for convenience.
     pub fn confirm_delivery(env: Env, hospital: Address, unit_id: u64) -> Result<(), Error> {
-        // Find the pending custody event for this unit
-        let custody_events: Map<String, CustodyEvent> = env
+        // Find the pending custody event for this unit. Hops opened before the
+        // PendingCustody index existed are found by scanning the event map.
+        let pending: Option<String> = env
             .storage()
             .persistent()
-            .get(&CUSTODY_EVENTS)
-            .unwrap_or(Map::new(&env));
+            .get(&DataKey::PendingCustody(unit_id));
+
+        let event_id = match pending {
+            Some(event_id) => event_id,
+            None => {
+                let custody_events: Map<String, CustodyEvent> = env
+                    .storage()
+                    .persistent()
+                    .get(&CUSTODY_EVENTS)
+                    .unwrap_or(Map::new(&env));
 
-        // Search for pending custody event with matching unit_id
-        let mut found_event_id: Option<String> = None;
-        for (event_id, event) in custody_events.iter() {
-            if event.unit_id == unit_id && event.status == CustodyStatus::Pending {
-                found_event_id = Some(event_id);
-                break;
+                custody_events
+                    .iter()
+                    .find(|(_, event)| {
+                        event.unit_id == unit_id && event.status == CustodyStatus::Pending
+                    })
+                    .map(|(event_id, _)| event_id)
+                    .ok_or(Error::UnitNotFound)?
             }
-        }
+        };
 
-        let event_id = found_event_id.ok_or(Error::UnitNotFound)?;
         Self::confirm_transfer(env, hospital, event_id)
     }
 
     /// Confirm an in-transit transfer using the derived event_id.
     ///
     /// Must be confirmed strictly before `initiated_at + TRANSFER_EXPIRY_SECONDS`.
-    /// Callers must compute the same hash (unit_id + from + to + ledger_sequence) to reference the transfer.
+    /// Closes the final hop when the unit arrives via couriers. Callers can
+    /// derive the event_id with `compute_event_id`.
     pub fn confirm_transfer(env: Env, hospital: Address, event_id: String) -> Result<(), Error> {
         // Validate event_id length
         if event_id.len() > MAX_UNIT_ID_LENGTH {
@@ -974,6 +1717,8 @@ impl HealthChainContract {
         }
 
         hospital.require_auth();
+        let actor = hospital.clone();
+        let hospital = delegates::acting_for(&env, &actor, DelegatePermission::ConfirmDelivery)?;
 
         // Verify hospital is registered
         if !Self::is_hospital(env.clone(), hospital.clone()) {
@@ -981,15 +1726,7 @@ impl HealthChainContract {
         }
 
         // Get custody event
-        let mut custody_events: Map<String, CustodyEvent> = env
-            .storage()
-            .persistent()
-            .get(&CUSTODY_EVENTS)
-            .unwrap_or(Map::new(&env));
-
-        let mut custody_event = custody_events
-            .get(event_id.clone())
-            .ok_or(Error::UnitNotFound)?;
+        let custody_event = Self::get_custody_event(env.clone(), event_id.clone())?;
 
         // Verify hospital is the recipient
         if custody_event.to_custodian != hospital {
@@ -1004,13 +1741,7 @@ impl HealthChainContract {
         let unit_id = custody_event.unit_id;
 
         // Get blood unit
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
 
         // Check status - must be InTransit
         if unit.status != BloodStatus::InTransit {
@@ -1025,36 +1756,38 @@ impl HealthChainContract {
             return Err(Error::TransferExpired);
         }
 
+        institutions::ensure_transfer_allowed(
+            &env,
+            &[&custody_event.from_custodian, &unit.bank_id, &hospital],
+        )?;
+
+        if crossmatch::policy(&env).require_on_transfer {
+            if let Some(request) = crossmatch::linked_request(&env, unit_id) {
+                crossmatch::ensure_passed(&env, &request, unit_id)?;
+            }
+        }
+
         let old_status = unit.status;
 
         // Check if blood unit expired during transit
         if unit.expiration_date <= current_time {
             unit.status = BloodStatus::Expired;
-            units.set(unit_id, unit.clone());
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
+            registry_write::put_unit(&env, &unit);
 
-            custody_event.status = CustodyStatus::Cancelled;
-            custody_events.set(event_id, custody_event);
+            close_custody_hop(&env, custody_event, CustodyStatus::Cancelled);
             env.storage()
                 .persistent()
-                .set(&CUSTODY_EVENTS, &custody_events);
+                .remove(&DataKey::UnitCustodian(unit_id));
 
-            record_status_change(
-                &env,
-                unit_id,
-                old_status,
-                BloodStatus::Expired,
-                hospital.clone(),
-            );
+            record_status_change(&env, unit_id, old_status, BloodStatus::Expired, actor);
             return Err(Error::UnitExpired);
         }
 
         // Update custody event status
-        custody_event.status = CustodyStatus::Confirmed;
-        custody_events.set(event_id.clone(), custody_event.clone());
+        let custody_event = close_custody_hop(&env, custody_event, CustodyStatus::Confirmed);
         env.storage()
             .persistent()
-            .set(&CUSTODY_EVENTS, &custody_events);
+            .remove(&DataKey::UnitCustodian(unit_id));
 
         // Append to custody trail (paginated)
         append_to_custody_trail(&env, unit_id, event_id.clone());
@@ -1063,17 +1796,10 @@ impl HealthChainContract {
         unit.status = BloodStatus::Delivered;
         unit.delivery_timestamp = Some(current_time);
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        registry_write::put_unit(&env, &unit);
 
         // Record status change
-        record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            BloodStatus::Delivered,
-            hospital.clone(),
-        );
+        record_status_change(&env, unit_id, old_status, BloodStatus::Delivered, actor);
 
         // Emit event
         env.events().publish(
@@ -1086,33 +1812,31 @@ impl HealthChainContract {
 
     /// Cancel an in-transit transfer using the derived event_id.
     ///
-    /// Transfer is cancellable at/after `initiated_at + TRANSFER_EXPIRY_SECONDS`.
-    /// Callers must compute the same hash (unit_id + from + to + ledger_sequence) to reference the transfer.
-    pub fn cancel_transfer(env: Env, bank_id: Address, event_id: String) -> Result<(), Error> {
+    /// Transfer is cancellable at/after `initiated_at + TRANSFER_EXPIRY_SECONDS`
+    /// by the sender of the hop. Cancelling a bank's hop returns the unit to
+    /// `Reserved`; cancelling a courier's hand-off leaves the unit with that
+    /// courier so it can be handed off again; cancelling a hospital's
+    /// re-transfer returns the unit to `Delivered` at that hospital.
+    pub fn cancel_transfer(env: Env, sender: Address, event_id: String) -> Result<(), Error> {
         // Validate event_id length
         if event_id.len() > MAX_UNIT_ID_LENGTH {
             return Err(Error::UnitIdTooLong);
         }
 
-        bank_id.require_auth();
+        sender.require_auth();
 
-        if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
+        if !Self::is_blood_bank(env.clone(), sender.clone())
+            && !Self::is_courier(env.clone(), sender.clone())
+            && !Self::is_hospital(env.clone(), sender.clone())
+        {
             return Err(Error::Unauthorized);
         }
 
         // Get custody event
-        let mut custody_events: Map<String, CustodyEvent> = env
-            .storage()
-            .persistent()
-            .get(&CUSTODY_EVENTS)
-            .unwrap_or(Map::new(&env));
+        let custody_event = Self::get_custody_event(env.clone(), event_id.clone())?;
 
-        let mut custody_event = custody_events
-            .get(event_id.clone())
-            .ok_or(Error::UnitNotFound)?;
-
-        // Verify bank is the sender
-        if custody_event.from_custodian != bank_id {
+        // Verify caller is the sender of this hop
+        if custody_event.from_custodian != sender {
             return Err(Error::Unauthorized);
         }
 
@@ -1123,16 +1847,10 @@ impl HealthChainContract {
 
         let unit_id = custody_event.unit_id;
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
 
         // Only cancellable while in transit
-        if unit.status != BloodStatus::InTransit {
+        if !is_in_transit(unit.status) {
             return Err(Error::InvalidStatus);
         }
 
@@ -1143,30 +1861,8 @@ impl HealthChainContract {
             return Err(Error::TransferNotExpired);
         }
 
-        // Update custody event status
-        custody_event.status = CustodyStatus::Cancelled;
-        custody_events.set(event_id.clone(), custody_event.clone());
-        env.storage()
-            .persistent()
-            .set(&CUSTODY_EVENTS, &custody_events);
-
-        let old_status = unit.status;
-
-        // Revert back to Reserved state; keep recipient_hospital + allocation_timestamp.
-        unit.status = BloodStatus::Reserved;
-        unit.transfer_timestamp = None;
-
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
-
-        // Record status change
-        record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            BloodStatus::Reserved,
-            bank_id.clone(),
-        );
+        // Update custody event status and revert the unit
+        let custody_event = revert_custody_hop(&env, custody_event, &mut unit, sender);
 
         // Emit event
         env.events().publish(
@@ -1181,49 +1877,411 @@ impl HealthChainContract {
         Ok(())
     }
 
-    /// Withdraw blood unit (mark as used/discarded)
-    pub fn withdraw_blood(
-        env: Env,
-        caller: Address,
-        unit_id: u64,
-        reason: WithdrawalReason,
-    ) -> Result<(), Error> {
-        caller.require_auth();
+    /// Set what `sweep_expired_transfers` does with timed-out units (admin
+    /// only; council proposal once a council is set)
+    pub fn set_stale_transfer_policy(env: Env, policy: StaleTransferPolicy) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
 
-        // Verify caller is authorized (blood bank or hospital)
-        let is_bank = Self::is_blood_bank(env.clone(), caller.clone());
-        let is_hosp = Self::is_hospital(env.clone(), caller.clone());
+        store_stale_transfer_policy(&env, policy);
 
-        if !is_bank && !is_hosp {
-            return Err(Error::Unauthorized);
-        }
+        Ok(())
+    }
 
-        // Get blood unit
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+    /// Get the policy applied to timed-out transfers (defaults to `Revert`)
+    pub fn get_stale_transfer_policy(env: Env) -> StaleTransferPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::StaleTransferPolicy)
+            .unwrap_or(StaleTransferPolicy::Revert)
+    }
 
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    /// Cancel up to `limit` custody hops still `Pending` after
+    /// `TRANSFER_EXPIRY_SECONDS`, oldest first. Permissionless.
+    ///
+    /// Each unit is reverted or quarantined according to the
+    /// `StaleTransferPolicy`, and a `("custody", "expired")` event carrying the
+    /// cancelled hop and the unit's new status is emitted. Queue entries whose
+    /// hop has already been closed or replaced are pruned without counting
+    /// towards `limit`. Returns the event IDs that were cancelled; call again
+    /// while it returns a full batch.
+    pub fn sweep_expired_transfers(env: Env, limit: u32) -> Result<Vec<String>, Error> {
+        if limit > MAX_BATCH_EXPIRY_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
 
-        let old_status = unit.status;
+        let policy = Self::get_stale_transfer_policy(env.clone());
         let current_time = env.ledger().timestamp();
+        let actor = env.current_contract_address();
+
+        let mut swept = Vec::new(&env);
+        bucket_queue::scan(&env, Queue::Transfers, None, |entry: (u64, u64, String)| {
+            let (initiated_at, unit_id, event_id) = entry.clone();
+            // Ordered by initiation time, so nothing later has expired either
+            if swept.len() >= limit
+                || current_time < initiated_at.saturating_add(TRANSFER_EXPIRY_SECONDS)
+            {
+                return false;
+            }
 
-        // Update unit
-        unit.status = BloodStatus::Discarded;
+            // Skip hops that are no longer the unit's open hop
+            let open_hop: Option<String> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PendingCustody(unit_id));
+            let custody_event = open_hop
+                .filter(|open_id| *open_id == event_id)
+                .and_then(|open_id| Self::get_custody_event(env.clone(), open_id).ok())
+                .filter(|event| {
+                    event.status == CustodyStatus::Pending && event.initiated_at == initiated_at
+                });
+            let (Some(custody_event), Ok(mut unit)) =
+                (custody_event, registry_read::get_unit(&env, unit_id))
+            else {
+                drop_pending_transfer(&env, &entry);
+                return true;
+            };
+
+            let custody_event = match policy {
+                StaleTransferPolicy::Revert => {
+                    revert_custody_hop(&env, custody_event, &mut unit, actor.clone())
+                }
+                StaleTransferPolicy::Quarantine => {
+                    let custody_event =
+                        close_custody_hop(&env, custody_event, CustodyStatus::Cancelled);
+                    // The unit leaves the transfer chain, so no courier holds it any more
+                    env.storage()
+                        .persistent()
+                        .remove(&DataKey::UnitCustodian(unit_id));
+                    let reviewer = unit.bank_id.clone();
+                    quarantine::place(
+                        &env,
+                        &mut unit,
+                        actor.clone(),
+                        QuarantineReason::AnomalyDetection,
+                        Bytes::new(&env),
+                        reviewer,
+                    );
+                    custody_event
+                }
+            };
 
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+            env.events().publish(
+                (symbol_short!("custody"), symbol_short!("expired")),
+                (custody_event, unit.status),
+            );
+            swept.push_back(event_id);
+            true
+        });
 
-        // Record status change
-        record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            BloodStatus::Discarded,
-            caller.clone(),
-        );
+        Ok(swept)
+    }
+
+    /// Send a delivered unit back to its bank or on to another hospital.
+    ///
+    /// Only the hospital currently holding the unit may start a re-transfer.
+    /// The receiver closes it with `confirm_retransfer` within
+    /// `TRANSFER_EXPIRY_SECONDS`; after that the sender may `cancel_transfer`.
+    pub fn initiate_retransfer(
+        env: Env,
+        hospital: Address,
+        unit_id: u64,
+        to_custodian: Address,
+    ) -> Result<String, Error> {
+        hospital.require_auth();
+
+        if !Self::is_hospital(env.clone(), hospital.clone()) {
+            return Err(Error::UnauthorizedHospital);
+        }
+
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
+
+        if unit.status != BloodStatus::Delivered {
+            return Err(Error::InvalidStatus);
+        }
+        if unit.recipient_hospital != Some(hospital.clone()) {
+            return Err(Error::NotCurrentCustodian);
+        }
+
+        // Returns go to the originating bank; forwards to another registered hospital.
+        if to_custodian == hospital
+            || (to_custodian != unit.bank_id
+                && !Self::is_hospital(env.clone(), to_custodian.clone()))
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if unit.expiration_date <= current_time {
+            return Err(Error::UnitExpired);
+        }
+
+        institutions::ensure_active(&env, &hospital)?;
+        institutions::ensure_active(&env, &to_custodian)?;
+
+        let custody_event = open_custody_hop(&env, unit_id, &hospital, &to_custodian)?;
+        let event_id = custody_event.event_id.clone();
+
+        let old_status = unit.status;
+        unit.status = BloodStatus::InRetransfer;
+        unit.transfer_timestamp = Some(current_time);
+
+        registry_write::put_unit(&env, &unit);
+
+        record_status_change(
+            &env,
+            unit_id,
+            old_status,
+            BloodStatus::InRetransfer,
+            hospital.clone(),
+        );
+
+        env.events().publish(
+            (symbol_short!("custody"), symbol_short!("retransfr")),
+            custody_event,
+        );
+
+        Ok(event_id)
+    }
+
+    /// Receive a re-transferred unit and attest whether the cold chain held.
+    ///
+    /// With `cold_chain_ok` the hop is confirmed and appended to the custody
+    /// trail: a returned unit becomes `Available` at its bank, a forwarded unit
+    /// becomes `Delivered` at the receiving hospital. Otherwise the hop is
+    /// `Rejected` and the unit is quarantined with
+    /// `QuarantineReason::TemperatureBreach` in the receiver's hands.
+    pub fn confirm_retransfer(
+        env: Env,
+        receiver: Address,
+        event_id: String,
+        cold_chain_ok: bool,
+    ) -> Result<(), Error> {
+        if event_id.len() > MAX_UNIT_ID_LENGTH {
+            return Err(Error::UnitIdTooLong);
+        }
+
+        receiver.require_auth();
+
+        let custody_event = Self::get_custody_event(env.clone(), event_id.clone())?;
+
+        if custody_event.to_custodian != receiver {
+            return Err(Error::Unauthorized);
+        }
+        if custody_event.status != CustodyStatus::Pending {
+            return Err(Error::InvalidStatus);
+        }
+
+        let unit_id = custody_event.unit_id;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
+
+        if unit.status != BloodStatus::InRetransfer {
+            return Err(Error::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time
+            >= custody_event
+                .initiated_at
+                .saturating_add(TRANSFER_EXPIRY_SECONDS)
+        {
+            return Err(Error::TransferExpired);
+        }
+
+        institutions::ensure_transfer_allowed(&env, &[&custody_event.from_custodian, &receiver])?;
+
+        let old_status = unit.status;
+        let is_return = receiver == unit.bank_id;
+
+        if is_return {
+            unit.recipient_hospital = None;
+            unit.allocation_timestamp = None;
+            unit.transfer_timestamp = None;
+            unit.delivery_timestamp = None;
+        } else {
+            unit.recipient_hospital = Some(receiver.clone());
+            unit.delivery_timestamp = Some(current_time);
+        }
+
+        // Check if blood unit expired during transit
+        if unit.expiration_date <= current_time {
+            unit.status = BloodStatus::Expired;
+            registry_write::put_unit(&env, &unit);
+            close_custody_hop(&env, custody_event, CustodyStatus::Cancelled);
+
+            record_status_change(
+                &env,
+                unit_id,
+                old_status,
+                BloodStatus::Expired,
+                receiver.clone(),
+            );
+            return Err(Error::UnitExpired);
+        }
+
+        if !cold_chain_ok {
+            let custody_event = close_custody_hop(&env, custody_event, CustodyStatus::Rejected);
+            env.events().publish(
+                (symbol_short!("custody"), symbol_short!("reject")),
+                custody_event,
+            );
+
+            let reviewer = unit.bank_id.clone();
+            quarantine::place(
+                &env,
+                &mut unit,
+                receiver,
+                QuarantineReason::TemperatureBreach,
+                Bytes::new(&env),
+                reviewer,
+            );
+
+            return Ok(());
+        }
+
+        let new_status = if is_return {
+            BloodStatus::Available
+        } else {
+            BloodStatus::Delivered
+        };
+
+        let custody_event = close_custody_hop(&env, custody_event, CustodyStatus::Confirmed);
+        append_to_custody_trail(&env, unit_id, event_id);
+
+        unit.status = new_status;
+        registry_write::put_unit(&env, &unit);
+
+        record_status_change(&env, unit_id, old_status, new_status, receiver.clone());
+
+        env.events().publish(
+            (symbol_short!("custody"), symbol_short!("confirm")),
+            custody_event,
+        );
+
+        Ok(())
+    }
+
+    /// Flag a donor after a positive test and recall their recent units.
+    ///
+    /// Callable by the admin or by the bank the donor ID belongs to. Every
+    /// `Available`, `Reserved` or in-transit unit from this donor registered
+    /// within `lookback_window` seconds is quarantined with
+    /// `QuarantineReason::DonorEvent` (any pending hop is cancelled and any
+    /// courier custody cleared). Units already `Delivered` are reported in one
+    /// `("donor", "recall")` event per hospital per batch. The donor is
+    /// deferred, so further registrations fail with `DonorDeferred`.
+    ///
+    /// At most `limit` (capped at `MAX_BATCH_SIZE`) of the donor's units are
+    /// checked per call; while the returned page has a `next_cursor`, finish
+    /// the recall with `resume_donor_recall`.
+    pub fn flag_donor(
+        env: Env,
+        caller: Address,
+        bank_id: Address,
+        donor_id: Symbol,
+        reason: String,
+        lookback_window: u64,
+        limit: u32,
+    ) -> Result<DonorRecallPage, Error> {
+        caller.require_auth();
+        ensure_can_recall(&env, &caller, &bank_id)?;
+
+        let deferral = DonorDeferral {
+            bank_id: bank_id.clone(),
+            donor_id: donor_id.clone(),
+            reason,
+            flagged_by: caller.clone(),
+            flagged_at: env.ledger().timestamp(),
+            lookback_window,
+        };
+        env.storage().persistent().set(
+            &DataKey::DonorDeferral(bank_id.clone(), donor_id.clone()),
+            &deferral,
+        );
+
+        let page = recall_donor_units(&env, &caller, &deferral, None, limit)?;
+
+        env.events().publish(
+            (symbol_short!("donor"), symbol_short!("flag")),
+            (deferral, page.quarantined.clone()),
+        );
+
+        Ok(page)
+    }
+
+    /// Continue the look-back recall of a flagged donor from `cursor`.
+    ///
+    /// Same callers, window and batch limit as `flag_donor`, which must have
+    /// deferred the donor first.
+    pub fn resume_donor_recall(
+        env: Env,
+        caller: Address,
+        bank_id: Address,
+        donor_id: Symbol,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<DonorRecallPage, Error> {
+        caller.require_auth();
+        ensure_can_recall(&env, &caller, &bank_id)?;
+
+        let deferral =
+            Self::get_donor_deferral(env.clone(), bank_id, donor_id).ok_or(Error::InvalidStatus)?;
+
+        recall_donor_units(&env, &caller, &deferral, Some(cursor), limit)
+    }
+
+    /// Get the deferral recorded for a flagged donor, if any
+    pub fn get_donor_deferral(
+        env: Env,
+        bank_id: Address,
+        donor_id: Symbol,
+    ) -> Option<DonorDeferral> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DonorDeferral(bank_id, donor_id))
+    }
+
+    /// Withdraw blood unit (mark as used/discarded)
+    pub fn withdraw_blood(
+        env: Env,
+        caller: Address,
+        unit_id: u64,
+        reason: WithdrawalReason,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        // Verify caller is authorized (blood bank or hospital)
+        let is_bank = Self::is_blood_bank(env.clone(), caller.clone());
+        let is_hosp = Self::is_hospital(env.clone(), caller.clone());
+
+        if !is_bank && !is_hosp {
+            return Err(Error::Unauthorized);
+        }
+
+        // Get blood unit
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
+
+        let old_status = unit.status;
+        let current_time = env.ledger().timestamp();
+
+        // Update unit
+        unit.status = BloodStatus::Discarded;
+
+        registry_write::put_unit(&env, &unit);
+
+        // Record status change
+        record_status_change(
+            &env,
+            unit_id,
+            old_status,
+            BloodStatus::Discarded,
+            caller.clone(),
+        );
 
         // Emit event
         env.events().publish(
@@ -1235,11 +2293,18 @@ impl HealthChainContract {
     }
 
     /// Place a blood unit into explicit quarantine state.
+    ///
+    /// Opens a [`QuarantineCase`] holding the evidence digest and assigned
+    /// reviewer; it must be finalized before its decision deadline or it is
+    /// escalated to discard. The reviewer must be a registered blood bank
+    /// other than `caller`, so nobody decides a case they opened.
     pub fn quarantine_blood(
         env: Env,
         caller: Address,
         unit_id: u64,
         reason: QuarantineReason,
+        evidence_digest: Bytes,
+        reviewer: Address,
     ) -> Result<(), Error> {
         caller.require_auth();
 
@@ -1248,14 +2313,11 @@ impl HealthChainContract {
         if !is_bank && !is_hosp {
             return Err(Error::Unauthorized);
         }
+        if reviewer == caller || !Self::is_blood_bank(env.clone(), reviewer.clone()) {
+            return Err(Error::Unauthorized);
+        }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
         let old_status = unit.status;
 
         if old_status == BloodStatus::Quarantined {
@@ -1267,35 +2329,18 @@ impl HealthChainContract {
             return Err(Error::UnitExpired);
         }
 
-        unit.status = BloodStatus::Quarantined;
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
-
-        record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            BloodStatus::Quarantined,
-            caller.clone(),
-        );
-
-        let quarantine_event = QuarantineLifecycleEvent {
-            blood_unit_id: unit_id,
-            old_status,
-            new_status: BloodStatus::Quarantined,
-            actor: caller,
-            reason,
-            disposition_code: 0,
-            timestamp: current_time,
-        };
-
-        env.events()
-            .publish((symbol_short!("quar"), symbol_short!("place")), quarantine_event);
+        // A unit held mid-transfer stays with whoever last had it
+        cancel_open_custody(&env, unit_id)?;
+        quarantine::place(&env, &mut unit, caller, reason, evidence_digest, reviewer);
 
         Ok(())
     }
 
     /// Finalize quarantine with explicit release (Available) or discard outcome.
+    ///
+    /// Only the reviewer assigned to the open case may decide it, or the admin
+    /// as an explicit override. Units quarantined before cases were persisted
+    /// have no reviewer and may be decided by any bank or hospital.
     pub fn finalize_quarantine(
         env: Env,
         caller: Address,
@@ -1305,19 +2350,22 @@ impl HealthChainContract {
     ) -> Result<(), Error> {
         caller.require_auth();
 
-        let is_bank = Self::is_blood_bank(env.clone(), caller.clone());
-        let is_hosp = Self::is_hospital(env.clone(), caller.clone());
-        if !is_bank && !is_hosp {
+        let admin: Option<Address> = env.storage().instance().get(&ADMIN);
+        let is_admin = admin.as_ref() == Some(&caller);
+        let open_case = quarantine::get_case(&env, unit_id)
+            .filter(|case| case.status == QuarantineCaseStatus::Open);
+        let may_decide = match open_case {
+            Some(case) => caller == case.reviewer,
+            None => {
+                Self::is_blood_bank(env.clone(), caller.clone())
+                    || Self::is_hospital(env.clone(), caller.clone())
+            }
+        };
+        if !may_decide && !is_admin {
             return Err(Error::Unauthorized);
         }
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
         let old_status = unit.status;
         if old_status != BloodStatus::Quarantined {
             return Err(Error::InvalidStatus);
@@ -1329,11 +2377,16 @@ impl HealthChainContract {
         };
 
         unit.status = new_status;
-        units.set(unit_id, unit.clone());
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        registry_write::put_unit(&env, &unit);
 
         record_status_change(&env, unit_id, old_status, new_status, caller.clone());
 
+        let case_status = match disposition {
+            QuarantineDisposition::Release => QuarantineCaseStatus::Released,
+            QuarantineDisposition::Discard => QuarantineCaseStatus::Discarded,
+        };
+        quarantine::close(&env, unit_id, case_status, caller.clone());
+
         let quarantine_event = QuarantineLifecycleEvent {
             blood_unit_id: unit_id,
             old_status,
@@ -1347,94 +2400,430 @@ impl HealthChainContract {
             timestamp: env.ledger().timestamp(),
         };
 
-        env.events()
-            .publish((symbol_short!("quar"), symbol_short!("final")), quarantine_event);
+        env.events().publish(
+            (symbol_short!("quar"), symbol_short!("final")),
+            quarantine_event,
+        );
 
         Ok(())
     }
 
-    // ── READ ──────────────────────────────────────────────────────────────────
+    /// Set the decision window for newly opened quarantine cases (admin only;
+    /// council proposal once a council is set)
+    pub fn set_quarantine_review_window(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
 
-    /// Get blood unit by ID.
-    ///
-    /// Delegates to [`registry_read::get_unit`].
-    pub fn get_blood_unit(env: Env, unit_id: u64) -> Result<BloodUnit, Error> {
-        registry_read::get_unit(&env, unit_id)
+        store_quarantine_review_window(&env, seconds)
     }
 
-    /// Get blood status.
-    ///
-    /// Delegates to [`registry_read::get_unit`].
-    pub fn get_blood_status(env: Env, unit_id: u64) -> Result<BloodStatus, Error> {
-        let unit = registry_read::get_unit(&env, unit_id)?;
-        Ok(unit.status)
+    /// Get the decision window for new quarantine cases, in seconds
+    pub fn get_quarantine_review_window(env: Env) -> u64 {
+        quarantine::review_window(&env)
     }
 
-    /// Check whether a blood unit's expiration date has passed.
-    ///
-    /// Delegates to [`registry_read::is_expired`].
-    pub fn is_expired(env: Env, unit_id: u64) -> Result<bool, Error> {
-        registry_read::is_expired(&env, unit_id)
+    /// Get the latest quarantine case opened for a unit, if any
+    pub fn get_quarantine_case(env: Env, unit_id: u64) -> Option<QuarantineCase> {
+        quarantine::get_case(&env, unit_id)
     }
 
-    /// Return all blood units donated by the given donor.
+    /// Page through open quarantine cases in ascending unit ID order.
     ///
-    /// Delegates to [`registry_read::get_units_by_donor`].
-    pub fn get_units_by_donor(env: Env, donor_id: Symbol) -> Vec<BloodUnit> {
-        registry_read::get_units_by_donor(&env, donor_id)
+    /// Delegates to [`quarantine::get_open_quarantines`].
+    pub fn get_open_quarantines(env: Env, cursor: Option<u64>, limit: u32) -> QuarantineCasePage {
+        quarantine::get_open_quarantines(&env, cursor, limit)
     }
 
-    /// Query blood units by status
-    pub fn query_by_status(env: Env, status: BloodStatus, max_results: u32) -> Vec<BloodUnit> {
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        let mut results = vec![&env];
-        let mut count = 0u32;
+    /// Check up to `limit` open cases after `cursor`, in ascending unit ID
+    /// order, and discard the units whose case passed its decision deadline
+    /// without a decision. Permissionless.
+    ///
+    /// Each overdue case is closed as `Escalated` and a `("quar", "final")`
+    /// event with the discard disposition is emitted. Returns the escalated
+    /// unit IDs and, while open cases remain unchecked, the cursor to pass to
+    /// the next call.
+    pub fn escalate_overdue_quarantines(
+        env: Env,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> Result<QuarantineEscalationPage, Error> {
+        if limit > MAX_BATCH_EXPIRY_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
+        if limit == 0 {
+            return Err(Error::InvalidQuantity);
+        }
 
-        for (_, unit) in units.iter() {
-            if unit.status == status {
-                results.push_back(unit);
-                count += 1;
-                if max_results > 0 && count >= max_results {
-                    break;
-                }
+        let current_time = env.ledger().timestamp();
+        let actor = env.current_contract_address();
+
+        let (unit_ids, next_cursor) = quarantine::open_case_ids_after(&env, cursor, limit);
+        let mut escalated = Vec::new(&env);
+        for unit_id in unit_ids.iter() {
+            let case = match quarantine::get_case(&env, unit_id) {
+                Some(case) => case,
+                None => continue,
+            };
+            if current_time < case.decision_deadline {
+                continue;
             }
+
+            let mut unit = registry_read::get_unit(&env, unit_id)?;
+            if unit.status == BloodStatus::Quarantined {
+                unit.status = BloodStatus::Discarded;
+                registry_write::put_unit(&env, &unit);
+                record_status_change(
+                    &env,
+                    unit_id,
+                    BloodStatus::Quarantined,
+                    BloodStatus::Discarded,
+                    actor.clone(),
+                );
+            }
+            quarantine::close(
+                &env,
+                unit_id,
+                QuarantineCaseStatus::Escalated,
+                actor.clone(),
+            );
+
+            env.events().publish(
+                (symbol_short!("quar"), symbol_short!("final")),
+                QuarantineLifecycleEvent {
+                    blood_unit_id: unit_id,
+                    old_status: BloodStatus::Quarantined,
+                    new_status: unit.status,
+                    actor: actor.clone(),
+                    reason: case.reason,
+                    disposition_code: 2,
+                    timestamp: current_time,
+                },
+            );
+            escalated.push_back(unit_id);
         }
 
-        results
+        Ok(QuarantineEscalationPage {
+            escalated,
+            next_cursor,
+        })
     }
 
-    /// Query blood units by hospital
-    pub fn query_by_hospital(env: Env, hospital: Address, max_results: u32) -> Vec<BloodUnit> {
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+    // ── READ ──────────────────────────────────────────────────────────────────
 
-        let mut results = vec![&env];
-        let mut count = 0u32;
+    /// Get blood unit by ID.
+    ///
+    /// Delegates to [`registry_read::get_unit`].
+    pub fn get_blood_unit(env: Env, unit_id: u64) -> Result<BloodUnit, Error> {
+        registry_read::get_unit(&env, unit_id)
+    }
 
-        for (_, unit) in units.iter() {
-            if unit.recipient_hospital == Some(hospital.clone()) {
-                results.push_back(unit);
-                count += 1;
-                if max_results > 0 && count >= max_results {
-                    break;
-                }
-            }
-        }
+    /// Get blood status.
+    ///
+    /// Delegates to [`registry_read::get_unit`].
+    pub fn get_blood_status(env: Env, unit_id: u64) -> Result<BloodStatus, Error> {
+        let unit = registry_read::get_unit(&env, unit_id)?;
+        Ok(unit.status)
+    }
+
+    /// Check whether a blood unit's expiration date has passed.
+    ///
+    /// Delegates to [`registry_read::is_expired`].
+    pub fn is_expired(env: Env, unit_id: u64) -> Result<bool, Error> {
+        registry_read::is_expired(&env, unit_id)
+    }
+
+    /// Return one page of blood units donated by the given donor.
+    ///
+    /// Delegates to [`registry_read::get_units_by_donor`].
+    pub fn get_units_by_donor(
+        env: Env,
+        donor_id: Symbol,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> UnitPage {
+        registry_read::get_units_by_donor(&env, donor_id, cursor, limit)
+    }
+
+    /// Query blood units by status, one page at a time in ascending ID order
+    pub fn query_by_status(
+        env: Env,
+        status: BloodStatus,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> UnitPage {
+        registry_read::page_index(&env, &UnitIndex::Status(status), cursor, limit)
+    }
 
-        results
+    /// Query blood units by hospital, one page at a time in ascending ID order
+    pub fn query_by_hospital(
+        env: Env,
+        hospital: Address,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> UnitPage {
+        registry_read::page_index(&env, &UnitIndex::Hospital(hospital), cursor, limit)
     }
 }
 
 // ── SHARED HELPERS (Internal) ──
 
+pub(crate) fn store_blood_bank(env: &Env, bank_id: Address) {
+    let mut banks: Map<Address, bool> = env
+        .storage()
+        .persistent()
+        .get(&BLOOD_BANKS)
+        .unwrap_or(Map::new(env));
+
+    institutions::clear_deregistration(env, &bank_id);
+    delegates::revoke_on_registration(env, &bank_id);
+    banks.set(bank_id, true);
+    env.storage().persistent().set(&BLOOD_BANKS, &banks);
+}
+
+pub(crate) fn store_hospital(env: &Env, hospital_id: Address) {
+    let mut hospitals: Map<Address, bool> = env
+        .storage()
+        .persistent()
+        .get(&HOSPITALS)
+        .unwrap_or(Map::new(env));
+
+    institutions::clear_deregistration(env, &hospital_id);
+    delegates::revoke_on_registration(env, &hospital_id);
+    hospitals.set(hospital_id, true);
+    env.storage().persistent().set(&HOSPITALS, &hospitals);
+}
+
+/// Migrate up to `max_units` legacy units and return how many remain.
+pub(crate) fn migrate_units(env: &Env, max_units: u32) -> Result<u32, Error> {
+    if max_units > MAX_BATCH_SIZE {
+        return Err(Error::BatchSizeExceeded);
+    }
+
+    let remaining = registry_write::migrate_legacy_units(env, max_units);
+
+    env.events().publish(
+        (symbol_short!("units"), symbol_short!("migrate")),
+        (max_units, remaining),
+    );
+
+    Ok(remaining)
+}
+
+pub(crate) fn store_fee_policy(
+    env: &Env,
+    fee_structure: FeeStructure,
+    treasury: Address,
+) -> Result<(), Error> {
+    if fee_structure.validate().is_err() {
+        return Err(Error::InvalidQuantity);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::FeeStructure, &fee_structure);
+    env.storage().instance().set(&DataKey::Treasury, &treasury);
+
+    Ok(())
+}
+
+pub(crate) fn store_arbitration_panel(
+    env: &Env,
+    arbiters: Vec<Address>,
+    threshold: u32,
+    vote_window: u64,
+    default_outcome: DisputeStatus,
+) -> Result<(), Error> {
+    if arbiters.is_empty() {
+        env.storage().instance().remove(&DataKey::ArbitrationPanel);
+        return Ok(());
+    }
+
+    if threshold == 0 || threshold > arbiters.len() || vote_window == 0 {
+        return Err(Error::InvalidQuantity);
+    }
+    // A split needs amounts, so the fallback must be a whole outcome
+    if matches!(
+        default_outcome,
+        DisputeStatus::Open | DisputeStatus::PartialSettlement
+    ) {
+        return Err(Error::InvalidDisputeStatus);
+    }
+
+    let panel = ArbitrationPanel {
+        arbiters,
+        threshold,
+        vote_window,
+        default_outcome,
+    };
+    env.storage()
+        .instance()
+        .set(&DataKey::ArbitrationPanel, &panel);
+
+    Ok(())
+}
+
+pub(crate) fn store_dispute_response_window(env: &Env, seconds: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::DisputeResponseWindow, &seconds);
+}
+
+pub(crate) fn store_crossmatch_policy(env: &Env, policy: CrossmatchPolicy) {
+    env.storage()
+        .instance()
+        .set(&DataKey::CrossmatchPolicy, &policy);
+}
+
+pub(crate) fn store_suspension_policy(env: &Env, policy: SuspensionPolicy) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SuspensionPolicy, &policy);
+}
+
+pub(crate) fn store_component_policy(
+    env: &Env,
+    component: BloodComponent,
+    policy: ComponentPolicy,
+) -> Result<(), Error> {
+    if policy.min_shelf_life_days == 0 || policy.min_shelf_life_days > policy.max_shelf_life_days {
+        return Err(Error::InvalidExpiration);
+    }
+    if policy.min_volume_ml == 0 || policy.min_volume_ml > policy.max_volume_ml {
+        return Err(Error::InvalidQuantity);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::ComponentPolicy(component), &policy);
+
+    env.events().publish(
+        (symbol_short!("policy"), symbol_short!("component")),
+        (component, policy),
+    );
+
+    Ok(())
+}
+
+pub(crate) fn store_stale_transfer_policy(env: &Env, policy: StaleTransferPolicy) {
+    env.storage()
+        .instance()
+        .set(&DataKey::StaleTransferPolicy, &policy);
+}
+
+pub(crate) fn store_quarantine_review_window(env: &Env, seconds: u64) -> Result<(), Error> {
+    if seconds == 0 {
+        return Err(Error::InvalidExpiration);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::QuarantineReviewWindow, &seconds);
+
+    Ok(())
+}
+
+pub(crate) fn store_courier(env: &Env, courier: Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Courier(courier.clone()), &true);
+
+    env.events().publish(
+        (symbol_short!("courier"), symbol_short!("register")),
+        courier,
+    );
+}
+
+pub(crate) fn store_nomination(env: &Env, nominee: Address) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+
+    // Lazily clear an expired nomination so a new one can be made.
+    if let Some(entry) = env
+        .storage()
+        .instance()
+        .get::<DataKey, NominationEntry>(&DataKey::PendingNominee)
+    {
+        let expired = now > entry.nominated_at.saturating_add(NOMINATION_EXPIRY_SECONDS);
+        if !expired {
+            return Err(Error::NominationPending);
+        }
+        env.storage().instance().remove(&DataKey::PendingNominee);
+    }
+
+    env.storage().instance().set(
+        &DataKey::PendingNominee,
+        &NominationEntry {
+            nominee,
+            nominated_at: now,
+        },
+    );
+    Ok(())
+}
+
+/// Run an approved council proposal's action and mark it executed.
+pub(crate) fn execute_proposal(env: &Env, proposal: &mut Proposal) -> Result<(), Error> {
+    match proposal.action.clone() {
+        CouncilAction::RegisterBloodBank(bank_id) => store_blood_bank(env, bank_id),
+        CouncilAction::RegisterHospital(hospital_id) => store_hospital(env, hospital_id),
+        CouncilAction::ResolveDispute(dispute_id, resolution) => {
+            let dispute = HealthChainContract::get_dispute(env.clone(), dispute_id)?;
+            if dispute.vote_deadline > 0 {
+                return Err(Error::Unauthorized);
+            }
+            settle_dispute(env, dispute_id, resolution, None)?;
+        }
+        CouncilAction::MigrateUnitStorage(max_units) => {
+            migrate_units(env, max_units)?;
+        }
+        CouncilAction::SetCouncil(members, threshold, proposal_ttl) => {
+            council::set_council(env, members, threshold, proposal_ttl)?;
+        }
+        CouncilAction::SuspendInstitution(institution, reason) => {
+            institutions::suspend(env, institution, reason)?;
+        }
+        CouncilAction::ReinstateInstitution(institution, reason) => {
+            institutions::reinstate(env, institution, reason)?;
+        }
+        CouncilAction::DeregisterInstitution(institution, reason) => {
+            institutions::deregister(env, institution, reason)?;
+        }
+        CouncilAction::SetFeePolicy(fee_structure, treasury) => {
+            store_fee_policy(env, fee_structure, treasury)?;
+        }
+        CouncilAction::SetArbitrationPanel(arbiters, threshold, vote_window, default_outcome) => {
+            store_arbitration_panel(env, arbiters, threshold, vote_window, default_outcome)?;
+        }
+        CouncilAction::SetDisputeResponseWindow(seconds) => {
+            store_dispute_response_window(env, seconds)
+        }
+        CouncilAction::SetCrossmatchPolicy(policy) => store_crossmatch_policy(env, policy),
+        CouncilAction::SetSuspensionPolicy(policy) => store_suspension_policy(env, policy),
+        CouncilAction::SetComponentPolicy(component, policy) => {
+            store_component_policy(env, component, policy)?;
+        }
+        CouncilAction::SetStaleTransferPolicy(policy) => store_stale_transfer_policy(env, policy),
+        CouncilAction::SetQuarantineReviewWindow(seconds) => {
+            store_quarantine_review_window(env, seconds)?;
+        }
+        CouncilAction::RegisterCourier(courier) => store_courier(env, courier),
+        CouncilAction::NominateSuperAdmin(nominee) => store_nomination(env, nominee)?,
+    }
+
+    proposal.executed = true;
+    council::put_proposal(env, proposal);
+
+    env.events().publish(
+        (symbol_short!("council"), symbol_short!("execute")),
+        proposal.id,
+    );
+
+    Ok(())
+}
+
 pub(crate) fn get_next_id(env: &Env) -> u64 {
     let id: u64 = env.storage().persistent().get(&NEXT_ID).unwrap_or(1);
     env.storage().persistent().set(&NEXT_ID, &(id + 1));
@@ -1502,158 +2891,548 @@ pub(crate) fn record_request_status_change(
         .publish((symbol_short!("blood"), symbol_short!("request")), event);
 }
 
-/// Append a custody event_id to the paginated trail for a unit
-pub(crate) fn append_to_custody_trail(env: &Env, unit_id: u64, event_id: String) {
-    // Get or create metadata
-    let meta_key = DataKey::UnitTrailMeta(unit_id);
-    let mut metadata: TrailMetadata =
-        env.storage()
-            .persistent()
-            .get(&meta_key)
-            .unwrap_or(TrailMetadata {
-                total_events: 0,
-                total_pages: 0,
-            });
-
-    // Calculate which page this event belongs to
-    let page_number = metadata.total_events / MAX_EVENTS_PER_PAGE;
-    let page_key = DataKey::UnitTrailPage(unit_id, page_number);
+/// Undo what banks committed to a request that is being cancelled or expired.
+///
+/// Reserved units go back to `Available` with their crossmatch links dropped,
+/// escrowed payments of banks that have not delivered are refunded and their
+/// contributions removed. Banks that already delivered keep their payment.
+pub(crate) fn release_request(
+    env: &Env,
+    request: &mut BloodRequest,
+    actor: &Address,
+) -> Result<(), Error> {
+    for unit_id in request.reserved_unit_ids.iter() {
+        let mut unit = registry_read::get_unit(env, unit_id)?;
+        if unit.status == BloodStatus::Reserved
+            && unit.recipient_hospital == Some(request.hospital_id.clone())
+        {
+            unit.status = BloodStatus::Available;
+            unit.recipient_hospital = None;
+            unit.allocation_timestamp = None;
+            registry_write::put_unit(env, &unit);
+            record_status_change(
+                env,
+                unit_id,
+                BloodStatus::Reserved,
+                BloodStatus::Available,
+                actor.clone(),
+            );
+        }
+    }
 
-    // Get or create the page
-    let mut page: Vec<String> = env
+    let payment_ids: Vec<u64> = env
         .storage()
         .persistent()
-        .get(&page_key)
+        .get(&DataKey::RequestPayments(request.id))
         .unwrap_or(Vec::new(env));
-
-    // Append event_id to the page
-    page.push_back(event_id);
-
-    // Save the page
-    env.storage().persistent().set(&page_key, &page);
-
-    // Update metadata
-    metadata.total_events += 1;
-    if page.len() == 1 {
-        // New page was created
-        metadata.total_pages += 1;
+    for payment_id in payment_ids.iter() {
+        let mut payment = escrow::get_payment(env, payment_id)?;
+        if payment.can_transition_to(PaymentStatus::Refunded)
+            && !contributions::has_delivered(env, request.id, &payment.payee)
+        {
+            escrow::refund(env, &mut payment)?;
+        }
     }
 
-    env.storage().persistent().set(&meta_key, &metadata);
+    crossmatch::unlink_request(env, request);
+    contributions::release_undelivered(env, request.id);
+    request.reserved_unit_ids = vec![env];
+    Ok(())
 }
 
-#[contractimpl]
-impl HealthChainContract {
-    /// Get transfer history for a blood unit
-    pub fn get_transfer_history(env: Env, unit_id: u64) -> Vec<StatusChangeEvent> {
-        let history_key = (HISTORY, unit_id);
-        env.storage()
-            .persistent()
-            .get(&history_key)
-            .unwrap_or(Vec::new(&env))
-    }
+/// Apply a dispute resolution: move escrowed funds, record the outcome on the
+/// dispute and its request, and emit `DisputeResolvedEvent`.
+///
+/// Shared by admin resolution and arbitration; callers handle authorization.
+pub(crate) fn settle_dispute(
+    env: &Env,
+    dispute_id: u64,
+    resolution: DisputeStatus,
+    split: Option<DisputeSplit>,
+) -> Result<(), Error> {
+    let mut disputes: Map<u64, Dispute> = env
+        .storage()
+        .persistent()
+        .get(&DISPUTES)
+        .ok_or(Error::DisputeNotFound)?;
 
-    /// Check if an address is an authorized hospital
-    pub fn is_hospital(env: Env, hospital_id: Address) -> bool {
-        let hospitals: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&HOSPITALS)
-            .unwrap_or(Map::new(&env));
+    let mut dispute = disputes.get(dispute_id).ok_or(Error::DisputeNotFound)?;
 
-        hospitals.get(hospital_id).unwrap_or(false)
+    if dispute.status != DisputeStatus::Open || resolution == DisputeStatus::Open {
+        return Err(Error::InvalidDisputeStatus);
+    }
+    if !dispute.evidence_closed(env.ledger().timestamp()) {
+        return Err(Error::EvidenceWindowOpen);
+    }
+    if split.is_some() != (resolution == DisputeStatus::PartialSettlement) {
+        return Err(Error::InvalidSplit);
     }
 
-    /// Helper: Derive deterministic event_id for custody transfers
-    /// Uses SHA256 hash of: unit_id + from_custodian + to_custodian + ledger_sequence
-    fn derive_event_id(
-        env: &Env,
-        unit_id: u64,
-        from_custodian: &Address,
-        to_custodian: &Address,
-    ) -> String {
-        use soroban_sdk::{Bytes, BytesN};
+    let mut payment = escrow::get_payment(env, dispute.payment_id)?;
+    payment.status = PaymentStatus::Resolved;
 
-        let ledger_sequence = env.ledger().sequence();
+    // Handle funds based on resolution
+    let (payee_amount, payer_amount) = match resolution {
+        DisputeStatus::ResolvedInFavorOfPayer => (0, escrow::refund(env, &mut payment)?),
+        DisputeStatus::ResolvedInFavorOfPayee => {
+            escrow::payout(env, &mut payment)?;
+            (payment.amount, 0)
+        }
+        DisputeStatus::PartialSettlement => {
+            let split = match split {
+                Some(DisputeSplit::ProRata) => {
+                    HealthChainContract::suggest_dispute_split(env.clone(), dispute_id)?
+                }
+                Some(split) => split,
+                None => return Err(Error::InvalidSplit),
+            };
+            let payee_amount = split
+                .payee_amount(payment.amount)
+                .map_err(|_| Error::InvalidSplit)?;
+            let payer_amount = escrow::settle(env, &mut payment, payee_amount)?;
+            (payee_amount, payer_amount)
+        }
+        _ => {
+            // Dismissed: the payment can still be released or refunded
+            payment.status = PaymentStatus::Escrowed;
+            escrow::put_payment(env, &payment);
+            (0, 0)
+        }
+    };
 
-        // Create input bytes for hashing
-        let mut input = Bytes::new(env);
+    dispute.status = resolution;
+    dispute.resolved_at = Some(env.ledger().timestamp());
+    dispute.payee_amount = payee_amount;
+    dispute.payer_amount = payer_amount;
+    disputes.set(dispute_id, dispute.clone());
+    env.storage().persistent().set(&DISPUTES, &disputes);
 
-        // Add unit_id (8 bytes)
-        for byte in unit_id.to_be_bytes().iter() {
-            input.push_back(*byte);
+    // Update Request Status
+    let mut requests: Map<u64, BloodRequest> = env
+        .storage()
+        .persistent()
+        .get(&REQUESTS)
+        .unwrap_or(Map::new(env));
+
+    // Only a request the dispute moved to Disputed follows its outcome
+    if let Some(mut request) = requests
+        .get(payment.request_id)
+        .filter(|request| request.status == RequestStatus::Disputed)
+    {
+        if resolution == DisputeStatus::Dismissed {
+            request.status = dispute.request_status;
+            request_queue::sync(env, &request);
+        } else {
+            request.status = RequestStatus::Resolved;
         }
+        requests.set(payment.request_id, request);
+        env.storage().persistent().set(&REQUESTS, &requests);
+    }
 
-        // Add from_custodian as Val (8 bytes)
-        let from_val_u64: u64 = from_custodian.to_val().get_payload();
-        for byte in from_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
-        }
+    // Emit DisputeResolvedEvent
+    env.events().publish(
+        (symbol_short!("dispute"), symbol_short!("resolved")),
+        DisputeResolvedEvent {
+            dispute_id,
+            payment_id: dispute.payment_id,
+            status: resolution,
+            payee_amount,
+            payer_amount,
+            resolved_at: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
 
-        // Add to_custodian as Val (8 bytes)
-        let to_val_u64: u64 = to_custodian.to_val().get_payload();
-        for byte in to_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
-        }
+/// Append an entry to a dispute's evidence log and return its index.
+pub(crate) fn append_dispute_evidence(
+    env: &Env,
+    dispute_id: u64,
+    party: Address,
+    digest: Bytes,
+    chunks: Vec<String>,
+) -> u64 {
+    let count_key = DataKey::DisputeEvidenceCount(dispute_id);
+    let index: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let entry = DisputeEvidence {
+        dispute_id,
+        index,
+        party,
+        digest,
+        chunks,
+        submitted_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::DisputeEvidence(dispute_id, index), &entry);
+    env.storage().persistent().set(&count_key, &(index + 1));
 
-        // Add ledger_sequence (4 bytes)
-        for byte in ledger_sequence.to_be_bytes().iter() {
-            input.push_back(*byte);
-        }
+    env.events()
+        .publish((symbol_short!("dispute"), symbol_short!("evidence")), entry);
 
-        // Compute SHA256 hash
-        let hash: BytesN<32> = env.crypto().sha256(&input).into();
+    index
+}
 
-        // Convert hash to hex string
-        let hex_chars = b"0123456789abcdef";
-        let mut hex_array = [0u8; HEX_HASH_LENGTH];
+/// Open a Pending custody hop for a unit and return the stored event.
+///
+/// Consumes the unit's next custody nonce, derives the event_id, stores the
+/// event and records it as the unit's open hop.
+pub(crate) fn open_custody_hop(
+    env: &Env,
+    unit_id: u64,
+    from_custodian: &Address,
+    to_custodian: &Address,
+) -> Result<CustodyEvent, Error> {
+    let nonce = HealthChainContract::get_custody_nonce(env.clone(), unit_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::CustodyNonce(unit_id), &(nonce + 1));
 
-        for i in 0..32u32 {
-            let byte = hash.get(i).unwrap();
-            let high = (byte >> 4) & 0x0f;
-            let low = byte & 0x0f;
-            hex_array[(i * 2) as usize] = hex_chars[high as usize];
-            hex_array[(i * 2 + 1) as usize] = hex_chars[low as usize];
-        }
+    let event_id =
+        HealthChainContract::derive_event_id(env, unit_id, from_custodian, to_custodian, nonce);
 
-        String::from_bytes(env, &hex_array)
+    // Validate event_id length (should always be HEX_HASH_LENGTH, but check for safety)
+    if event_id.len() > MAX_UNIT_ID_LENGTH {
+        return Err(Error::UnitIdTooLong);
     }
 
-    /// Public function to compute event_id for a given transfer
-    /// Callers can use this to compute the event_id needed for confirm_transfer and cancel_transfer
-    pub fn compute_event_id(
-        env: Env,
-        unit_id: u64,
-        from_custodian: Address,
-        to_custodian: Address,
-        ledger_sequence: u32,
-    ) -> String {
-        use soroban_sdk::{Bytes, BytesN};
+    let custody_event = CustodyEvent {
+        event_id: event_id.clone(),
+        unit_id,
+        from_custodian: from_custodian.clone(),
+        to_custodian: to_custodian.clone(),
+        initiated_at: env.ledger().timestamp(),
+        ledger_sequence: env.ledger().sequence(),
+        status: CustodyStatus::Pending,
+    };
 
-        // Create input bytes for hashing
-        let mut input = Bytes::new(&env);
+    let mut custody_events: Map<String, CustodyEvent> = env
+        .storage()
+        .persistent()
+        .get(&CUSTODY_EVENTS)
+        .unwrap_or(Map::new(env));
 
-        // Add unit_id (8 bytes)
-        for byte in unit_id.to_be_bytes().iter() {
-            input.push_back(*byte);
-        }
+    custody_events.set(event_id.clone(), custody_event.clone());
+    env.storage()
+        .persistent()
+        .set(&CUSTODY_EVENTS, &custody_events);
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingCustody(unit_id), &event_id);
 
-        // Add from_custodian as Val (8 bytes)
-        let from_val_u64: u64 = from_custodian.to_val().get_payload();
-        for byte in from_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
+    queue_pending_transfer(env, (custody_event.initiated_at, unit_id, event_id));
+
+    Ok(custody_event)
+}
+
+/// Move a Pending custody hop to its final status and clear it as the unit's
+/// open hop. Returns the updated event.
+pub(crate) fn close_custody_hop(
+    env: &Env,
+    mut custody_event: CustodyEvent,
+    status: CustodyStatus,
+) -> CustodyEvent {
+    let mut custody_events: Map<String, CustodyEvent> = env
+        .storage()
+        .persistent()
+        .get(&CUSTODY_EVENTS)
+        .unwrap_or(Map::new(env));
+
+    custody_event.status = status;
+    custody_events.set(custody_event.event_id.clone(), custody_event.clone());
+    env.storage()
+        .persistent()
+        .set(&CUSTODY_EVENTS, &custody_events);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingCustody(custody_event.unit_id));
+
+    drop_pending_transfer(
+        env,
+        &(
+            custody_event.initiated_at,
+            custody_event.unit_id,
+            custody_event.event_id.clone(),
+        ),
+    );
+
+    custody_event
+}
+
+/// Add an open custody hop `(initiated_at, unit_id, event_id)` to the sweep
+/// queue, bucketed by the hour it was initiated
+fn queue_pending_transfer(env: &Env, entry: (u64, u64, String)) {
+    let bucket = entry.0 / QUEUE_BUCKET_SECONDS;
+    bucket_queue::insert(env, Queue::Transfers, bucket, entry);
+}
+
+/// Remove one entry from the sweep queue, if present
+fn drop_pending_transfer(env: &Env, entry: &(u64, u64, String)) {
+    bucket_queue::remove(env, Queue::Transfers, entry.0 / QUEUE_BUCKET_SECONDS, entry);
+}
+
+/// Cancel `unit_id`'s open custody hop, if any, and clear its courier
+/// custodian, for a unit leaving the transfer chain into quarantine
+pub(crate) fn cancel_open_custody(env: &Env, unit_id: u64) -> Result<(), Error> {
+    let pending: Option<String> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PendingCustody(unit_id));
+    if let Some(event_id) = pending {
+        let custody_event = HealthChainContract::get_custody_event(env.clone(), event_id)?;
+        close_custody_hop(env, custody_event, CustodyStatus::Cancelled);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::UnitCustodian(unit_id));
+    Ok(())
+}
+
+/// Whether a unit is between custodians (dispatch or re-transfer)
+pub(crate) fn is_in_transit(status: BloodStatus) -> bool {
+    status == BloodStatus::InTransit || status == BloodStatus::InRetransfer
+}
+
+/// Only the admin or the donor's own bank may run a recall
+fn ensure_can_recall(env: &Env, caller: &Address, bank_id: &Address) -> Result<(), Error> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&ADMIN)
+        .ok_or(Error::Unauthorized)?;
+    let is_own_bank =
+        caller == bank_id && HealthChainContract::is_blood_bank(env.clone(), bank_id.clone());
+    if *caller != admin && !is_own_bank {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Check up to `limit` of a flagged donor's units after `cursor`,
+/// quarantining those still in circulation inside the deferral's look-back
+/// window and reporting delivered ones to their hospitals.
+fn recall_donor_units(
+    env: &Env,
+    caller: &Address,
+    deferral: &DonorDeferral,
+    cursor: Option<u64>,
+    limit: u32,
+) -> Result<DonorRecallPage, Error> {
+    if limit > MAX_BATCH_SIZE {
+        return Err(Error::BatchSizeExceeded);
+    }
+    if limit == 0 {
+        return Err(Error::InvalidQuantity);
+    }
+
+    let window_start = deferral.flagged_at.saturating_sub(deferral.lookback_window);
+    let donor_index = UnitIndex::Donor(deferral.bank_id.clone(), deferral.donor_id.clone());
+    let (unit_ids, next_cursor) = registry_read::index_ids(env, &donor_index, cursor, limit);
+
+    let mut quarantined = Vec::new(env);
+    let mut delivered: Map<Address, Vec<u64>> = Map::new(env);
+
+    for unit_id in unit_ids.iter() {
+        let mut unit = registry_read::get_unit(env, unit_id)?;
+        if unit.registration_timestamp < window_start {
+            continue;
         }
 
-        // Add to_custodian as Val (8 bytes)
-        let to_val_u64: u64 = to_custodian.to_val().get_payload();
-        for byte in to_val_u64.to_be_bytes().iter() {
-            input.push_back(*byte);
+        match unit.status {
+            BloodStatus::Available
+            | BloodStatus::Reserved
+            | BloodStatus::InTransit
+            | BloodStatus::InRetransfer => {
+                cancel_open_custody(env, unit_id)?;
+                quarantine::place(
+                    env,
+                    &mut unit,
+                    caller.clone(),
+                    QuarantineReason::DonorEvent,
+                    Bytes::new(env),
+                    deferral.bank_id.clone(),
+                );
+                quarantined.push_back(unit_id);
+            }
+            BloodStatus::Delivered => {
+                if let Some(hospital) = unit.recipient_hospital {
+                    let mut ids = delivered.get(hospital.clone()).unwrap_or(Vec::new(env));
+                    ids.push_back(unit_id);
+                    delivered.set(hospital, ids);
+                }
+            }
+            _ => {}
         }
+    }
+
+    for (hospital, unit_ids) in delivered.iter() {
+        env.events().publish(
+            (symbol_short!("donor"), symbol_short!("recall")),
+            DonorRecallEvent {
+                bank_id: deferral.bank_id.clone(),
+                donor_id: deferral.donor_id.clone(),
+                hospital,
+                unit_ids,
+                reason: deferral.reason.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    Ok(DonorRecallPage {
+        quarantined,
+        next_cursor,
+    })
+}
+
+/// Cancel a Pending custody hop and put `unit` back in its pre-hop state.
+///
+/// A bank's hop returns the unit to `Reserved` and a re-transfer returns it to
+/// `Delivered`; if a courier holds the unit it simply stays with the courier.
+pub(crate) fn revert_custody_hop(
+    env: &Env,
+    custody_event: CustodyEvent,
+    unit: &mut BloodUnit,
+    actor: Address,
+) -> CustodyEvent {
+    let custody_event = close_custody_hop(env, custody_event, CustodyStatus::Cancelled);
+
+    let holder: Option<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::UnitCustodian(unit.id));
+
+    if holder.is_none() && is_in_transit(unit.status) {
+        let old_status = unit.status;
+
+        // Keep recipient_hospital + allocation_timestamp.
+        let new_status = if old_status == BloodStatus::InRetransfer {
+            BloodStatus::Delivered
+        } else {
+            unit.transfer_timestamp = None;
+            BloodStatus::Reserved
+        };
+        unit.status = new_status;
+
+        registry_write::put_unit(env, unit);
+        record_status_change(env, unit.id, old_status, new_status, actor);
+    }
+
+    custody_event
+}
+
+/// Append a custody event_id to the paginated trail for a unit
+pub(crate) fn append_to_custody_trail(env: &Env, unit_id: u64, event_id: String) {
+    // Get or create metadata
+    let meta_key = DataKey::UnitTrailMeta(unit_id);
+    let mut metadata: TrailMetadata =
+        env.storage()
+            .persistent()
+            .get(&meta_key)
+            .unwrap_or(TrailMetadata {
+                total_events: 0,
+                total_pages: 0,
+            });
+
+    // Calculate which page this event belongs to
+    let page_number = metadata.total_events / MAX_EVENTS_PER_PAGE;
+    let page_key = DataKey::UnitTrailPage(unit_id, page_number);
+
+    // Get or create the page
+    let mut page: Vec<String> = env
+        .storage()
+        .persistent()
+        .get(&page_key)
+        .unwrap_or(Vec::new(env));
+
+    // Append event_id to the page
+    page.push_back(event_id);
+
+    // Save the page
+    env.storage().persistent().set(&page_key, &page);
+
+    // Update metadata
+    metadata.total_events += 1;
+    if page.len() == 1 {
+        // New page was created
+        metadata.total_pages += 1;
+    }
+
+    env.storage().persistent().set(&meta_key, &metadata);
+}
+
+#[contractimpl]
+impl HealthChainContract {
+    /// Get one page of the transfer history for a blood unit, oldest first.
+    ///
+    /// The history is append-only, so the cursor is the position of the next
+    /// event to return.
+    pub fn get_transfer_history(
+        env: Env,
+        unit_id: u64,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> StatusChangePage {
+        let history_key = (HISTORY, unit_id);
+        let history: Vec<StatusChangeEvent> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env));
 
-        // Add ledger_sequence (4 bytes)
-        for byte in ledger_sequence.to_be_bytes().iter() {
-            input.push_back(*byte);
+        let len = history.len();
+        let start = cursor.unwrap_or(0).min(len as u64) as u32;
+        let end = start
+            .saturating_add(registry_read::page_limit(limit))
+            .min(len);
+
+        StatusChangePage {
+            items: history.slice(start..end),
+            next_cursor: if end < len { Some(end as u64) } else { None },
         }
+    }
+
+    /// Check if an address is an authorized hospital
+    pub fn is_hospital(env: Env, hospital_id: Address) -> bool {
+        let hospitals: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&HOSPITALS)
+            .unwrap_or(Map::new(&env));
+
+        hospitals.get(hospital_id).unwrap_or(false)
+    }
+
+    /// Helper: Derive deterministic event_id for custody transfers
+    ///
+    /// SHA-256 over, in order:
+    /// - `EVENT_ID_VERSION` (1 byte)
+    /// - `unit_id` (8 bytes, big-endian)
+    /// - XDR encoding of `from_custodian` as an `ScVal::Address`
+    /// - XDR encoding of `to_custodian` as an `ScVal::Address`
+    /// - per-unit custody `nonce` (8 bytes, big-endian)
+    ///
+    /// rendered as 64 lowercase hex characters. Every input is canonical, so
+    /// off-chain callers can reproduce the ID without calling the contract.
+    /// IDs issued before the version byte existed remain stored under their
+    /// original strings and are still resolved by `get_custody_event`.
+    fn derive_event_id(
+        env: &Env,
+        unit_id: u64,
+        from_custodian: &Address,
+        to_custodian: &Address,
+        nonce: u64,
+    ) -> String {
+        use soroban_sdk::xdr::ToXdr;
+
+        let mut input = Bytes::new(env);
+        input.push_back(EVENT_ID_VERSION);
+        input.extend_from_array(&unit_id.to_be_bytes());
+        input.append(&from_custodian.clone().to_xdr(env));
+        input.append(&to_custodian.clone().to_xdr(env));
+        input.extend_from_array(&nonce.to_be_bytes());
 
         // Compute SHA256 hash
         let hash: BytesN<32> = env.crypto().sha256(&input).into();
@@ -1670,7 +3449,33 @@ impl HealthChainContract {
             hex_array[(i * 2 + 1) as usize] = hex_chars[low as usize];
         }
 
-        String::from_bytes(&env, &hex_array)
+        String::from_bytes(env, &hex_array)
+    }
+
+    /// Public function to compute event_id for a given transfer
+    /// Callers can use this to compute the event_id needed for confirm_transfer and cancel_transfer
+    ///
+    /// `nonce` is the unit's custody nonce at the time the transfer was
+    /// initiated (see `get_custody_nonce`); the derivation is documented on
+    /// `derive_event_id` and can equally be done off-chain.
+    pub fn compute_event_id(
+        env: Env,
+        unit_id: u64,
+        from_custodian: Address,
+        to_custodian: Address,
+        nonce: u64,
+    ) -> String {
+        Self::derive_event_id(&env, unit_id, &from_custodian, &to_custodian, nonce)
+    }
+
+    /// Get the custody nonce the next transfer of this unit will use
+    ///
+    /// Starts at 0 and increases by one for every initiated transfer.
+    pub fn get_custody_nonce(env: Env, unit_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CustodyNonce(unit_id))
+            .unwrap_or(0)
     }
 
     /// Get custody event by event_id
@@ -1750,6 +3555,23 @@ impl HealthChainContract {
         Ok(())
     }
 
+    /// Migrate units from the legacy `UNITS` map into per-unit entries (admin only).
+    ///
+    /// Moves at most `max_units` (capped at `MAX_BATCH_SIZE`) per call so each
+    /// invocation stays within resource limits; call repeatedly until it
+    /// returns 0. Returns the number of units still waiting in the legacy map.
+    pub fn migrate_unit_storage(env: Env, max_units: u32) -> Result<u32, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        migrate_units(&env, max_units)
+    }
+
     /// Create a blood request (hospital only)
     pub fn create_request(
         env: Env,
@@ -1771,6 +3593,7 @@ impl HealthChainContract {
         if !hospitals.get(hospital_id.clone()).unwrap_or(false) {
             return Err(Error::Unauthorized);
         }
+        institutions::ensure_active(&env, &hospital_id)?;
 
         if !(MIN_REQUEST_ML..=MAX_REQUEST_ML).contains(&quantity_ml) {
             return Err(Error::InvalidQuantity);
@@ -1828,6 +3651,7 @@ impl HealthChainContract {
             .get(&REQUESTS)
             .unwrap_or(Map::new(&env));
 
+        request_queue::sync(&env, &request);
         requests.set(request_id, request);
         env.storage().persistent().set(&REQUESTS, &requests);
 
@@ -1851,91 +3675,511 @@ impl HealthChainContract {
         Ok(request_id)
     }
 
-    /// Create a payment for a request
-    pub fn create_payment(
+    /// Get open requests in priority order: Critical first, then earliest
+    /// `required_by`, then oldest.
+    ///
+    /// With `blood_type`, only requests that units of that type can serve are
+    /// returned, so an O-negative bank sees every request. `cursor` is the
+    /// `next_cursor` of the previous page.
+    pub fn get_request_queue(
         env: Env,
-        request_id: u64,
-        payer: Address,
-        payee: Address,
-        amount: i128,
-        asset: Address,
-    ) -> Result<u64, Error> {
-        payer.require_auth();
+        blood_type: Option<BloodType>,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> RequestQueuePage {
+        request_queue::page(&env, blood_type, cursor, limit)
+    }
 
-        let mut payments: Map<u64, Payment> = env
+    /// Set the component a pending request asks for (requesting hospital
+    /// only). Requests ask for whole blood until this is called.
+    pub fn set_request_component(
+        env: Env,
+        request_id: u64,
+        component: BloodComponent,
+    ) -> Result<(), Error> {
+        let requests: Map<u64, BloodRequest> = env
             .storage()
             .persistent()
-            .get(&PAYMENTS)
+            .get(&REQUESTS)
             .unwrap_or(Map::new(&env));
+        let request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+        request.hospital_id.require_auth();
 
-        let payment_id = env
-            .storage()
-            .instance()
-            .get(&NEXT_PAYMENT_ID)
-            .unwrap_or(1u64);
-
-        let payment = Payment {
-            id: payment_id,
-            request_id,
-            payer,
-            payee,
-            amount,
-            asset,
-            fee_structure: FeeStructure {
-                policy_id: Symbol::new(&env, "default_fee_policy"),
-                service_fee: 0,
-                network_fee: 0,
-                performance_bonus: 0,
-                fixed_fee: 0,
-            },
-            status: PaymentStatus::Pending,
-            escrow_released_at: None,
-        };
-
-        if let Err(_) = payment.validate() {
-            return Err(Error::StorageError);
+        // Units already committed were checked against the old component
+        if request.status != RequestStatus::Pending
+            || !contributions::get_contributions(&env, request_id).is_empty()
+        {
+            return Err(Error::InvalidStatus);
         }
 
-        payments.set(payment_id, payment);
-        env.storage().persistent().set(&PAYMENTS, &payments);
         env.storage()
-            .instance()
-            .set(&NEXT_PAYMENT_ID, &(payment_id + 1));
+            .persistent()
+            .set(&DataKey::RequestComponent(request_id), &component);
+        Ok(())
+    }
 
-        Ok(payment_id)
+    /// Get the component a request asks for
+    pub fn get_request_component(env: Env, request_id: u64) -> BloodComponent {
+        Self::requested_component(&env, request_id)
     }
 
-    /// Raise a dispute for a payment
-    pub fn raise_dispute(
+    /// Propose available units of `bank_id` to cover what is left of a
+    /// request: compatible units only, exact matches before substitutes and
+    /// earliest expiry first. Pass the result to `approve_request`.
+    pub fn propose_units(env: Env, bank_id: Address, request_id: u64) -> Result<Vec<u64>, Error> {
+        let requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+        let request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+
+        Ok(compatibility::propose(
+            &env,
+            &bank_id,
+            &request,
+            Self::requested_component(&env, request_id),
+        ))
+    }
+
+    /// Record a crossmatch of a reserved unit against a request (requesting
+    /// hospital or its lab delegate).
+    ///
+    /// Replaces any earlier attestation for the pair. An `Incompatible`
+    /// result returns the unit to its bank's available stock and withdraws it
+    /// from the request, which drops back to `InProgress` if no longer covered.
+    pub fn attest_crossmatch(
         env: Env,
-        payment_id: u64,
-        raised_by: Address,
-        reason: String,
-        evidence_digest: Bytes,
-        evidence_ref_chunks: Vec<String>,
-    ) -> Result<u64, Error> {
-        raised_by.require_auth();
+        lab: Address,
+        request_id: u64,
+        unit_id: u64,
+        result: CrossmatchResult,
+        tested_at: u64,
+        report_digest: BytesN<32>,
+    ) -> Result<(), Error> {
+        lab.require_auth();
+        let hospital = delegates::acting_for(&env, &lab, DelegatePermission::AttestCrossmatch)?;
 
-        let mut payments: Map<u64, Payment> = env
+        let mut requests: Map<u64, BloodRequest> = env
             .storage()
             .persistent()
-            .get(&PAYMENTS)
-            .ok_or(Error::PaymentNotFound)?;
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+        let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
 
-        let mut payment = payments.get(payment_id).ok_or(Error::PaymentNotFound)?;
+        if request.hospital_id != hospital {
+            return Err(Error::UnauthorizedHospital);
+        }
+        if !matches!(
+            request.status,
+            RequestStatus::Approved | RequestStatus::InProgress
+        ) || !request.reserved_unit_ids.contains(unit_id)
+        {
+            return Err(Error::InvalidStatus);
+        }
 
-        if !payment.can_transition_to(PaymentStatus::Disputed) {
-            return Err(Error::InvalidTransition);
+        // Units are crossmatched before they leave the bank
+        let mut unit = registry_read::get_unit(&env, unit_id)?;
+        if unit.status != BloodStatus::Reserved {
+            return Err(Error::InvalidStatus);
         }
 
-        let dispute_id = env
-            .storage()
-            .instance()
-            .get(&NEXT_DISPUTE_ID)
-            .unwrap_or(1u64);
+        let current_time = env.ledger().timestamp();
+        if tested_at > current_time {
+            return Err(Error::InvalidStatus);
+        }
 
-        let dispute = Dispute {
-            id: dispute_id,
+        let attestation = CrossmatchAttestation {
+            request_id,
+            unit_id,
+            result,
+            lab: lab.clone(),
+            tested_at,
+            report_digest,
+            recorded_at: current_time,
+        };
+        crossmatch::put_attestation(&env, &attestation);
+        env.events().publish(
+            (symbol_short!("xmatch"), symbol_short!("attest")),
+            attestation,
+        );
+
+        if result == CrossmatchResult::Compatible {
+            return Ok(());
+        }
+
+        // Return the unit to its bank's available stock
+        let old_status = unit.status;
+        unit.status = BloodStatus::Available;
+        unit.recipient_hospital = None;
+        unit.allocation_timestamp = None;
+        registry_write::put_unit(&env, &unit);
+        crossmatch::unlink_unit(&env, unit_id);
+        record_status_change(
+            &env,
+            unit_id,
+            old_status,
+            BloodStatus::Available,
+            lab.clone(),
+        );
+
+        // Withdraw it from the request
+        let committed_quantity = contributions::withdraw(&env, request_id, unit_id, unit.quantity)?;
+        if let Some(index) = request.reserved_unit_ids.first_index_of(unit_id) {
+            request.reserved_unit_ids.remove(index);
+        }
+        request.fulfilled_quantity_ml = committed_quantity;
+
+        let old_request_status = request.status;
+        if committed_quantity < request.quantity_ml {
+            request.status = RequestStatus::InProgress;
+        }
+        request_queue::sync(&env, &request);
+
+        requests.set(request_id, request.clone());
+        env.storage().persistent().set(&REQUESTS, &requests);
+
+        if request.status != old_request_status {
+            record_request_status_change(
+                &env,
+                request_id,
+                old_request_status,
+                request.status,
+                lab,
+                None,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Get the crossmatch attestation of a unit against a request
+    pub fn get_crossmatch(
+        env: Env,
+        request_id: u64,
+        unit_id: u64,
+    ) -> Option<CrossmatchAttestation> {
+        crossmatch::get_attestation(&env, request_id, unit_id)
+    }
+
+    /// Set whether `confirm_transfer` and `fulfill_request` require a
+    /// compatible crossmatch for Critical and Urgent requests (admin only;
+    /// council proposal once a council is set)
+    pub fn set_crossmatch_policy(env: Env, policy: CrossmatchPolicy) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_crossmatch_policy(&env, policy);
+
+        Ok(())
+    }
+
+    /// Get where crossmatches are required (nowhere by default)
+    pub fn get_crossmatch_policy(env: Env) -> CrossmatchPolicy {
+        crossmatch::policy(&env)
+    }
+
+    /// Get each bank's committed units and delivery for a request, in the
+    /// order the banks first committed
+    pub fn get_request_contributions(env: Env, request_id: u64) -> Vec<BankContribution> {
+        contributions::get_contributions(&env, request_id)
+    }
+
+    /// Set the fee schedule for new payments and the treasury receiving the
+    /// fees (admin only; council proposal once a council is set)
+    pub fn set_fee_policy(
+        env: Env,
+        fee_structure: FeeStructure,
+        treasury: Address,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_fee_policy(&env, fee_structure, treasury)
+    }
+
+    /// Get the fee schedule applied to new payments
+    pub fn get_fee_policy(env: Env) -> FeeStructure {
+        escrow::fee_structure(&env)
+    }
+
+    /// Create a payment for a request and lock `amount` of `asset` in escrow.
+    ///
+    /// The gross `amount` is transferred from the payer to the contract. The
+    /// configured fee schedule is deducted up front, so `Payment::amount` is
+    /// what the payee receives on payout; the fees go to the treasury. Funds
+    /// are only released once `release_conditions` hold. A request may have
+    /// one payment per payee, so each contributing bank is paid its own share.
+    ///
+    /// The request must exist and still be open, the payer must be the
+    /// hospital that made it, the payee a registered blood bank, and `amount`
+    /// positive.
+    pub fn create_payment(
+        env: Env,
+        request_id: u64,
+        payer: Address,
+        payee: Address,
+        amount: i128,
+        asset: Address,
+        release_conditions: ReleaseConditions,
+    ) -> Result<u64, Error> {
+        payer.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidQuantity);
+        }
+
+        let requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+        let request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
+        if !request_queue::is_open(request.status) {
+            return Err(Error::InvalidStatus);
+        }
+        if request.hospital_id != payer {
+            return Err(Error::UnauthorizedHospital);
+        }
+        if !Self::is_blood_bank(env.clone(), payee.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::RequestPayment(request_id, payee.clone()))
+        {
+            return Err(Error::PaymentAlreadyExists);
+        }
+
+        let payment_id = env
+            .storage()
+            .instance()
+            .get(&NEXT_PAYMENT_ID)
+            .unwrap_or(1u64);
+
+        let fee_structure = escrow::fee_structure(&env);
+        let net_amount = fee_structure
+            .calculate_net_amount(amount)
+            .map_err(|_| Error::FeesExceedAmount)?;
+
+        let mut payment = Payment {
+            id: payment_id,
+            request_id,
+            payer,
+            payee,
+            amount: net_amount,
+            asset,
+            fee_structure,
+            status: PaymentStatus::Pending,
+            escrow_released_at: None,
+        };
+
+        if payment.validate().is_err() {
+            return Err(Error::StorageError);
+        }
+
+        let escrow_account = EscrowAccount {
+            payment_id,
+            locked_amount: amount,
+            release_conditions,
+        };
+        escrow::lock(&env, &payment, &escrow_account);
+
+        payment.status = PaymentStatus::Escrowed;
+        escrow::put_payment(&env, &payment);
+        env.storage().persistent().set(
+            &DataKey::RequestPayment(request_id, payment.payee.clone()),
+            &payment_id,
+        );
+        let mut request_payments: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RequestPayments(request_id))
+            .unwrap_or(Vec::new(&env));
+        request_payments.push_back(payment_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RequestPayments(request_id), &request_payments);
+        env.storage()
+            .instance()
+            .set(&NEXT_PAYMENT_ID, &(payment_id + 1));
+
+        Ok(payment_id)
+    }
+
+    /// Get a payment by ID
+    pub fn get_payment(env: Env, payment_id: u64) -> Result<Payment, Error> {
+        escrow::get_payment(&env, payment_id)
+    }
+
+    /// Get the escrow account holding a payment's funds
+    pub fn get_escrow(env: Env, payment_id: u64) -> Result<EscrowAccount, Error> {
+        escrow::get_escrow(&env, payment_id)
+    }
+
+    /// Anchor delivery, signature, photo and medical hashes for a payment.
+    ///
+    /// Submitted by the payer, the escrow's authorized approver, or a
+    /// verifier the payer designated as a delegate with
+    /// `DelegatePermission::ConfirmDelivery`. The payee cannot attest its own
+    /// delivery. The bundle is marked `validated` only when all four hashes
+    /// are 32-byte digests; a validated bundle also marks the escrow's medical
+    /// records as verified. Replaces any earlier bundle.
+    pub fn attach_proof_bundle(
+        env: Env,
+        payment_id: u64,
+        bundle: ProofBundle,
+    ) -> Result<(), Error> {
+        bundle.submitted_by.require_auth();
+
+        let payment = escrow::get_payment(&env, payment_id)?;
+        let escrow_account = escrow::get_escrow(&env, payment_id)?;
+        let attests_for = delegates::acting_for(
+            &env,
+            &bundle.submitted_by,
+            DelegatePermission::ConfirmDelivery,
+        )?;
+        let is_approver = escrow_account.release_conditions.authorized_approver
+            == Some(bundle.submitted_by.clone());
+        if bundle.submitted_by == payment.payee || (attests_for != payment.payer && !is_approver) {
+            return Err(Error::Unauthorized);
+        }
+        if payment.status == PaymentStatus::Pending || payment.is_terminal() {
+            return Err(Error::InvalidPaymentStatus);
+        }
+
+        let mut bundle = bundle;
+        bundle.submitted_at = env.ledger().timestamp();
+        bundle.validated = bundle.hashes_valid();
+
+        if bundle.validated {
+            let mut escrow_account = escrow_account;
+            escrow_account.release_conditions.medical_records_verified = true;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(payment_id), &escrow_account);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProofBundle(payment_id), &bundle);
+
+        env.events().publish(
+            (symbol_short!("escrow"), symbol_short!("proof")),
+            (payment_id, bundle),
+        );
+
+        Ok(())
+    }
+
+    /// Get the proof bundle attached to a payment, if any
+    pub fn get_proof_bundle(env: Env, payment_id: u64) -> Option<ProofBundle> {
+        escrow::get_proof_bundle(&env, payment_id)
+    }
+
+    /// Release an escrowed payment to the payee once its conditions hold.
+    ///
+    /// `approver` must be the conditions' `authorized_approver` when one is
+    /// set, otherwise the payer. Each unmet condition fails with its own error:
+    /// `ReleaseTooEarly`, `MedicalRecordsUnverified`, `ApproverMismatch`,
+    /// `ProofBundleMissing` or `ProofBundleInvalid`.
+    pub fn release_escrow(env: Env, payment_id: u64, approver: Address) -> Result<(), Error> {
+        approver.require_auth();
+
+        let mut payment = escrow::get_payment(&env, payment_id)?;
+        if payment.status != PaymentStatus::Escrowed {
+            return Err(Error::InvalidPaymentStatus);
+        }
+
+        let escrow_account = escrow::get_escrow(&env, payment_id)?;
+        if escrow_account
+            .release_conditions
+            .authorized_approver
+            .is_none()
+            && approver != payment.payer
+        {
+            return Err(Error::ApproverMismatch);
+        }
+
+        escrow::check_release(&env, payment_id, Some(&approver))?;
+        escrow::payout(&env, &mut payment)
+    }
+
+    /// Raise a dispute for a payment
+    ///
+    /// `raised_by` may be the payer, the payee, or one of their delegates;
+    /// the dispute is recorded against the party the delegate acts for.
+    pub fn raise_dispute(
+        env: Env,
+        payment_id: u64,
+        raised_by: Address,
+        reason: String,
+        evidence_digest: Bytes,
+        evidence_ref_chunks: Vec<String>,
+    ) -> Result<u64, Error> {
+        raised_by.require_auth();
+        let raised_by = delegates::acting_for(&env, &raised_by, DelegatePermission::RaiseDispute)?;
+
+        let mut payments: Map<u64, Payment> = env
+            .storage()
+            .persistent()
+            .get(&PAYMENTS)
+            .ok_or(Error::PaymentNotFound)?;
+
+        let mut payment = payments.get(payment_id).ok_or(Error::PaymentNotFound)?;
+
+        if raised_by != payment.payer && raised_by != payment.payee {
+            return Err(Error::Unauthorized);
+        }
+        if !payment.can_transition_to(PaymentStatus::Disputed) {
+            return Err(Error::InvalidTransition);
+        }
+
+        let dispute_id = env
+            .storage()
+            .instance()
+            .get(&NEXT_DISPUTE_ID)
+            .unwrap_or(1u64);
+
+        let response_deadline = env
+            .ledger()
+            .timestamp()
+            .saturating_add(Self::get_dispute_response_window(env.clone()));
+
+        // Disputes raised while a panel is configured go to arbitration once
+        // evidence is in, before that panel even if it is later replaced
+        let (vote_deadline, arbiters, vote_threshold, default_outcome) =
+            match Self::get_arbitration_panel(env.clone()) {
+                Some(panel) => (
+                    response_deadline.saturating_add(panel.vote_window),
+                    panel.arbiters,
+                    panel.threshold,
+                    panel.default_outcome,
+                ),
+                None => (0, Vec::new(&env), 0, DisputeStatus::Dismissed),
+            };
+
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+        let request = requests.get(payment.request_id);
+
+        let dispute = Dispute {
+            id: dispute_id,
             payment_id,
             raised_by: raised_by.clone(),
             status: DisputeStatus::Open,
@@ -1944,6 +4188,19 @@ impl HealthChainContract {
             evidence_ref_chunks: evidence_ref_chunks.clone(),
             raised_at: env.ledger().timestamp(),
             resolved_at: None,
+            payee_amount: 0,
+            payer_amount: 0,
+            response_deadline,
+            payer_submitted: raised_by == payment.payer,
+            payee_submitted: raised_by == payment.payee,
+            vote_deadline,
+            votes: Vec::new(&env),
+            arbiters,
+            vote_threshold,
+            default_outcome,
+            request_status: request
+                .as_ref()
+                .map_or(RequestStatus::Pending, |request| request.status),
         };
 
         payment.status = PaymentStatus::Disputed;
@@ -1962,15 +4219,20 @@ impl HealthChainContract {
             .instance()
             .set(&NEXT_DISPUTE_ID, &(dispute_id + 1));
 
-        // Update Request Status if possible
-        let mut requests: Map<u64, BloodRequest> = env
-            .storage()
-            .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
+        append_dispute_evidence(
+            &env,
+            dispute_id,
+            raised_by.clone(),
+            evidence_digest.clone(),
+            evidence_ref_chunks,
+        );
 
-        if let Some(mut request) = requests.get(payment.request_id) {
+        // Update Request Status if possible; a request other banks also serve
+        // stays open so their shares can still be delivered, refunded or expire
+        let shared = contributions::has_other_banks(&env, payment.request_id, &payment.payee);
+        if let Some(mut request) = request.filter(|_| !shared) {
             request.status = RequestStatus::Disputed;
+            request_queue::sync(&env, &request);
             requests.set(payment.request_id, request);
             env.storage().persistent().set(&REQUESTS, &requests);
         }
@@ -1992,10 +4254,46 @@ impl HealthChainContract {
     }
 
     /// Resolve a dispute (admin only)
+    ///
+    /// A `PartialSettlement` requires a `split` describing the payee's share of
+    /// the payment's net amount; every other outcome must pass `None`. The
+    /// payee and payer amounts are recorded on the dispute and in the event.
+    /// Disputes raised while an arbitration panel is configured can only be
+    /// resolved by the panel.
     pub fn resolve_dispute(
         env: Env,
         dispute_id: u64,
         resolution: DisputeStatus,
+        split: Option<DisputeSplit>,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        let dispute = Self::get_dispute(env.clone(), dispute_id)?;
+        if dispute.vote_deadline > 0 {
+            return Err(Error::Unauthorized);
+        }
+
+        settle_dispute(&env, dispute_id, resolution, split)
+    }
+
+    /// Configure the arbitration panel for newly raised disputes (admin only;
+    /// council proposal once a council is set).
+    ///
+    /// `threshold` matching votes out of `arbiters` resolve a dispute; if the
+    /// `vote_window` passes first, `default_outcome` applies. Passing an empty
+    /// `arbiters` list returns disputes to admin resolution.
+    pub fn set_arbitration_panel(
+        env: Env,
+        arbiters: Vec<Address>,
+        threshold: u32,
+        vote_window: u64,
+        default_outcome: DisputeStatus,
     ) -> Result<(), Error> {
         let admin: Address = env
             .storage()
@@ -2003,75 +4301,256 @@ impl HealthChainContract {
             .get(&ADMIN)
             .ok_or(Error::Unauthorized)?;
         admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_arbitration_panel(&env, arbiters, threshold, vote_window, default_outcome)
+    }
+
+    /// Get the configured arbitration panel, if any
+    pub fn get_arbitration_panel(env: Env) -> Option<ArbitrationPanel> {
+        env.storage().instance().get(&DataKey::ArbitrationPanel)
+    }
+
+    /// Cast an arbiter's vote on a dispute under arbitration.
+    ///
+    /// Only arbiters of the panel the dispute was raised under may vote, and
+    /// that panel's `threshold` applies, whatever the panel is now.
+    /// `split_bps` is the payee's share in basis points and only counts for a
+    /// `PartialSettlement` vote; such votes match only when their splits are
+    /// equal. The dispute resolves as soon as `threshold` votes match.
+    pub fn vote_on_dispute(
+        env: Env,
+        dispute_id: u64,
+        arbiter: Address,
+        outcome: DisputeStatus,
+        split_bps: u32,
+        rationale_digest: Bytes,
+    ) -> Result<(), Error> {
+        arbiter.require_auth();
 
         let mut disputes: Map<u64, Dispute> = env
             .storage()
             .persistent()
             .get(&DISPUTES)
             .ok_or(Error::DisputeNotFound)?;
-
         let mut dispute = disputes.get(dispute_id).ok_or(Error::DisputeNotFound)?;
 
-        if dispute.status != DisputeStatus::Open {
-            return Err(Error::InvalidDisputeStatus);
+        if !dispute.arbiters.contains(&arbiter) {
+            return Err(Error::Unauthorized);
         }
 
-        let mut payments: Map<u64, Payment> = env
-            .storage()
-            .persistent()
-            .get(&PAYMENTS)
-            .ok_or(Error::PaymentNotFound)?;
-
-        let mut payment = payments
-            .get(dispute.payment_id)
-            .ok_or(Error::PaymentNotFound)?;
+        if dispute.status != DisputeStatus::Open || outcome == DisputeStatus::Open {
+            return Err(Error::InvalidDisputeStatus);
+        }
+        if dispute.vote_deadline == 0 {
+            return Err(Error::Unauthorized);
+        }
+        if !dispute.evidence_closed(env.ledger().timestamp()) {
+            return Err(Error::EvidenceWindowOpen);
+        }
+        if env.ledger().timestamp() >= dispute.vote_deadline {
+            return Err(Error::VotingClosed);
+        }
+        if dispute.votes.iter().any(|vote| vote.arbiter == arbiter) {
+            return Err(Error::AlreadyVoted);
+        }
+        let split_bps = if outcome == DisputeStatus::PartialSettlement {
+            if split_bps > 10_000 {
+                return Err(Error::InvalidSplit);
+            }
+            split_bps
+        } else {
+            0
+        };
 
-        dispute.status = resolution;
-        dispute.resolved_at = Some(env.ledger().timestamp());
+        let vote = ArbiterVote {
+            arbiter,
+            outcome,
+            split_bps,
+            rationale_digest,
+            voted_at: env.ledger().timestamp(),
+        };
+        dispute.votes.push_back(vote.clone());
         disputes.set(dispute_id, dispute.clone());
         env.storage().persistent().set(&DISPUTES, &disputes);
 
-        payment.status = PaymentStatus::Resolved;
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("vote")),
+            (dispute_id, vote),
+        );
 
-        // Handle funds based on resolution
-        match resolution {
-            DisputeStatus::ResolvedInFavorOfPayer => {
-                payment.status = PaymentStatus::Refunded;
-            }
-            DisputeStatus::ResolvedInFavorOfPayee => {
-                payment.status = PaymentStatus::Completed;
-            }
-            _ => {}
+        let matching = dispute
+            .votes
+            .iter()
+            .filter(|v| v.outcome == outcome && v.split_bps == split_bps)
+            .count() as u32;
+        if matching >= dispute.vote_threshold {
+            let split = if outcome == DisputeStatus::PartialSettlement {
+                Some(DisputeSplit::BasisPoints(split_bps))
+            } else {
+                None
+            };
+            settle_dispute(&env, dispute_id, outcome, split)?;
         }
 
-        payments.set(dispute.payment_id, payment.clone());
-        env.storage().persistent().set(&PAYMENTS, &payments);
+        Ok(())
+    }
 
-        // Update Request Status
-        let mut requests: Map<u64, BloodRequest> = env
+    /// Set the evidence response window for newly raised disputes (admin only;
+    /// council proposal once a council is set)
+    pub fn set_dispute_response_window(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::Unauthorized)?;
+        admin.require_auth();
+        council::ensure_no_council(&env)?;
+
+        store_dispute_response_window(&env, seconds);
+
+        Ok(())
+    }
+
+    /// Get the evidence response window for new disputes, in seconds
+    pub fn get_dispute_response_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::DisputeResponseWindow)
+            .unwrap_or(DISPUTE_RESPONSE_SECONDS)
+    }
+
+    /// Append evidence to an open dispute's log.
+    ///
+    /// `party` must be the payment's payer or payee, and the dispute's
+    /// response window must still be open. Either party may submit several
+    /// times; entries are never modified.
+    pub fn submit_dispute_evidence(
+        env: Env,
+        dispute_id: u64,
+        party: Address,
+        digest: Bytes,
+        chunks: Vec<String>,
+    ) -> Result<u64, Error> {
+        party.require_auth();
+
+        let mut disputes: Map<u64, Dispute> = env
             .storage()
             .persistent()
-            .get(&REQUESTS)
-            .unwrap_or(Map::new(&env));
+            .get(&DISPUTES)
+            .ok_or(Error::DisputeNotFound)?;
+        let mut dispute = disputes.get(dispute_id).ok_or(Error::DisputeNotFound)?;
+        let payment = escrow::get_payment(&env, dispute.payment_id)?;
 
-        if let Some(mut request) = requests.get(payment.request_id) {
-            request.status = RequestStatus::Resolved;
-            requests.set(payment.request_id, request);
-            env.storage().persistent().set(&REQUESTS, &requests);
+        if party != payment.payer && party != payment.payee {
+            return Err(Error::Unauthorized);
+        }
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::InvalidDisputeStatus);
+        }
+        if env.ledger().timestamp() >= dispute.response_deadline {
+            return Err(Error::ResponseWindowClosed);
         }
 
-        // Emit DisputeResolvedEvent
-        env.events().publish(
-            (symbol_short!("dispute"), symbol_short!("resolved")),
-            DisputeResolvedEvent {
-                dispute_id,
-                payment_id: dispute.payment_id,
-                status: resolution,
-                resolved_at: env.ledger().timestamp(),
-            },
-        );
+        if party == payment.payer {
+            dispute.payer_submitted = true;
+        } else {
+            dispute.payee_submitted = true;
+        }
+        disputes.set(dispute_id, dispute);
+        env.storage().persistent().set(&DISPUTES, &disputes);
 
-        Ok(())
+        Ok(append_dispute_evidence(
+            &env, dispute_id, party, digest, chunks,
+        ))
+    }
+
+    /// Get one page of a dispute's evidence log, oldest first.
+    ///
+    /// The log is append-only, so the cursor is the index of the next entry
+    /// to return.
+    pub fn get_dispute_evidence(
+        env: Env,
+        dispute_id: u64,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> DisputeEvidencePage {
+        let len: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DisputeEvidenceCount(dispute_id))
+            .unwrap_or(0);
+        let start = cursor.unwrap_or(0).min(len);
+        let end = start
+            .saturating_add(registry_read::page_limit(limit) as u64)
+            .min(len);
+
+        let mut items = Vec::new(&env);
+        for index in start..end {
+            if let Some(entry) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DisputeEvidence(dispute_id, index))
+            {
+                items.push_back(entry);
+            }
+        }
+
+        DisputeEvidencePage {
+            items,
+            next_cursor: if end < len { Some(end) } else { None },
+        }
+    }
+
+    /// Apply the default outcome of the panel a dispute was raised under once
+    /// its vote deadline passed without reaching the threshold. Permissionless.
+    pub fn finalize_dispute_vote(env: Env, dispute_id: u64) -> Result<(), Error> {
+        let dispute = Self::get_dispute(env.clone(), dispute_id)?;
+        if dispute.status != DisputeStatus::Open || dispute.vote_deadline == 0 {
+            return Err(Error::InvalidDisputeStatus);
+        }
+        if env.ledger().timestamp() < dispute.vote_deadline {
+            return Err(Error::VotingOpen);
+        }
+
+        settle_dispute(&env, dispute_id, dispute.default_outcome, None)
+    }
+
+    /// Suggest a pro-rata split for a dispute from how much of its own
+    /// contribution to the linked request the payee delivered, as basis points
+    /// of the payment owed to it. A payee with no contribution delivered nothing.
+    pub fn suggest_dispute_split(env: Env, dispute_id: u64) -> Result<DisputeSplit, Error> {
+        let disputes: Map<u64, Dispute> = env
+            .storage()
+            .persistent()
+            .get(&DISPUTES)
+            .ok_or(Error::DisputeNotFound)?;
+        let dispute = disputes.get(dispute_id).ok_or(Error::DisputeNotFound)?;
+        let payment = escrow::get_payment(&env, dispute.payment_id)?;
+
+        // Each payment belongs to one bank, so only its own share counts
+        let contribution = contributions::get_contributions(&env, payment.request_id)
+            .iter()
+            .find(|contribution| contribution.bank_id == payment.payee);
+        let Some(contribution) = contribution.filter(|c| c.quantity_ml > 0) else {
+            return Ok(DisputeSplit::BasisPoints(0));
+        };
+
+        let bps = (contribution.delivered_quantity_ml as u64)
+            .checked_mul(10_000)
+            .ok_or(Error::ArithmeticError)?
+            / contribution.quantity_ml as u64;
+        Ok(DisputeSplit::BasisPoints(bps.min(10_000) as u32))
+    }
+
+    /// Get a dispute by ID
+    pub fn get_dispute(env: Env, dispute_id: u64) -> Result<Dispute, Error> {
+        let disputes: Map<u64, Dispute> = env
+            .storage()
+            .persistent()
+            .get(&DISPUTES)
+            .ok_or(Error::DisputeNotFound)?;
+        disputes.get(dispute_id).ok_or(Error::DisputeNotFound)
     }
 
     /// Update request status
@@ -2097,6 +4576,7 @@ impl HealthChainContract {
 
         let old_status = request.status;
         request.status = new_status;
+        request_queue::sync(&env, &request);
 
         requests.set(request_id, request);
         env.storage().persistent().set(&REQUESTS, &requests);
@@ -2108,6 +4588,15 @@ impl HealthChainContract {
     }
 
     /// Approve a pending request and reserve matching units for it.
+    ///
+    /// `unit_ids` must be a non-empty list of distinct units held by the
+    /// approving bank. Every unit must be ABO/Rh compatible with the request and of a
+    /// component that can fill it; compatible substitutes are accepted and
+    /// listed in the event's `substitute_unit_ids`.
+    ///
+    /// Several banks may each commit units while the request is not yet fully
+    /// covered; each bank's share is tracked as a [`BankContribution`] and
+    /// the request's reserved units and quantity aggregate all of them.
     pub fn approve_request(
         env: Env,
         bank_id: Address,
@@ -2115,6 +4604,8 @@ impl HealthChainContract {
         unit_ids: Vec<u64>,
     ) -> Result<(), Error> {
         bank_id.require_auth();
+        let actor = bank_id.clone();
+        let bank_id = delegates::acting_for(&env, &actor, DelegatePermission::Allocate)?;
 
         if !Self::is_blood_bank(env.clone(), bank_id.clone()) {
             return Err(Error::Unauthorized);
@@ -2128,25 +4619,40 @@ impl HealthChainContract {
 
         let mut request = requests.get(request_id).ok_or(Error::UnitNotFound)?;
 
-        if request.status != RequestStatus::Pending {
+        if request.status != RequestStatus::Pending && request.status != RequestStatus::InProgress {
             return Err(Error::InvalidStatus);
         }
+        institutions::ensure_active(&env, &bank_id)?;
+        institutions::ensure_active(&env, &request.hospital_id)?;
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
+        contributions::ensure_distinct_units(&unit_ids)?;
 
         let current_time = env.ledger().timestamp();
+        let requested_component = Self::requested_component(&env, request_id);
         let mut total_quantity: u32 = 0;
+        let mut substitute_unit_ids = Vec::new(&env);
 
         for i in 0..unit_ids.len() {
             let unit_id = unit_ids.get(i).unwrap();
-            let unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+            let unit = registry_read::get_unit(&env, unit_id)?;
 
-            if unit.blood_type != request.blood_type {
-                return Err(Error::InvalidStatus);
+            // The bank is credited and paid for these units
+            if unit.bank_id != bank_id {
+                return Err(Error::Unauthorized);
+            }
+
+            if !compatibility::component_serves(unit.component, requested_component) {
+                return Err(Error::InvalidComponent);
+            }
+            let rank = compatibility::substitution_rank(
+                unit.component,
+                unit.blood_type,
+                requested_component,
+                request.blood_type,
+            )
+            .ok_or(Error::InvalidStatus)?;
+            if rank > 0 {
+                substitute_unit_ids.push_back(unit_id);
             }
 
             if unit.status != BloodStatus::Available {
@@ -2165,21 +4671,22 @@ impl HealthChainContract {
         // Reserve units to the requesting hospital.
         for i in 0..unit_ids.len() {
             let unit_id = unit_ids.get(i).unwrap();
-            let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+            let mut unit = registry_read::get_unit(&env, unit_id)?;
             let old_status = unit.status;
 
             unit.status = BloodStatus::Reserved;
             unit.recipient_hospital = Some(request.hospital_id.clone());
             unit.allocation_timestamp = Some(current_time);
 
-            units.set(unit_id, unit);
+            registry_write::put_unit(&env, &unit);
+            crossmatch::link_unit(&env, unit_id, request_id);
 
             record_status_change(
                 &env,
                 unit_id,
                 old_status,
                 BloodStatus::Reserved,
-                bank_id.clone(),
+                actor.clone(),
             );
 
             env.events().publish(
@@ -2188,12 +4695,13 @@ impl HealthChainContract {
             );
         }
 
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        let committed_quantity =
+            contributions::commit(&env, request_id, &bank_id, &unit_ids, total_quantity)?;
 
         let old_status = request.status;
-        request.reserved_unit_ids = unit_ids.clone();
-        request.fulfilled_quantity_ml = total_quantity;
-        request.status = if total_quantity >= request.quantity_ml {
+        request.reserved_unit_ids.append(&unit_ids);
+        request.fulfilled_quantity_ml = committed_quantity;
+        request.status = if committed_quantity >= request.quantity_ml {
             RequestStatus::Approved
         } else {
             RequestStatus::InProgress
@@ -2202,14 +4710,9 @@ impl HealthChainContract {
         requests.set(request_id, request.clone());
         env.storage().persistent().set(&REQUESTS, &requests);
 
-        record_request_status_change(
-            &env,
-            request_id,
-            old_status,
-            request.status,
-            bank_id.clone(),
-            None,
-        );
+        if request.status != old_status {
+            record_request_status_change(&env, request_id, old_status, request.status, actor, None);
+        }
 
         env.events().publish(
             (symbol_short!("request"), symbol_short!("approve")),
@@ -2217,12 +4720,13 @@ impl HealthChainContract {
                 request_id,
                 blood_bank: bank_id,
                 assigned_unit_ids: unit_ids,
-                total_quantity_ml: total_quantity,
+                total_quantity_ml: committed_quantity,
                 fulfillment_percentage: Self::calculate_fulfillment_percentage(
                     request.quantity_ml,
-                    total_quantity,
+                    committed_quantity,
                 )?,
                 status: request.status,
+                substitute_unit_ids,
             },
         );
 
@@ -2230,6 +4734,9 @@ impl HealthChainContract {
     }
 
     /// Cancel blood request
+    ///
+    /// Reserved units return to `Available` and the escrowed payments of
+    /// banks that have not delivered are refunded, as on expiry.
     pub fn cancel_request(env: Env, request_id: u64, reason: String) -> Result<(), Error> {
         let mut requests: Map<u64, BloodRequest> = env
             .storage()
@@ -2258,27 +4765,9 @@ impl HealthChainContract {
         let old_status = request.status;
         request.status = RequestStatus::Cancelled;
 
-        // Release reserved units
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        for i in 0..request.reserved_unit_ids.len() {
-            let unit_id = request.reserved_unit_ids.get(i).unwrap();
-            if let Some(mut unit) = units.get(unit_id) {
-                if unit.status == BloodStatus::Reserved {
-                    unit.status = BloodStatus::Available;
-                    unit.recipient_hospital = None;
-                    unit.allocation_timestamp = None;
-                    units.set(unit_id, unit);
-                }
-            }
-        }
-
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
-        request.reserved_unit_ids = vec![&env];
+        // Release reserved units and refund banks that have not delivered
+        release_request(&env, &mut request, &caller)?;
+        request_queue::sync(&env, &request);
 
         requests.set(request_id, request);
         env.storage().persistent().set(&REQUESTS, &requests);
@@ -2296,7 +4785,88 @@ impl HealthChainContract {
         Ok(())
     }
 
-    /// Fulfill blood request
+    /// Expire up to `limit` open requests whose `required_by` has passed,
+    /// earliest deadline first. Permissionless.
+    ///
+    /// Only the overdue front of the open-request deadline index is read, so
+    /// the cost of a call is bounded by `limit`. Each expired request releases
+    /// its reserved units back to `Available`, drops their crossmatch links,
+    /// frees its dedup key so the hospital can submit it again, refunds the
+    /// escrowed payments of banks that have not delivered and drops those
+    /// banks' contributions, and emits a `RequestStatusChangeEvent` with the
+    /// reason. Returns the expired request IDs; call again while it returns a
+    /// full batch.
+    pub fn sweep_expired_requests(env: Env, limit: u32) -> Result<Vec<u64>, Error> {
+        if limit > MAX_BATCH_EXPIRY_SIZE {
+            return Err(Error::BatchSizeExceeded);
+        }
+
+        let mut requests: Map<u64, BloodRequest> = env
+            .storage()
+            .persistent()
+            .get(&REQUESTS)
+            .unwrap_or(Map::new(&env));
+        let mut request_keys: Map<RequestKey, u64> = env
+            .storage()
+            .persistent()
+            .get(&REQUEST_KEYS)
+            .unwrap_or(Map::new(&env));
+
+        let current_time = env.ledger().timestamp();
+        let actor = env.current_contract_address();
+
+        let mut expired = Vec::new(&env);
+        for request_id in request_queue::due(&env, current_time, limit).iter() {
+            let Some(mut request) = requests.get(request_id) else {
+                continue;
+            };
+
+            let request_key = RequestKey {
+                hospital_id: request.hospital_id.clone(),
+                blood_type: request.blood_type,
+                quantity_ml: request.quantity_ml,
+                urgency: request.urgency,
+                required_by: request.required_by,
+            };
+            if request_keys.get(request_key.clone()) == Some(request_id) {
+                request_keys.remove(request_key);
+            }
+
+            release_request(&env, &mut request, &actor)?;
+
+            let old_status = request.status;
+            request.status = RequestStatus::Expired;
+            request_queue::sync(&env, &request);
+            requests.set(request_id, request);
+
+            record_request_status_change(
+                &env,
+                request_id,
+                old_status,
+                RequestStatus::Expired,
+                actor.clone(),
+                Some(String::from_str(&env, "required_by passed")),
+            );
+            expired.push_back(request_id);
+        }
+
+        if !expired.is_empty() {
+            env.storage().persistent().set(&REQUESTS, &requests);
+            env.storage().persistent().set(&REQUEST_KEYS, &request_keys);
+        }
+
+        Ok(expired)
+    }
+
+    /// Deliver a bank's share of a blood request.
+    ///
+    /// A bank that committed units through `approve_request` must deliver
+    /// exactly those units, and every unit must be its own and still reserved
+    /// or in transit for the request; its escrowed payment is then
+    /// released when its conditions need no approver. The request becomes
+    /// `Fulfilled` once it is fully committed and every contributing bank has
+    /// delivered. A request approved through `update_request_status` is
+    /// fulfilled by the first delivery, as before.
     pub fn fulfill_request(
         env: Env,
         bank_id: Address,
@@ -2316,6 +4886,7 @@ impl HealthChainContract {
         if !HealthChainContract::is_blood_bank(env.clone(), bank_id.clone()) {
             return Err(Error::Unauthorized);
         }
+        institutions::ensure_active(&env, &bank_id)?;
 
         // Can only fulfill if Approved or InProgress
         if request.status != RequestStatus::Approved && request.status != RequestStatus::InProgress
@@ -2323,28 +4894,49 @@ impl HealthChainContract {
             return Err(Error::InvalidStatus);
         }
 
-        if request.reserved_unit_ids.len() > 0 && request.reserved_unit_ids != unit_ids {
+        contributions::ensure_distinct_units(&unit_ids)?;
+
+        if crossmatch::policy(&env).require_on_fulfillment {
+            for unit_id in unit_ids.iter() {
+                crossmatch::ensure_passed(&env, &request, unit_id)?;
+            }
+        }
+
+        // Requests approved without `approve_request` have no contributions;
+        // the delivering bank becomes their only contributor
+        let committed = !contributions::get_contributions(&env, request_id).is_empty();
+        if !committed
+            && !request.reserved_unit_ids.is_empty()
+            && request.reserved_unit_ids != unit_ids
+        {
             return Err(Error::InvalidStatus);
         }
 
         // Update blood units to Delivered status
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
         let mut delivered_quantity: u32 = 0;
 
         for i in 0..unit_ids.len() {
             let unit_id = unit_ids.get(i).unwrap();
-            let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+            let mut unit = registry_read::get_unit(&env, unit_id)?;
+
+            // Only the bank holding the unit is credited and paid for it
+            if unit.bank_id != bank_id {
+                return Err(Error::Unauthorized);
+            }
 
             // Verify unit is reserved for this hospital
             if unit.recipient_hospital != Some(request.hospital_id.clone()) {
                 return Err(Error::Unauthorized);
             }
 
+            // ...and still on its way there, for this request
+            if unit.status != BloodStatus::Reserved && unit.status != BloodStatus::InTransit {
+                return Err(Error::InvalidStatus);
+            }
+            if crossmatch::linked_request_id(&env, unit_id).is_some_and(|id| id != request_id) {
+                return Err(Error::InvalidStatus);
+            }
+
             // Update to delivered
             let old_status = unit.status;
             unit.status = BloodStatus::Delivered;
@@ -2354,7 +4946,7 @@ impl HealthChainContract {
                 .checked_add(unit.quantity)
                 .ok_or(Error::ArithmeticError)?;
 
-            units.set(unit_id, unit.clone());
+            registry_write::put_unit(&env, &unit);
 
             // Record blood unit status change
             record_status_change(
@@ -2366,42 +4958,95 @@ impl HealthChainContract {
             );
         }
 
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        if !committed {
+            contributions::commit(&env, request_id, &bank_id, &unit_ids, delivered_quantity)?;
+            if request.reserved_unit_ids.is_empty() {
+                request.reserved_unit_ids = unit_ids.clone();
+            }
+        }
+        let contributions =
+            contributions::deliver(&env, request_id, &bank_id, &unit_ids, delivered_quantity)?;
+        let delivered_total = contributions::delivered_total(&contributions)?;
+
+        let complete = (!committed || request.status == RequestStatus::Approved)
+            && contributions
+                .iter()
+                .all(|contribution| contribution.delivered_at.is_some());
+
+        if complete {
+            // Update request
+            let old_status = request.status;
+            request.status = RequestStatus::Fulfilled;
+            request.fulfilled_quantity_ml = delivered_total;
+            request.fulfillment_timestamp = Some(env.ledger().timestamp());
+            request_queue::sync(&env, &request);
+
+            let delivered_unit_ids = request.reserved_unit_ids.clone();
+            requests.set(request_id, request);
+            env.storage().persistent().set(&REQUESTS, &requests);
 
-        // Update request
-        let old_status = request.status;
-        request.status = RequestStatus::Fulfilled;
-        request.fulfilled_quantity_ml = delivered_quantity;
-        request.fulfillment_timestamp = Some(env.ledger().timestamp());
-        request.reserved_unit_ids = unit_ids.clone();
+            // Record and emit status change
+            record_request_status_change(
+                &env,
+                request_id,
+                old_status,
+                RequestStatus::Fulfilled,
+                bank_id.clone(),
+                None,
+            );
 
-        requests.set(request_id, request);
-        env.storage().persistent().set(&REQUESTS, &requests);
+            env.events().publish(
+                (symbol_short!("request"), symbol_short!("fulfill")),
+                RequestFulfilledEvent {
+                    request_id,
+                    blood_bank: bank_id.clone(),
+                    delivered_unit_ids,
+                    delivered_quantity_ml: delivered_total,
+                    fulfilled_at: env.ledger().timestamp(),
+                },
+            );
+        } else {
+            requests.set(request_id, request);
+            env.storage().persistent().set(&REQUESTS, &requests);
 
-        // Record and emit status change
-        record_request_status_change(
-            &env,
-            request_id,
-            old_status,
-            RequestStatus::Fulfilled,
-            bank_id.clone(),
-            None,
-        );
+            env.events().publish(
+                (symbol_short!("request"), symbol_short!("deliver")),
+                RequestFulfilledEvent {
+                    request_id,
+                    blood_bank: bank_id.clone(),
+                    delivered_unit_ids: unit_ids,
+                    delivered_quantity_ml: delivered_quantity,
+                    fulfilled_at: env.ledger().timestamp(),
+                },
+            );
+        }
 
-        env.events().publish(
-            (symbol_short!("request"), symbol_short!("fulfill")),
-            RequestFulfilledEvent {
-                request_id,
-                blood_bank: bank_id,
-                delivered_unit_ids: unit_ids,
-                delivered_quantity_ml: delivered_quantity,
-                fulfilled_at: env.ledger().timestamp(),
-            },
-        );
+        // Release this bank's escrowed payment for the request when its
+        // conditions need no approver; otherwise it waits for `release_escrow`
+        let payment_id: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RequestPayment(request_id, bank_id));
+        if let Some(payment_id) = payment_id {
+            let mut payment = escrow::get_payment(&env, payment_id)?;
+            if payment.status == PaymentStatus::Escrowed
+                && escrow::check_release(&env, payment_id, None).is_ok()
+            {
+                escrow::payout(&env, &mut payment)?;
+            }
+        }
 
         Ok(())
     }
 
+    /// Helper: Component requested by `request_id`, whole blood by default
+    fn requested_component(env: &Env, request_id: u64) -> BloodComponent {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RequestComponent(request_id))
+            .unwrap_or(BloodComponent::WholeBlood)
+    }
+
     /// Helper: Validate status transitions
     fn is_valid_status_transition(old_status: &RequestStatus, new_status: &RequestStatus) -> bool {
         match (old_status, new_status) {
@@ -2422,6 +5067,7 @@ impl HealthChainContract {
             (RequestStatus::Fulfilled, _) => false,
             (RequestStatus::Cancelled, _) => false,
             (RequestStatus::Rejected, _) => false,
+            (RequestStatus::Expired, _) => false,
 
             // Any other transition is invalid
             _ => false,
@@ -2445,7 +5091,8 @@ impl HealthChainContract {
 
     // ── SUPER ADMIN TWO-STEP TRANSFER ────────────────────────────────────────────────────
 
-    /// Nominate a new SuperAdmin (current admin only).
+    /// Nominate a new SuperAdmin (current admin only; council proposal once a
+    /// council is set).
     ///
     /// Clears any expired pending nomination before checking for an active one.
     pub fn nominate_super_admin(env: Env, nominee: Address) -> Result<(), Error> {
@@ -2455,30 +5102,9 @@ impl HealthChainContract {
             .get(&ADMIN)
             .ok_or(Error::Unauthorized)?;
         admin.require_auth();
+        council::ensure_no_council(&env)?;
 
-        let now = env.ledger().timestamp();
-
-        // Lazily clear an expired nomination so a new one can be made.
-        if let Some(entry) = env
-            .storage()
-            .instance()
-            .get::<DataKey, NominationEntry>(&DataKey::PendingNominee)
-        {
-            let expired = now > entry.nominated_at.saturating_add(NOMINATION_EXPIRY_SECONDS);
-            if !expired {
-                return Err(Error::NominationPending);
-            }
-            env.storage().instance().remove(&DataKey::PendingNominee);
-        }
-
-        env.storage().instance().set(
-            &DataKey::PendingNominee,
-            &NominationEntry {
-                nominee,
-                nominated_at: now,
-            },
-        );
-        Ok(())
+        store_nomination(&env, nominee)
     }
 
     /// Accept a pending SuperAdmin nomination (nominee only).
@@ -2516,19 +5142,55 @@ impl HealthChainContract {
         Ok(())
     }
 
-    /// Store a health record hash
-    pub fn store_record(env: Env, patient_id: Symbol, record_hash: Symbol) -> Vec<Symbol> {
-        vec![&env, patient_id, record_hash]
+    /// Store a new version of a patient's health record hash
+    ///
+    /// `author` must be the patient or a provider with an active grant.
+    /// Returns the new version number. Delegates to [`records::store_record`].
+    pub fn store_record(
+        env: Env,
+        author: Address,
+        patient: Address,
+        record_hash: BytesN<32>,
+    ) -> Result<u32, Error> {
+        records::store_record(&env, author, patient, record_hash)
+    }
+
+    /// Retrieve a patient's record, the latest version when `version` is `None`
+    pub fn get_record(
+        env: Env,
+        patient: Address,
+        version: Option<u32>,
+    ) -> Result<PatientRecord, Error> {
+        records::get_record(&env, patient, version)
+    }
+
+    /// Grant a provider access to the patient's records (patient only)
+    ///
+    /// Replaces any existing grant; `expires_at` of `None` means until revoked.
+    pub fn grant_access(
+        env: Env,
+        patient: Address,
+        provider: Address,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        records::grant_access(&env, patient, provider, expires_at)
+    }
+
+    /// Revoke a provider's access to the patient's records (patient only)
+    pub fn revoke_access(env: Env, patient: Address, provider: Address) -> Result<(), Error> {
+        records::revoke_access(&env, patient, provider)
     }
 
-    /// Retrieve stored record
-    pub fn get_record(_env: Env, patient_id: Symbol) -> Symbol {
-        patient_id
+    /// Get the access grant a patient has given a provider, if any
+    pub fn get_access_grant(env: Env, patient: Address, provider: Address) -> Option<AccessGrant> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessGrant(patient, provider))
     }
 
-    /// Verify record access
-    pub fn verify_access(_env: Env, _patient_id: Symbol, _provider_id: Symbol) -> bool {
-        true
+    /// Verify record access and emit an access-log event (provider auth required)
+    pub fn verify_access(env: Env, patient: Address, provider: Address) -> bool {
+        records::verify_access(&env, patient, provider)
     }
 
     /// Add a blood unit to inventory (legacy function for testing)
@@ -2563,110 +5225,86 @@ impl HealthChainContract {
             delivery_timestamp: None,
         };
 
-        let mut units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
-        units.set(id, unit);
-        env.storage().persistent().set(&BLOOD_UNITS, &units);
+        registry_write::put_unit(&env, &unit);
 
         id
     }
 
     /// Query blood inventory by blood type with filters
-    /// Query blood inventory by blood type with filters
+    ///
+    /// Returns `Available`, non-expired units with at least `min_quantity` ml,
+    /// earliest expiration first (FIFO), read from the expiry-ordered index.
+    /// At most `MAX_UNITS_SCANNED_PER_PAGE` units are read per call, so a page
+    /// may be short while `next_cursor` is still set. The cursor is the ID of
+    /// the last unit read by the previous page.
     pub fn query_by_blood_type(
         env: Env,
         blood_type: BloodType,
         min_quantity: u32,
-        max_results: u32,
-    ) -> Vec<BloodUnit> {
-        let units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> UnitPage {
         let current_time = env.ledger().timestamp();
-        let mut results = vec![&env];
-        let mut temp_units = vec![&env];
-
-        // Collect matching units (Available status, non-expired, matching blood type, sufficient quantity)
-        for (_, unit) in units.iter() {
-            if unit.blood_type == blood_type
-                && unit.status == BloodStatus::Available
-                && unit.quantity >= min_quantity
-                && unit.expiration_date > current_time
-            {
-                temp_units.push_back(unit);
-            }
-        }
-
-        // Sort by expiration date (FIFO - earliest expiration first)
-        let len = temp_units.len();
-        for i in 0..len {
-            for j in 0..len.saturating_sub(i + 1) {
-                let unit_j = temp_units.get(j).unwrap();
-                let unit_j_plus_1 = temp_units.get(j + 1).unwrap();
 
-                if unit_j.expiration_date > unit_j_plus_1.expiration_date {
-                    temp_units.set(j, unit_j_plus_1.clone());
-                    temp_units.set(j + 1, unit_j);
+        // Resume strictly after the cursor's (expiration, id) position. A unit's
+        // expiration never changes, so this holds even if it has left the bucket.
+        let after = cursor
+            .and_then(|after_id| registry_read::get_unit(&env, after_id).ok())
+            .map(|after| (after.expiration_date, after.id));
+
+        let limit = registry_read::page_limit(limit);
+        let mut items = vec![&env];
+        let mut next_cursor = None;
+        let mut scanned: u32 = 0;
+
+        registry_read::scan_available(&env, blood_type, current_time, after, |_, unit_id| {
+            if items.len() >= limit {
+                next_cursor = items.last().map(|unit: BloodUnit| unit.id);
+                return false;
+            }
+            if let Ok(unit) = registry_read::get_unit(&env, unit_id) {
+                if unit.quantity >= min_quantity {
+                    items.push_back(unit);
                 }
             }
-        }
-
-        // Apply pagination
-        let limit = if max_results == 0 {
-            len
-        } else {
-            max_results.min(len)
-        };
-        for i in 0..limit {
-            if let Some(unit) = temp_units.get(i) {
-                results.push_back(unit);
+            scanned += 1;
+            if scanned == MAX_UNITS_SCANNED_PER_PAGE {
+                next_cursor = Some(unit_id);
+                return false;
             }
-        }
+            true
+        });
 
-        results
+        UnitPage { items, next_cursor }
     }
 
     /// Check if sufficient blood quantity is available
     pub fn check_availability(env: Env, blood_type: BloodType, required_quantity: u32) -> bool {
-        let units: Map<u64, BloodUnit> = env
-            .storage()
-            .persistent()
-            .get(&BLOOD_UNITS)
-            .unwrap_or(Map::new(&env));
-
         let current_time = env.ledger().timestamp();
         let mut total_quantity: u32 = 0;
 
         // Sum up available quantities for the blood type (Available status and non-expired only)
-        for (_, unit) in units.iter() {
-            if unit.blood_type == blood_type
-                && unit.status == BloodStatus::Available
-                && unit.expiration_date > current_time
-            {
+        registry_read::scan_available(&env, blood_type, current_time, None, |_, unit_id| {
+            if let Ok(unit) = registry_read::get_unit(&env, unit_id) {
                 total_quantity = total_quantity.saturating_add(unit.quantity);
-
-                // Early exit if we've found enough
-                if total_quantity >= required_quantity {
-                    return true;
-                }
             }
-        }
+            // Early exit once we've found enough
+            total_quantity < required_quantity
+        });
 
         total_quantity >= required_quantity
     }
 
-    /// Get all blood units registered by a specific bank.
+    /// Get one page of blood units registered by a specific bank.
     ///
     /// Delegates to [`registry_read::get_units_by_bank`].
-    pub fn get_units_by_bank(env: Env, bank_id: Address) -> Vec<BloodUnit> {
-        registry_read::get_units_by_bank(&env, bank_id)
+    pub fn get_units_by_bank(
+        env: Env,
+        bank_id: Address,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> UnitPage {
+        registry_read::get_units_by_bank(&env, bank_id, cursor, limit)
     }
 
     /// Mark a single blood unit as Expired if its expiration time has passed.
@@ -2801,6 +5439,7 @@ impl HealthChainContract {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::bucket_queue::QueueKey;
     use soroban_sdk::{
         symbol_short, testutils::Address as _, testutils::Events, testutils::Ledger as _, Address,
         Env, IntoVal, String, Symbol, TryFromVal,
@@ -2839,7 +5478,7 @@ mod test {
     #[should_panic(expected = "Error(Contract, #1)")]
     fn test_attack_hospital_spoofs_bank_register_blood_should_fail() {
         let env = Env::default();
-        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
 
         // Register a hospital (not a bank)
         let hospital = Address::generate(&env);
@@ -2858,13 +5497,13 @@ mod test {
     #[should_panic(expected = "Error(Contract, #1)")]
     fn test_attack_unregistered_hospital_create_request_should_fail() {
         let env = Env::default();
-        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
 
         // Unregistered hospital tries to create a request
         let rogue_hospital = Address::generate(&env);
         let current_time = env.ledger().timestamp();
         let required_by = current_time + (2 * 86400);
-        let delivery = String::from_slice(&env, "Ward 7B - ICU");
+        let delivery = String::from_str(&env, "Ward 7B - ICU");
 
         env.mock_all_auths();
         client.create_request(
@@ -2881,7 +5520,7 @@ mod test {
     #[should_panic(expected = "Error(Contract, #1)")]
     fn test_attack_unregistered_bank_allocates_blood_should_fail() {
         let env = Env::default();
-        let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+        let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
 
         // Create a unit via legacy add_blood_unit (no bank auth required)
         let current_time = env.ledger().timestamp();
@@ -3053,7 +5692,7 @@ mod test {
         client.register_blood_bank(&bank);
 
         // Verify bank is registered
-        assert_eq!(client.is_blood_bank(&bank), true);
+        assert!(client.is_blood_bank(&bank));
     }
 
     #[test]
@@ -3193,6 +5832,224 @@ mod test {
         );
     }
 
+    // ======================================================
+    // Component Policy Tests
+    // ======================================================
+
+    fn setup_bank(env: &Env) -> (Address, HealthChainContractClient<'_>) {
+        let (_, _, client) = setup_contract_with_admin(env);
+        let bank = Address::generate(env);
+        client.register_blood_bank(&bank);
+        (bank, client)
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_register_platelets_beyond_five_days_fails() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+
+        client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::Platelets,
+            &250,
+            &(env.ledger().timestamp() + 6 * 86400),
+            &None,
+        );
+    }
+
+    #[test]
+    fn test_register_frozen_components_with_long_shelf_life() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+        let expiration = env.ledger().timestamp() + 300 * 86400;
+
+        client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::Plasma,
+            &250,
+            &expiration,
+            &None,
+        );
+        client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::Cryoprecipitate,
+            &15,
+            &expiration,
+            &None,
+        );
+
+        assert_eq!(
+            client
+                .get_component_policy(&BloodComponent::Plasma)
+                .storage_temp,
+            StorageTempClass::Frozen
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_component_policy_override_applies_to_batch_registration() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+
+        client.set_component_policy(
+            &BloodComponent::WholeBlood,
+            &ComponentPolicy {
+                min_shelf_life_days: 1,
+                max_shelf_life_days: 35,
+                min_volume_ml: 50,
+                max_volume_ml: 500,
+                storage_temp: StorageTempClass::Refrigerated,
+            },
+        );
+
+        let expiration = env.ledger().timestamp() + 40 * 86400;
+        client.batch_register_blood(
+            &bank,
+            &vec![
+                &env,
+                (
+                    BloodType::OPositive,
+                    BloodComponent::WholeBlood,
+                    450u32,
+                    expiration,
+                    None,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_set_component_policy_rejects_inverted_volume_range() {
+        let env = Env::default();
+        let (_, client) = setup_bank(&env);
+
+        client.set_component_policy(
+            &BloodComponent::Platelets,
+            &ComponentPolicy {
+                min_shelf_life_days: 1,
+                max_shelf_life_days: 5,
+                min_volume_ml: 400,
+                max_volume_ml: 100,
+                storage_temp: StorageTempClass::RoomTemperature,
+            },
+        );
+    }
+
+    // ======================================================
+    // Component Split Tests
+    // ======================================================
+
+    fn split_children(env: &Env, rbc_ml: u32) -> Vec<(BloodComponent, u32, u64)> {
+        let now = env.ledger().timestamp();
+        vec![
+            env,
+            (BloodComponent::RedBloodCells, rbc_ml, now + 35 * 86400),
+            (BloodComponent::Plasma, 200u32, now + 300 * 86400),
+            (BloodComponent::Platelets, 50u32, now + 5 * 86400),
+        ]
+    }
+
+    #[test]
+    fn test_split_unit_discards_parent_and_links_children() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+        let parent_id = client.register_blood(
+            &bank,
+            &BloodType::BNegative,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 30 * 86400),
+            &Some(symbol_short!("donor7")),
+        );
+
+        let child_ids = client.split_unit(&bank, &parent_id, &split_children(&env, 200));
+        assert_eq!(child_ids.len(), 3);
+
+        assert_eq!(
+            client.get_blood_unit(&parent_id).status,
+            BloodStatus::Discarded
+        );
+        assert_eq!(client.get_child_units(&parent_id), child_ids);
+
+        for child_id in child_ids.iter() {
+            let child = client.get_blood_unit(&child_id);
+            assert_eq!(child.blood_type, BloodType::BNegative);
+            assert_eq!(child.donor_id, symbol_short!("donor7"));
+            assert_eq!(child.status, BloodStatus::Available);
+            assert_eq!(client.get_parent_unit(&child_id), Some(parent_id));
+        }
+        assert_eq!(
+            client.get_blood_unit(&child_ids.get(1).unwrap()).component,
+            BloodComponent::Plasma
+        );
+    }
+
+    #[test]
+    fn test_split_unit_children_inherit_custody_trail() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_delivered_unit(&env);
+        let return_id = client.initiate_retransfer(&hospital, &unit_id, &bank);
+        client.confirm_retransfer(&bank, &return_id, &true);
+
+        let child_ids = client.split_unit(&bank, &unit_id, &split_children(&env, 150));
+
+        let parent_trail = client.get_custody_trail(&unit_id, &0);
+        assert_eq!(parent_trail.len(), 2);
+        for child_id in child_ids.iter() {
+            assert_eq!(client.get_custody_trail(&child_id, &0), parent_trail);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_split_unit_rejects_children_exceeding_parent_volume() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+        let parent_id = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &400,
+            &(env.ledger().timestamp() + 30 * 86400),
+            &None,
+        );
+
+        // 300 + 200 + 50 ml > 400 ml
+        client.split_unit(&bank, &parent_id, &split_children(&env, 300));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #32)")]
+    fn test_split_unit_requires_whole_blood_parent() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+        let parent_id = client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &BloodComponent::RedBloodCells,
+            &450,
+            &(env.ledger().timestamp() + 30 * 86400),
+            &None,
+        );
+
+        client.split_unit(&bank, &parent_id, &split_children(&env, 100));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")]
+    fn test_split_unit_unknown_parent_fails() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+
+        client.split_unit(&bank, &99, &split_children(&env, 100));
+    }
+
     #[test]
     fn test_register_blood_without_donor_id() {
         let env = Env::default();
@@ -3348,7 +6205,7 @@ mod test {
         client.register_blood_bank(&bank);
 
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (1 * 86400) + 1; // Just over 1 day
+        let expiration = current_time + 86400 + 1; // Just over 1 day
 
         let result = client.register_blood(
             &bank,
@@ -3426,27 +6283,32 @@ mod test {
     #[test]
     fn test_store_record() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register(HealthChainContract, ());
         let client = HealthChainContractClient::new(&env, &contract_id);
 
-        let patient = symbol_short!("patient1");
-        let hash = symbol_short!("hash123");
+        let patient = Address::generate(&env);
+        let hash = BytesN::from_array(&env, &[7u8; 32]);
 
-        let result = client.store_record(&patient, &hash);
-        assert_eq!(result.len(), 2);
+        let version = client.store_record(&patient, &patient, &hash);
+        assert_eq!(version, 1);
+        assert_eq!(client.get_record(&patient, &None).record_hash, hash);
     }
 
     #[test]
     fn test_verify_access() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register(HealthChainContract, ());
         let client = HealthChainContractClient::new(&env, &contract_id);
 
-        let patient = symbol_short!("patient1");
-        let provider = symbol_short!("doctor1");
+        let patient = Address::generate(&env);
+        let provider = Address::generate(&env);
+
+        assert!(!client.verify_access(&patient, &provider));
 
-        let has_access = client.verify_access(&patient, &provider);
-        assert_eq!(has_access, true);
+        client.grant_access(&patient, &provider, &None);
+        assert!(client.verify_access(&patient, &provider));
     }
 
     #[test]
@@ -3501,7 +6363,9 @@ mod test {
         );
 
         // Query O+ blood
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
+        let results = client
+            .query_by_blood_type(&BloodType::OPositive, &0, &None, &10)
+            .items;
         assert_eq!(results.len(), 2);
     }
 
@@ -3532,7 +6396,9 @@ mod test {
             &symbol_short!("loc1"),
         );
 
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
+        let results = client
+            .query_by_blood_type(&BloodType::OPositive, &0, &None, &10)
+            .items;
         assert_eq!(results.len(), 1);
         assert_eq!(results.get(0).unwrap().quantity, 50);
     }
@@ -3563,7 +6429,9 @@ mod test {
         );
 
         // Query with min_quantity = 50
-        let results = client.query_by_blood_type(&BloodType::OPositive, &50, &10);
+        let results = client
+            .query_by_blood_type(&BloodType::OPositive, &50, &None, &10)
+            .items;
         assert_eq!(results.len(), 1);
         assert_eq!(results.get(0).unwrap().quantity, 100);
     }
@@ -3602,7 +6470,9 @@ mod test {
             &symbol_short!("loc1"),
         );
 
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
+        let results = client
+            .query_by_blood_type(&BloodType::OPositive, &0, &None, &10)
+            .items;
         assert_eq!(results.len(), 3);
 
         // Verify FIFO order (earliest expiration first)
@@ -3631,15 +6501,98 @@ mod test {
             );
         }
 
-        // Query with max_results = 2
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &2);
+        // Query with limit = 2
+        let results = client
+            .query_by_blood_type(&BloodType::OPositive, &0, &None, &2)
+            .items;
         assert_eq!(results.len(), 2);
 
-        // Query with max_results = 0 (should return all)
-        let all_results = client.query_by_blood_type(&BloodType::OPositive, &0, &0);
+        // Query with limit = 0 (default page size, covers all 5)
+        let all_results = client
+            .query_by_blood_type(&BloodType::OPositive, &0, &None, &0)
+            .items;
         assert_eq!(all_results.len(), 5);
     }
 
+    #[test]
+    fn test_query_by_blood_type_cursor_resumes_in_fifo_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+
+        // Register in reverse expiration order so FIFO differs from ID order
+        for i in (1..=5u64).rev() {
+            client.add_blood_unit(
+                &BloodType::OPositive,
+                &100,
+                &(current_time + 86400 * i),
+                &symbol_short!("donor"),
+                &symbol_short!("loc1"),
+            );
+        }
+
+        let mut cursor = None;
+        let mut expirations = Vec::new(&env);
+        let mut pages = 0;
+        loop {
+            let page = client.query_by_blood_type(&BloodType::OPositive, &0, &cursor, &2);
+            for unit in page.items.iter() {
+                expirations.push_back(unit.expiration_date);
+            }
+            pages += 1;
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(pages, 3);
+        assert_eq!(expirations.len(), 5);
+        for i in 1..expirations.len() {
+            assert!(expirations.get(i - 1).unwrap() < expirations.get(i).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_query_by_blood_type_bounds_units_scanned_per_page() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthChainContract, ());
+        let client = HealthChainContractClient::new(&env, &contract_id);
+
+        let current_time = env.ledger().timestamp();
+        for _ in 0..MAX_UNITS_SCANNED_PER_PAGE {
+            client.add_blood_unit(
+                &BloodType::OPositive,
+                &100,
+                &(current_time + 86400),
+                &symbol_short!("donor"),
+                &symbol_short!("loc1"),
+            );
+        }
+        let large_id = client.add_blood_unit(
+            &BloodType::OPositive,
+            &450,
+            &(current_time + 2 * 86400),
+            &symbol_short!("donor"),
+            &symbol_short!("loc1"),
+        );
+
+        // Only small units fit in the first scan; the page comes back empty
+        let first = client.query_by_blood_type(&BloodType::OPositive, &400, &None, &10);
+        assert!(first.items.is_empty());
+        assert!(first.next_cursor.is_some());
+
+        let second =
+            client.query_by_blood_type(&BloodType::OPositive, &400, &first.next_cursor, &10);
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items.get(0).unwrap().id, large_id);
+        assert_eq!(second.next_cursor, None);
+    }
+
     #[test]
     fn test_query_no_results() {
         let env = Env::default();
@@ -3648,7 +6601,9 @@ mod test {
         let client = HealthChainContractClient::new(&env, &contract_id);
 
         // Query without adding any units
-        let results = client.query_by_blood_type(&BloodType::OPositive, &0, &10);
+        let results = client
+            .query_by_blood_type(&BloodType::OPositive, &0, &None, &10)
+            .items;
         assert_eq!(results.len(), 0);
     }
 
@@ -3679,7 +6634,7 @@ mod test {
 
         // Check for 120 units (should be available: 100 + 50 = 150)
         let available = client.check_availability(&BloodType::OPositive, &120);
-        assert_eq!(available, true);
+        assert!(available);
     }
 
     #[test]
@@ -3701,7 +6656,7 @@ mod test {
 
         // Check for 200 units (only 100 available)
         let available = client.check_availability(&BloodType::OPositive, &200);
-        assert_eq!(available, false);
+        assert!(!available);
     }
 
     #[test]
@@ -3733,11 +6688,11 @@ mod test {
 
         // Check for 75 units (only 50 available, expired doesn't count)
         let available = client.check_availability(&BloodType::OPositive, &75);
-        assert_eq!(available, false);
+        assert!(!available);
 
         // Check for 50 units (should be available)
         let available = client.check_availability(&BloodType::OPositive, &50);
-        assert_eq!(available, true);
+        assert!(available);
     }
 
     #[test]
@@ -3749,7 +6704,7 @@ mod test {
 
         // Check without adding any units
         let available = client.check_availability(&BloodType::OPositive, &1);
-        assert_eq!(available, false);
+        assert!(!available);
     }
 
     #[test]
@@ -4230,7 +7185,7 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #25)")] // ArithmeticError
+    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
     fn test_approve_request_fails_on_total_quantity_overflow() {
         let env = Env::default();
         let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
@@ -4258,21 +7213,15 @@ mod test {
         );
 
         env.as_contract(&contract_id, || {
-            let mut units: Map<u64, BloodUnit> = env
-                .storage()
-                .persistent()
-                .get(&BLOOD_UNITS)
-                .unwrap_or(Map::new(&env));
-
-            let mut unit_1 = units.get(unit_id_1).unwrap();
+            let mut unit_1 = registry_read::get_unit(&env, unit_id_1).unwrap();
             unit_1.quantity = u32::MAX;
-            units.set(unit_id_1, unit_1);
+            unit_1.bank_id = bank.clone();
+            registry_write::put_unit(&env, &unit_1);
 
-            let mut unit_2 = units.get(unit_id_2).unwrap();
+            let mut unit_2 = registry_read::get_unit(&env, unit_id_2).unwrap();
             unit_2.quantity = 1;
-            units.set(unit_id_2, unit_2);
-
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
+            unit_2.bank_id = bank.clone();
+            registry_write::put_unit(&env, &unit_2);
         });
 
         let request_id = client.create_request(
@@ -4290,7 +7239,7 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #25)")] // ArithmeticError
+    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
     fn test_approve_request_fails_on_fulfillment_percentage_overflow() {
         let env = Env::default();
         let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
@@ -4311,17 +7260,10 @@ mod test {
         );
 
         env.as_contract(&contract_id, || {
-            let mut units: Map<u64, BloodUnit> = env
-                .storage()
-                .persistent()
-                .get(&BLOOD_UNITS)
-                .unwrap_or(Map::new(&env));
-
-            let mut unit = units.get(unit_id).unwrap();
+            let mut unit = registry_read::get_unit(&env, unit_id).unwrap();
             unit.quantity = u32::MAX;
-            units.set(unit_id, unit);
-
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
+            unit.bank_id = bank.clone();
+            registry_write::put_unit(&env, &unit);
         });
 
         let request_id = client.create_request(
@@ -4442,7 +7384,7 @@ mod test {
     #[test]
     fn test_cancel_request_releases_reservations() {
         let env = Env::default();
-        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _admin, hospital, client) = setup_contract_with_hospital(&env);
 
         // Register a blood bank
         let bank = Address::generate(&env);
@@ -4532,7 +7474,7 @@ mod test {
     #[test]
     fn test_fulfill_request_updates_inventory() {
         let env = Env::default();
-        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, _admin, hospital, client) = setup_contract_with_hospital(&env);
 
         // Register a blood bank
         let bank = Address::generate(&env);
@@ -4650,7 +7592,7 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #25)")] // ArithmeticError
+    #[should_panic(expected = "Error(Contract, #28)")] // ArithmeticError
     fn test_fulfill_request_fails_on_delivered_quantity_overflow() {
         let env = Env::default();
         let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
@@ -4679,11 +7621,6 @@ mod test {
             &Some(symbol_short!("d2")),
         );
 
-        env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id_1, &hospital);
-        env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id_2, &hospital);
-
         let request_id = client.create_request(
             &hospital,
             &BloodType::BPositive,
@@ -4698,184 +7635,1094 @@ mod test {
         client.approve_request(&bank, &request_id, &unit_ids);
 
         env.as_contract(&contract_id, || {
-            let mut units: Map<u64, BloodUnit> = env
-                .storage()
-                .persistent()
-                .get(&BLOOD_UNITS)
-                .unwrap_or(Map::new(&env));
-
-            let mut unit_1 = units.get(unit_id_1).unwrap();
+            let mut unit_1 = registry_read::get_unit(&env, unit_id_1).unwrap();
             unit_1.quantity = u32::MAX;
-            units.set(unit_id_1, unit_1);
+            registry_write::put_unit(&env, &unit_1);
 
-            let mut unit_2 = units.get(unit_id_2).unwrap();
+            let mut unit_2 = registry_read::get_unit(&env, unit_id_2).unwrap();
             unit_2.quantity = 1;
-            units.set(unit_id_2, unit_2);
+            registry_write::put_unit(&env, &unit_2);
+        });
+
+        env.mock_all_auths();
+        client.fulfill_request(&bank, &request_id, &unit_ids);
+    }
+
+    #[test]
+    fn test_status_transition_pending_to_rejected() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Low,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
+
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::Rejected);
+    }
+
+    #[test]
+    fn test_status_transition_approved_to_cancelled() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
+
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+
+        env.mock_all_auths();
+        client.cancel_request(&request_id, &String::from_str(&env, "Changed requirements"));
+    }
+
+    #[test]
+    fn test_status_transition_in_progress_to_fulfilled() {
+        let env = Env::default();
+        let (_, _admin, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let expiration = current_time + (7 * 86400);
+
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::BPositive,
+            &BloodComponent::WholeBlood,
+            &500,
+            &expiration,
+            &Some(symbol_short!("donor1")),
+        );
+
+        env.mock_all_auths();
+        client.allocate_blood(&bank, &unit_id, &hospital);
+
+        let required_by = current_time + 3600;
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::BPositive,
+            &500,
+            &UrgencyLevel::Critical,
+            &required_by,
+            &String::from_str(&env, "ER"),
+        );
+
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::Approved);
+        env.mock_all_auths();
+        client.update_request_status(&request_id, &RequestStatus::InProgress);
+
+        let unit_ids = vec![&env, unit_id];
+        env.mock_all_auths();
+        client.fulfill_request(&bank, &request_id, &unit_ids);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
+    }
+
+    #[test]
+    fn test_cancel_request_emits_event_with_reason() {
+        let env = Env::default();
+        let (_contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+        let current_time = env.ledger().timestamp();
+        let required_by = current_time + 3600;
+
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &500,
+            &UrgencyLevel::Urgent,
+            &required_by,
+            &String::from_str(&env, "Ward A"),
+        );
+
+        let cancel_reason = String::from_str(&env, "Patient condition improved");
+        env.mock_all_auths();
+        client.cancel_request(&request_id, &cancel_reason);
+    }
+
+    #[test]
+    fn test_sweep_expired_requests_releases_units_and_dedup_key() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(current_time + 7 * 86400),
+            &None,
+        );
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &450,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward A"),
+        );
+        client.approve_request(&bank, &request_id, &vec![&env, unit_id]);
+
+        // Not due yet
+        assert!(client.sweep_expired_requests(&10).is_empty());
+
+        env.ledger()
+            .with_mut(|li| li.timestamp = current_time + 3600);
+        let expired = client.sweep_expired_requests(&10);
+        assert_eq!(expired, vec![&env, request_id]);
+
+        let last_event = env.events().all().last().unwrap();
+        let event_data: RequestStatusChangeEvent = last_event.2.into_val(&env);
+        assert_eq!(event_data.old_status, RequestStatus::Approved);
+        assert_eq!(event_data.new_status, RequestStatus::Expired);
+        assert_eq!(
+            event_data.reason,
+            Some(String::from_str(&env, "required_by passed"))
+        );
+
+        let (request, request_keys) = env.as_contract(&contract_id, || {
+            let requests: Map<u64, BloodRequest> =
+                env.storage().persistent().get(&REQUESTS).unwrap();
+            let request_keys: Map<RequestKey, u64> =
+                env.storage().persistent().get(&REQUEST_KEYS).unwrap();
+            (requests.get(request_id).unwrap(), request_keys)
+        });
+        assert_eq!(request.status, RequestStatus::Expired);
+        assert!(request.reserved_unit_ids.is_empty());
+        assert!(request_keys.is_empty());
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Available);
+        assert_eq!(unit.recipient_hospital, None);
+        let linked = env.as_contract(&contract_id, || {
+            crossmatch::linked_request_id(&env, unit_id)
+        });
+        assert_eq!(linked, None);
+        assert!(client.get_request_contributions(&request_id).is_empty());
+
+        // Already expired requests are not swept again
+        assert!(client.sweep_expired_requests(&10).is_empty());
+    }
+
+    #[test]
+    fn test_sweep_expired_requests_takes_earliest_deadlines_first() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        env.mock_all_auths();
+
+        let current_time = env.ledger().timestamp();
+        let request = |quantity_ml: u32, required_by: u64| {
+            client.create_request(
+                &hospital,
+                &BloodType::OPositive,
+                &quantity_ml,
+                &UrgencyLevel::Routine,
+                &required_by,
+                &String::from_str(&env, "Ward A"),
+            )
+        };
+        let late = request(450, current_time + 7200);
+        let early = request(500, current_time + 3600);
+        let open = request(550, current_time + 86_400);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp = current_time + 7200);
+        assert_eq!(client.sweep_expired_requests(&1), vec![&env, early]);
+        assert_eq!(client.sweep_expired_requests(&10), vec![&env, late]);
+        assert!(client.sweep_expired_requests(&10).is_empty());
+
+        let page = client.get_request_queue(&None, &None, &10);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items.get(0).unwrap().id, open);
+    }
+
+    #[test]
+    fn test_cancel_request_unlinks_units_and_contributions() {
+        let env = Env::default();
+        let (contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let current_time = env.ledger().timestamp();
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(current_time + 7 * 86400),
+            &None,
+        );
+        let request_id = client.create_request(
+            &hospital,
+            &BloodType::OPositive,
+            &450,
+            &UrgencyLevel::Urgent,
+            &(current_time + 3600),
+            &String::from_str(&env, "Ward A"),
+        );
+        client.approve_request(&bank, &request_id, &vec![&env, unit_id]);
+        assert_eq!(client.get_request_contributions(&request_id).len(), 1);
+
+        client.cancel_request(&request_id, &String::from_str(&env, "No longer needed"));
+
+        let linked = env.as_contract(&contract_id, || {
+            crossmatch::linked_request_id(&env, unit_id)
+        });
+        assert_eq!(linked, None);
+        assert!(client.get_request_contributions(&request_id).is_empty());
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Available
+        );
+
+        // A cancelled request is no longer due for expiry
+        env.ledger()
+            .with_mut(|li| li.timestamp = current_time + 3600);
+        assert!(client.sweep_expired_requests(&10).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #12)")] // BatchSizeExceeded
+    fn test_sweep_expired_requests_rejects_oversized_batch() {
+        let env = Env::default();
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
+
+        client.sweep_expired_requests(&(MAX_BATCH_EXPIRY_SIZE + 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound (used for request not found)
+    fn test_update_status_nonexistent_request() {
+        let env = Env::default();
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+
+        // Try to update status of non-existent request
+        client.update_request_status(&999u64, &RequestStatus::Approved);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
+    fn test_cancel_nonexistent_request() {
+        let env = Env::default();
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
+
+        env.mock_all_auths();
+
+        // Try to cancel non-existent request
+        client.cancel_request(&999u64, &String::from_str(&env, "Test"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
+    fn test_fulfill_nonexistent_request() {
+        let env = Env::default();
+        let (_, _, _, client) = setup_contract_with_hospital(&env);
+
+        let bank = Address::generate(&env);
+        env.mock_all_auths();
+        client.register_blood_bank(&bank);
+
+        let unit_ids = vec![&env, 1u64];
+        env.mock_all_auths();
+        client.fulfill_request(&bank, &999u64, &unit_ids);
+    }
+
+    // ======================================================
+    // Custody Event ID Derivation Tests
+    // ======================================================
+
+    fn setup_reserved_unit(env: &Env) -> (Address, Address, u64, HealthChainContractClient<'_>) {
+        let (_, _, hospital, client) = setup_contract_with_hospital(env);
+        let bank = Address::generate(env);
+        client.register_blood_bank(&bank);
+
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::OPositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 7 * 86400),
+            &None,
+        );
+        client.allocate_blood(&bank, &unit_id, &hospital);
+
+        (bank, hospital, unit_id, client)
+    }
+
+    #[test]
+    fn test_event_id_is_reproducible_from_canonical_bytes() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+
+        assert_eq!(client.get_custody_nonce(&unit_id), 0);
+        let event_id = client.initiate_transfer(&bank, &unit_id);
+        assert_eq!(client.get_custody_nonce(&unit_id), 1);
+
+        // Rebuild the preimage exactly as an off-chain caller would
+        let mut input = Bytes::new(&env);
+        input.push_back(EVENT_ID_VERSION);
+        input.extend_from_array(&unit_id.to_be_bytes());
+        input.append(&bank.clone().to_xdr(&env));
+        input.append(&hospital.clone().to_xdr(&env));
+        input.extend_from_array(&0u64.to_be_bytes());
+        let hash: BytesN<32> = env.crypto().sha256(&input).into();
+
+        let mut hex = [0u8; HEX_HASH_LENGTH];
+        for (i, byte) in hash.to_array().iter().enumerate() {
+            hex[i * 2] = b"0123456789abcdef"[(byte >> 4) as usize];
+            hex[i * 2 + 1] = b"0123456789abcdef"[(byte & 0x0f) as usize];
+        }
+
+        assert_eq!(event_id, String::from_bytes(&env, &hex));
+        assert_eq!(
+            client.compute_event_id(&unit_id, &bank, &hospital, &0),
+            event_id
+        );
+
+        // Known answer for fixed inputs, so a change to the derivation is caught
+        let from = Address::from_str(
+            &env,
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+        );
+        let to = Address::from_str(
+            &env,
+            "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4",
+        );
+        assert_eq!(
+            client.compute_event_id(&42, &from, &to, &7),
+            String::from_str(
+                &env,
+                "a086ae0c5a38e8daa286967710a68e3864ef8f1fa37de6fc31816efebd054ffe"
+            )
+        );
+    }
+
+    #[test]
+    fn test_retransfer_after_cancel_gets_new_event_id() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+
+        let first_id = client.initiate_transfer(&bank, &unit_id);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        client.cancel_transfer(&bank, &first_id);
+
+        let second_id = client.initiate_transfer(&bank, &unit_id);
+        assert_ne!(first_id, second_id);
+        assert_eq!(
+            client.compute_event_id(&unit_id, &bank, &hospital, &1),
+            second_id
+        );
+
+        // The earlier event stays resolvable under its original ID
+        let first_event = client.get_custody_event(&first_id);
+        assert_eq!(first_event.status, CustodyStatus::Cancelled);
+    }
+
+    // ======================================================
+    // Multi-hop Custody Tests
+    // ======================================================
+
+    fn register_courier(env: &Env, client: &HealthChainContractClient<'_>) -> Address {
+        let courier = Address::generate(env);
+        client.register_courier(&courier);
+        courier
+    }
+
+    #[test]
+    fn test_multi_hop_custody_records_each_handoff_in_trail() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+        let courier_a = register_courier(&env, &client);
+        let courier_b = register_courier(&env, &client);
+
+        let hop_1 = client.dispatch_to_courier(&bank, &unit_id, &courier_a);
+        assert_eq!(client.get_current_custodian(&unit_id), bank);
+        client.accept_custody(&courier_a, &hop_1);
+        assert_eq!(client.get_current_custodian(&unit_id), courier_a);
+
+        let hop_2 = client.handoff_custody(&courier_a, &unit_id, &courier_b);
+        client.accept_custody(&courier_b, &hop_2);
+        assert_eq!(client.get_current_custodian(&unit_id), courier_b);
+
+        let hop_3 = client.handoff_custody(&courier_b, &unit_id, &hospital);
+        client.confirm_transfer(&hospital, &hop_3);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
+        assert_eq!(client.get_current_custodian(&unit_id), hospital);
+
+        let trail = client.get_custody_trail(&unit_id, &0);
+        assert_eq!(trail.len(), 3);
+        assert_eq!(trail.get(0).unwrap(), hop_1);
+        assert_eq!(trail.get(1).unwrap(), hop_2);
+        assert_eq!(trail.get(2).unwrap(), hop_3);
+
+        let last = client.get_custody_event(&hop_3);
+        assert_eq!(last.from_custodian, courier_b);
+        assert_eq!(last.to_custodian, hospital);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #29)")] // NotCurrentCustodian
+    fn test_handoff_by_courier_not_holding_unit_fails() {
+        let env = Env::default();
+        let (bank, _, unit_id, client) = setup_reserved_unit(&env);
+        let courier_a = register_courier(&env, &client);
+        let courier_b = register_courier(&env, &client);
+
+        let hop_1 = client.dispatch_to_courier(&bank, &unit_id, &courier_a);
+        client.accept_custody(&courier_a, &hop_1);
+
+        client.handoff_custody(&courier_b, &unit_id, &courier_a);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #29)")] // NotCurrentCustodian
+    fn test_handoff_before_accepting_custody_fails() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+        let courier = register_courier(&env, &client);
+
+        client.dispatch_to_courier(&bank, &unit_id, &courier);
+        client.handoff_custody(&courier, &unit_id, &hospital);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_handoff_to_unregistered_custodian_fails() {
+        let env = Env::default();
+        let (bank, _, unit_id, client) = setup_reserved_unit(&env);
+        let courier = register_courier(&env, &client);
+        let stranger = Address::generate(&env);
+
+        let hop_1 = client.dispatch_to_courier(&bank, &unit_id, &courier);
+        client.accept_custody(&courier, &hop_1);
+        client.handoff_custody(&courier, &unit_id, &stranger);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_dispatch_to_unregistered_courier_fails() {
+        let env = Env::default();
+        let (bank, _, unit_id, client) = setup_reserved_unit(&env);
+        let stranger = Address::generate(&env);
+
+        client.dispatch_to_courier(&bank, &unit_id, &stranger);
+    }
+
+    #[test]
+    fn test_cancelled_courier_handoff_leaves_unit_with_courier() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+        let courier = register_courier(&env, &client);
+
+        let hop_1 = client.dispatch_to_courier(&bank, &unit_id, &courier);
+        client.accept_custody(&courier, &hop_1);
+        let hop_2 = client.handoff_custody(&courier, &unit_id, &hospital);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        client.cancel_transfer(&courier, &hop_2);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::InTransit);
+        assert_eq!(client.get_current_custodian(&unit_id), courier);
+
+        // The courier can retry the final hop
+        let hop_3 = client.handoff_custody(&courier, &unit_id, &hospital);
+        client.confirm_transfer(&hospital, &hop_3);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Delivered
+        );
+    }
+
+    // ======================================================
+    // Re-transfer Tests
+    // ======================================================
+
+    fn setup_delivered_unit(env: &Env) -> (Address, Address, u64, HealthChainContractClient<'_>) {
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(env);
+        let event_id = client.initiate_transfer(&bank, &unit_id);
+        client.confirm_transfer(&hospital, &event_id);
+        (bank, hospital, unit_id, client)
+    }
+
+    #[test]
+    fn test_return_to_bank_makes_unit_available_again() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_delivered_unit(&env);
+
+        let event_id = client.initiate_retransfer(&hospital, &unit_id, &bank);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::InRetransfer
+        );
+
+        client.confirm_retransfer(&bank, &event_id, &true);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Available);
+        assert_eq!(unit.recipient_hospital, None);
+        assert!(client.check_availability(&BloodType::OPositive, &450));
+        assert_eq!(client.get_custody_trail(&unit_id, &0).len(), 2);
+    }
+
+    #[test]
+    fn test_forward_to_other_hospital_moves_delivery() {
+        let env = Env::default();
+        let (_, hospital, unit_id, client) = setup_delivered_unit(&env);
+        let other_hospital = Address::generate(&env);
+        client.register_hospital(&other_hospital);
+
+        let event_id = client.initiate_retransfer(&hospital, &unit_id, &other_hospital);
+        client.confirm_retransfer(&other_hospital, &event_id, &true);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
+        assert_eq!(unit.recipient_hospital, Some(other_hospital.clone()));
+        assert_eq!(client.get_current_custodian(&unit_id), other_hospital);
+        assert_eq!(
+            client.query_by_hospital(&hospital, &None, &0).items.len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_retransfer_with_broken_cold_chain_quarantines_unit() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_delivered_unit(&env);
+
+        let event_id = client.initiate_retransfer(&hospital, &unit_id, &bank);
+        client.confirm_retransfer(&bank, &event_id, &false);
+
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Quarantined
+        );
+        assert_eq!(
+            client.get_custody_event(&event_id).status,
+            CustodyStatus::Rejected
+        );
+        // Rejected hops are not part of the confirmed trail
+        assert_eq!(client.get_custody_trail(&unit_id, &0).len(), 1);
+
+        let case = client.get_quarantine_case(&unit_id).unwrap();
+        assert_eq!(case.reason, QuarantineReason::TemperatureBreach);
+        assert_eq!(case.reviewer, bank);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #29)")] // NotCurrentCustodian
+    fn test_retransfer_by_non_holding_hospital_fails() {
+        let env = Env::default();
+        let (bank, _, unit_id, client) = setup_delivered_unit(&env);
+        let other_hospital = Address::generate(&env);
+        client.register_hospital(&other_hospital);
+
+        client.initiate_retransfer(&other_hospital, &unit_id, &bank);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_retransfer_to_unregistered_address_fails() {
+        let env = Env::default();
+        let (_, hospital, unit_id, client) = setup_delivered_unit(&env);
+        let stranger = Address::generate(&env);
+
+        client.initiate_retransfer(&hospital, &unit_id, &stranger);
+    }
+
+    #[test]
+    fn test_cancelled_retransfer_returns_unit_to_delivered() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_delivered_unit(&env);
+
+        let event_id = client.initiate_retransfer(&hospital, &unit_id, &bank);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        client.cancel_transfer(&hospital, &event_id);
+
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
+        assert_eq!(unit.recipient_hospital, Some(hospital));
+    }
+
+    // ======================================================
+    // Stale Transfer Sweep Tests
+    // ======================================================
+
+    #[test]
+    fn test_sweep_expired_transfers_reverts_to_reserved_by_default() {
+        let env = Env::default();
+        let (bank, _, unit_id, client) = setup_reserved_unit(&env);
+        let event_id = client.initiate_transfer(&bank, &unit_id);
+
+        // Not yet expired: nothing to sweep
+        assert_eq!(client.sweep_expired_transfers(&10).len(), 0);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        let swept = client.sweep_expired_transfers(&10);
+        assert_eq!(swept.len(), 1);
+        assert_eq!(swept.get(0).unwrap(), event_id);
+
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Reserved
+        );
+        assert_eq!(
+            client.get_custody_event(&event_id).status,
+            CustodyStatus::Cancelled
+        );
+
+        // Already swept
+        assert_eq!(client.sweep_expired_transfers(&10).len(), 0);
+    }
+
+    #[test]
+    fn test_sweep_expired_transfers_quarantines_under_policy() {
+        let env = Env::default();
+        let (bank, _, unit_id, client) = setup_reserved_unit(&env);
+        client.set_stale_transfer_policy(&StaleTransferPolicy::Quarantine);
+        client.initiate_transfer(&bank, &unit_id);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        client.sweep_expired_transfers(&10);
+
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Quarantined
+        );
+    }
+
+    #[test]
+    fn test_sweep_quarantine_clears_courier_custodian() {
+        let env = Env::default();
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+        let courier = register_courier(&env, &client);
+        client.set_stale_transfer_policy(&StaleTransferPolicy::Quarantine);
+
+        let hop_1 = client.dispatch_to_courier(&bank, &unit_id, &courier);
+        client.accept_custody(&courier, &hop_1);
+        client.handoff_custody(&courier, &unit_id, &hospital);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        assert_eq!(client.sweep_expired_transfers(&10).len(), 1);
+
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Quarantined
+        );
+        env.as_contract(&client.address, || {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&DataKey::UnitCustodian(unit_id)));
+        });
+        assert_eq!(client.get_current_custodian(&unit_id), bank);
+    }
+
+    #[test]
+    fn test_sweep_expired_transfers_prunes_stale_entries() {
+        let env = Env::default();
+        let (bank, _, unit_id, client) = setup_reserved_unit(&env);
+        let event_id = client.initiate_transfer(&bank, &unit_id);
+
+        // A queued hop that is no longer open
+        let stale = (0u64, unit_id + 1, String::from_str(&env, "stale"));
+        env.as_contract(&client.address, || {
+            queue_pending_transfer(&env, stale.clone());
+        });
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        assert_eq!(
+            client.sweep_expired_transfers(&10),
+            vec![&env, event_id.clone()]
+        );
+        assert_eq!(
+            client.get_custody_event(&event_id).status,
+            CustodyStatus::Cancelled
+        );
+        env.as_contract(&client.address, || {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&QueueKey::Buckets(Queue::Transfers)));
+        });
+    }
+
+    #[test]
+    fn test_sweep_expired_transfers_respects_limit_and_order() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        client.register_blood_bank(&bank);
+
+        let expiration = env.ledger().timestamp() + 7 * 86400;
+        let mut event_ids = Vec::new(&env);
+        for _ in 0..3 {
+            let unit_id = client.register_blood(
+                &bank,
+                &BloodType::APositive,
+                &BloodComponent::WholeBlood,
+                &300,
+                &expiration,
+                &None,
+            );
+            client.allocate_blood(&bank, &unit_id, &hospital);
+            event_ids.push_back(client.initiate_transfer(&bank, &unit_id));
+            env.ledger().with_mut(|li| li.timestamp += 60);
+        }
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+
+        let first = client.sweep_expired_transfers(&2);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first.get(0).unwrap(), event_ids.get(0).unwrap());
+        assert_eq!(first.get(1).unwrap(), event_ids.get(1).unwrap());
+
+        let rest = client.sweep_expired_transfers(&2);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest.get(0).unwrap(), event_ids.get(2).unwrap());
+    }
+
+    #[test]
+    fn test_confirmed_transfer_is_not_swept() {
+        let env = Env::default();
+        let (_, _, unit_id, client) = setup_delivered_unit(&env);
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS);
+        assert_eq!(client.sweep_expired_transfers(&10).len(), 0);
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Delivered
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #12)")]
+    fn test_sweep_expired_transfers_rejects_oversized_batch() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+
+        client.sweep_expired_transfers(&(MAX_BATCH_EXPIRY_SIZE + 1));
+    }
 
-            env.storage().persistent().set(&BLOOD_UNITS, &units);
-        });
+    // ======================================================
+    // Donor Flag / Look-back Tests
+    // ======================================================
 
-        env.mock_all_auths();
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+    fn register_donor_unit(
+        env: &Env,
+        client: &HealthChainContractClient<'_>,
+        bank: &Address,
+        donor: &Symbol,
+    ) -> u64 {
+        client.register_blood(
+            bank,
+            &BloodType::APositive,
+            &BloodComponent::WholeBlood,
+            &450,
+            &(env.ledger().timestamp() + 30 * 86400),
+            &Some(donor.clone()),
+        )
     }
 
     #[test]
-    fn test_status_transition_pending_to_rejected() {
+    fn test_flag_donor_quarantines_units_inside_window() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let (_, admin, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        client.register_blood_bank(&bank);
+        let donor = symbol_short!("donor1");
 
-        env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let old_unit = register_donor_unit(&env, &client, &bank, &donor);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Low,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+        env.ledger().with_mut(|li| li.timestamp = 20 * 86400);
+        let available = register_donor_unit(&env, &client, &bank, &donor);
+        let in_transit = register_donor_unit(&env, &client, &bank, &donor);
+        client.allocate_blood(&bank, &in_transit, &hospital);
+        let event_id = client.initiate_transfer(&bank, &in_transit);
+
+        let page = client.flag_donor(
+            &admin,
+            &bank,
+            &donor,
+            &String::from_str(&env, "HBV positive"),
+            &(7 * 86400),
+            &10,
         );
 
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Rejected);
+        assert_eq!(page.quarantined, vec![&env, available, in_transit]);
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(
+            client.get_blood_unit(&available).status,
+            BloodStatus::Quarantined
+        );
+        assert_eq!(
+            client.get_blood_unit(&in_transit).status,
+            BloodStatus::Quarantined
+        );
+        assert_eq!(
+            client.get_custody_event(&event_id).status,
+            CustodyStatus::Cancelled
+        );
+        assert_eq!(
+            client.get_blood_unit(&old_unit).status,
+            BloodStatus::Available
+        );
+
+        let case = client.get_quarantine_case(&available).unwrap();
+        assert_eq!(case.reason, QuarantineReason::DonorEvent);
+        assert_eq!(case.opened_by, admin);
+
+        let deferral = client.get_donor_deferral(&bank, &donor).unwrap();
+        assert_eq!(deferral.flagged_by, admin);
+        assert_eq!(deferral.lookback_window, 7 * 86400);
     }
 
     #[test]
-    fn test_status_transition_approved_to_cancelled() {
+    fn test_flag_donor_emits_recall_for_delivered_units() {
         let env = Env::default();
-        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
-
-        env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+        let donor = symbol_short!("donor2");
+        let donor_unit = register_donor_unit(&env, &client, &bank, &donor);
+        client.allocate_blood(&bank, &donor_unit, &hospital);
+        let event_id = client.initiate_transfer(&bank, &donor_unit);
+        client.confirm_transfer(&hospital, &event_id);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+        let page = client.flag_donor(
+            &bank,
+            &bank,
+            &donor,
+            &String::from_str(&env, "Seroconversion"),
+            &86400,
+            &10,
         );
 
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
+        // One recall for the hospital plus the flag summary
+        assert_eq!(env.events().all().len(), 2);
 
-        env.mock_all_auths();
-        client.cancel_request(&request_id, &String::from_str(&env, "Changed requirements"));
+        assert!(page.quarantined.is_empty());
+        assert_eq!(
+            client.get_blood_unit(&donor_unit).status,
+            BloodStatus::Delivered
+        );
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Reserved
+        );
     }
 
     #[test]
-    fn test_status_transition_in_progress_to_fulfilled() {
+    #[should_panic(expected = "Error(Contract, #33)")]
+    fn test_flagged_donor_cannot_register_again() {
         let env = Env::default();
-        let (_, _admin, hospital, client) = setup_contract_with_hospital(&env);
-
-        let bank = Address::generate(&env);
-        env.mock_all_auths();
-        client.register_blood_bank(&bank);
-
-        let current_time = env.ledger().timestamp();
-        let expiration = current_time + (7 * 86400);
+        let (bank, client) = setup_bank(&env);
+        let donor = symbol_short!("donor3");
 
-        let unit_id = client.register_blood(
+        client.flag_donor(
             &bank,
-            &BloodType::BPositive,
-            &BloodComponent::WholeBlood,
-            &500,
-            &expiration,
-            &Some(symbol_short!("donor1")),
+            &bank,
+            &donor,
+            &String::from_str(&env, "Travel deferral"),
+            &0,
+            &10,
         );
+        register_donor_unit(&env, &client, &bank, &donor);
+    }
 
-        env.mock_all_auths();
-        client.allocate_blood(&bank, &unit_id, &hospital);
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_flag_donor_rejects_other_bank() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+        let other_bank = Address::generate(&env);
+        client.register_blood_bank(&other_bank);
 
-        let required_by = current_time + 3600;
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::BPositive,
-            &500,
-            &UrgencyLevel::Critical,
-            &required_by,
-            &String::from_str(&env, "ER"),
+        client.flag_donor(
+            &other_bank,
+            &bank,
+            &symbol_short!("donor4"),
+            &String::from_str(&env, "Not ours"),
+            &86400,
+            &10,
         );
+    }
 
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::Approved);
-        env.mock_all_auths();
-        client.update_request_status(&request_id, &RequestStatus::InProgress);
+    #[test]
+    fn test_flag_donor_recalls_in_batches() {
+        let env = Env::default();
+        let (bank, client) = setup_bank(&env);
+        let donor = symbol_short!("donor5");
+        let mut unit_ids = Vec::new(&env);
+        for _ in 0..5 {
+            unit_ids.push_back(register_donor_unit(&env, &client, &bank, &donor));
+        }
 
-        let unit_ids = vec![&env, unit_id];
-        env.mock_all_auths();
-        client.fulfill_request(&bank, &request_id, &unit_ids);
+        let page = client.flag_donor(
+            &bank,
+            &bank,
+            &donor,
+            &String::from_str(&env, "HIV positive"),
+            &86400,
+            &2,
+        );
+        assert_eq!(page.quarantined, unit_ids.slice(0..2));
+        assert_eq!(
+            client.get_blood_unit(&unit_ids.get(2).unwrap()).status,
+            BloodStatus::Available
+        );
 
-        let unit = client.get_blood_unit(&unit_id);
-        assert_eq!(unit.status, BloodStatus::Delivered);
+        let mut recalled = page.quarantined;
+        let mut cursor = page.next_cursor;
+        while let Some(after) = cursor {
+            let page = client.resume_donor_recall(&bank, &bank, &donor, &after, &2);
+            assert!(page.quarantined.len() <= 2);
+            recalled.append(&page.quarantined);
+            cursor = page.next_cursor;
+        }
+        assert_eq!(recalled, unit_ids);
     }
 
     #[test]
-    fn test_cancel_request_emits_event_with_reason() {
+    #[should_panic(expected = "Error(Contract, #12)")]
+    fn test_flag_donor_rejects_oversized_batch() {
         let env = Env::default();
-        let (_contract_id, _, hospital, client) = setup_contract_with_hospital(&env);
-
-        env.mock_all_auths();
-        let current_time = env.ledger().timestamp();
-        let required_by = current_time + 3600;
+        let (bank, client) = setup_bank(&env);
 
-        let request_id = client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &500,
-            &UrgencyLevel::Urgent,
-            &required_by,
-            &String::from_str(&env, "Ward A"),
+        client.flag_donor(
+            &bank,
+            &bank,
+            &symbol_short!("donor6"),
+            &String::from_str(&env, "Too many"),
+            &86400,
+            &(MAX_BATCH_SIZE + 1),
         );
-
-        let cancel_reason = String::from_str(&env, "Patient condition improved");
-        env.mock_all_auths();
-        client.cancel_request(&request_id, &cancel_reason);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound (used for request not found)
-    fn test_update_status_nonexistent_request() {
+    #[should_panic(expected = "Error(Contract, #6)")]
+    fn test_resume_donor_recall_requires_flagged_donor() {
         let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
-
-        env.mock_all_auths();
+        let (bank, client) = setup_bank(&env);
 
-        // Try to update status of non-existent request
-        client.update_request_status(&999u64, &RequestStatus::Approved);
+        client.resume_donor_recall(&bank, &bank, &symbol_short!("donor7"), &0, &10);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
-    fn test_cancel_nonexistent_request() {
+    fn test_flag_donor_clears_courier_custodian() {
         let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
+        let (bank, hospital, _, client) = setup_reserved_unit(&env);
+        let courier = register_courier(&env, &client);
+        let donor = symbol_short!("donor8");
+        let unit_id = register_donor_unit(&env, &client, &bank, &donor);
+        client.allocate_blood(&bank, &unit_id, &hospital);
+        let hop_1 = client.dispatch_to_courier(&bank, &unit_id, &courier);
+        client.accept_custody(&courier, &hop_1);
+        let hop_2 = client.handoff_custody(&courier, &unit_id, &hospital);
 
-        env.mock_all_auths();
+        client.flag_donor(
+            &bank,
+            &bank,
+            &donor,
+            &String::from_str(&env, "HCV positive"),
+            &86400,
+            &10,
+        );
 
-        // Try to cancel non-existent request
-        client.cancel_request(&999u64, &String::from_str(&env, "Test"));
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Quarantined
+        );
+        assert_eq!(
+            client.get_custody_event(&hop_2).status,
+            CustodyStatus::Cancelled
+        );
+        assert_eq!(client.get_current_custodian(&unit_id), bank);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #7)")] // UnitNotFound
-    fn test_fulfill_nonexistent_request() {
+    fn test_quarantine_blood_cancels_open_custody_hop() {
         let env = Env::default();
-        let (_, _, _, client) = setup_contract_with_hospital(&env);
+        let (bank, hospital, unit_id, client) = setup_reserved_unit(&env);
+        let courier = register_courier(&env, &client);
+        let reviewer = Address::generate(&env);
+        client.register_blood_bank(&reviewer);
+        let hop_1 = client.dispatch_to_courier(&bank, &unit_id, &courier);
+        client.accept_custody(&courier, &hop_1);
+        let hop_2 = client.handoff_custody(&courier, &unit_id, &hospital);
+
+        client.quarantine_blood(
+            &bank,
+            &unit_id,
+            &QuarantineReason::TemperatureBreach,
+            &Bytes::from_array(&env, &[7u8; 32]),
+            &reviewer,
+        );
 
-        let bank = Address::generate(&env);
-        env.mock_all_auths();
-        client.register_blood_bank(&bank);
+        assert_eq!(
+            client.get_custody_event(&hop_2).status,
+            CustodyStatus::Cancelled
+        );
+        assert_eq!(client.get_current_custodian(&unit_id), bank);
 
-        let unit_ids = vec![&env, 1u64];
-        env.mock_all_auths();
-        client.fulfill_request(&bank, &999u64, &unit_ids);
+        // Nothing is left for the transfer sweep to act on
+        env.ledger()
+            .with_mut(|li| li.timestamp += TRANSFER_EXPIRY_SECONDS + 1);
+        assert!(client.sweep_expired_transfers(&10).is_empty());
+        assert_eq!(
+            client.get_blood_unit(&unit_id).status,
+            BloodStatus::Quarantined
+        );
     }
 
-
     // ======================================================
     // Custodian Check Tests (#101)
     // ======================================================
@@ -5142,8 +8989,112 @@ mod test {
         let empty_bank = Address::generate(&env);
 
         // This should return an empty Vec and NOT panic
-        let results = client.get_units_by_bank(&empty_bank);
-        assert_eq!(results.len(), 0);
+        let page = client.get_units_by_bank(&empty_bank, &None, &0);
+        assert_eq!(page.items.len(), 0);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn test_get_units_by_bank_walks_all_pages_with_cursor() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+        client.register_blood_bank(&bank);
+
+        let expiration = env.ledger().timestamp() + 86400 * 10;
+        for _ in 0..5 {
+            client.register_blood(
+                &bank,
+                &BloodType::APositive,
+                &BloodComponent::WholeBlood,
+                &300,
+                &expiration,
+                &None,
+            );
+        }
+
+        let first = client.get_units_by_bank(&bank, &None, &2);
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.items.get(0).unwrap().id, 1);
+        assert_eq!(first.next_cursor, Some(2));
+
+        let second = client.get_units_by_bank(&bank, &first.next_cursor, &2);
+        assert_eq!(second.items.get(0).unwrap().id, 3);
+        assert_eq!(second.next_cursor, Some(4));
+
+        let last = client.get_units_by_bank(&bank, &second.next_cursor, &2);
+        assert_eq!(last.items.len(), 1);
+        assert_eq!(last.items.get(0).unwrap().id, 5);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn test_query_by_status_paginates_and_clamps_limit() {
+        let env = Env::default();
+        let (_, _, client) = setup_contract_with_admin(&env);
+        let bank = Address::generate(&env);
+        client.register_blood_bank(&bank);
+
+        let expiration = env.ledger().timestamp() + 86400 * 10;
+        for _ in 0..3 {
+            client.register_blood(
+                &bank,
+                &BloodType::BPositive,
+                &BloodComponent::Plasma,
+                &200,
+                &expiration,
+                &None,
+            );
+        }
+
+        let page = client.query_by_status(&BloodStatus::Available, &None, &1000);
+        assert_eq!(page.items.len(), 3);
+        assert_eq!(page.next_cursor, None);
+
+        let page = client.query_by_status(&BloodStatus::Available, &Some(1), &1);
+        assert_eq!(page.items.get(0).unwrap().id, 2);
+        assert_eq!(page.next_cursor, Some(2));
+    }
+
+    #[test]
+    fn test_get_transfer_history_pages_oldest_first() {
+        let env = Env::default();
+        let (_, _, hospital, client) = setup_contract_with_hospital(&env);
+        let bank = Address::generate(&env);
+        client.register_blood_bank(&bank);
+
+        let unit_id = client.register_blood(
+            &bank,
+            &BloodType::ONegative,
+            &BloodComponent::WholeBlood,
+            &300,
+            &(env.ledger().timestamp() + 86400 * 10),
+            &None,
+        );
+        client.allocate_blood(&bank, &unit_id, &hospital);
+        client.cancel_allocation(&bank, &unit_id);
+        client.allocate_blood(&bank, &unit_id, &hospital);
+
+        // Registration, allocate, cancel, allocate
+        let first = client.get_transfer_history(&unit_id, &None, &2);
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(
+            first.items.get(0).unwrap().new_status,
+            BloodStatus::Available
+        );
+        assert_eq!(
+            first.items.get(1).unwrap().new_status,
+            BloodStatus::Reserved
+        );
+        assert_eq!(first.next_cursor, Some(2));
+
+        let second = client.get_transfer_history(&unit_id, &first.next_cursor, &2);
+        assert_eq!(second.items.len(), 2);
+        assert_eq!(
+            second.items.get(1).unwrap().new_status,
+            BloodStatus::Reserved
+        );
+        assert_eq!(second.next_cursor, None);
     }
 
     /// Test for Issue #125: Donor ID collision across different banks
@@ -5189,7 +9140,9 @@ mod test {
         );
 
         // Get units for donor "001" at Bank A - should only return Bank A's unit
-        let all_donor_units = client.get_units_by_donor(&symbol_short!("001"));
+        let all_donor_units = client
+            .get_units_by_donor(&symbol_short!("001"), &None, &0)
+            .items;
         let mut bank_a_units = vec![&env];
         for i in 0..all_donor_units.len() {
             let unit = all_donor_units.get(i).unwrap();
@@ -5223,7 +9176,7 @@ mod test {
 
         // Register another unit for donor "001" at Bank A
         env.mock_all_auths();
-        let unit_a2 = client.register_blood(
+        let _unit_a2 = client.register_blood(
             &bank_a,
             &BloodType::ONegative,
             &BloodComponent::WholeBlood,
@@ -5233,7 +9186,9 @@ mod test {
         );
 
         // Verify Bank A now has 2 units for donor "001"
-        let all_updated = client.get_units_by_donor(&symbol_short!("001"));
+        let all_updated = client
+            .get_units_by_donor(&symbol_short!("001"), &None, &0)
+            .items;
         let mut bank_a_units_updated = vec![&env];
         for i in 0..all_updated.len() {
             let unit = all_updated.get(i).unwrap();
@@ -5265,7 +9220,9 @@ mod test {
         client.register_blood_bank(&bank);
 
         // Query for a donor that doesn't exist
-        let units = client.get_units_by_donor(&symbol_short!("NOEXIST"));
+        let units = client
+            .get_units_by_donor(&symbol_short!("NOEXIST"), &None, &0)
+            .items;
         assert_eq!(units.len(), 0);
     }
 
@@ -5287,7 +9244,9 @@ mod test {
         client.register_blood(&bank, &BloodType::ABPositive, &BloodComponent::WholeBlood, &300, &expiration, &None);
 
         // Anonymous donors are stored as "ANON"
-        let units = client.get_units_by_donor(&symbol_short!("ANON"));
+        let units = client
+            .get_units_by_donor(&symbol_short!("ANON"), &None, &0)
+            .items;
         assert_eq!(units.len(), 1);
     }
 
@@ -5352,16 +9311,10 @@ mod test {
 
         for i in 0..5 {
             env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
+                let mut unit = registry_read::get_unit(&env, unit_id).unwrap();
                 unit.status = BloodStatus::Reserved;
                 unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
+                registry_write::put_unit(&env, &unit);
             });
 
             env.mock_all_auths();
@@ -5409,16 +9362,10 @@ mod test {
         for i in 0..25 {
             // Manually set unit to Reserved state
             env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
+                let mut unit = registry_read::get_unit(&env, unit_id).unwrap();
                 unit.status = BloodStatus::Reserved;
                 unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
+                registry_write::put_unit(&env, &unit);
             });
 
             env.mock_all_auths();
@@ -5472,16 +9419,10 @@ mod test {
 
         for i in 0..100 {
             env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
+                let mut unit = registry_read::get_unit(&env, unit_id).unwrap();
                 unit.status = BloodStatus::Reserved;
                 unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
+                registry_write::put_unit(&env, &unit);
             });
 
             env.mock_all_auths();
@@ -5565,7 +9506,7 @@ mod test {
     #[test]
     fn test_migrate_trail_index() {
         let env = Env::default();
-        let (_, admin, _, client) = setup_contract_with_hospital(&env);
+        let (_, _admin, _, client) = setup_contract_with_hospital(&env);
 
         let bank = Address::generate(&env);
         env.mock_all_auths();
@@ -5633,16 +9574,10 @@ mod test {
 
         for i in 0..20 {
             env.as_contract(&contract_id, || {
-                let mut units: Map<u64, BloodUnit> = env
-                    .storage()
-                    .persistent()
-                    .get(&BLOOD_UNITS)
-                    .unwrap_or(Map::new(&env));
-                let mut unit = units.get(unit_id).unwrap();
+                let mut unit = registry_read::get_unit(&env, unit_id).unwrap();
                 unit.status = BloodStatus::Reserved;
                 unit.recipient_hospital = Some(hospital.clone());
-                units.set(unit_id, unit);
-                env.storage().persistent().set(&BLOOD_UNITS, &units);
+                registry_write::put_unit(&env, &unit);
             });
 
             env.mock_all_auths();
@@ -5667,7 +9602,7 @@ mod test {
     #[test]
     fn test_super_admin_successful_transfer() {
         let env = Env::default();
-        let (_, admin, client) = setup_contract_with_admin(&env);
+        let (_, _admin, client) = setup_contract_with_admin(&env);
         let new_admin = Address::generate(&env);
 
         env.mock_all_auths();
@@ -5797,7 +9732,7 @@ mod test {
         client.verify_organization(&admin, &org);
 
         let events = env.events().all();
-        assert!(events.len() >= 2);
+        assert!(!events.is_empty());
         let (_, topics, _) = events.last().unwrap();
         assert_eq!(topics.len(), 2);
         assert_eq!(
@@ -5815,7 +9750,7 @@ mod test {
         client.unverify_organization(&admin, &org, &reason);
 
         let events = env.events().all();
-        assert!(events.len() >= 3);
+        assert!(!events.is_empty());
         let (_, topics, _) = events.last().unwrap();
         assert_eq!(topics.len(), 2);
         assert_eq!(
@@ -5856,7 +9791,7 @@ mod test {
         client.verify_organization(&admin, &org);
 
         let organization = client.get_organization(&org);
-        assert_eq!(organization.verified, true);
+        assert!(organization.verified);
         assert!(organization.verified_timestamp.is_some());
     }
 
@@ -5921,7 +9856,7 @@ mod test {
         client.unverify_organization(&admin, &org, &reason);
 
         let organization = client.get_organization(&org);
-        assert_eq!(organization.verified, false);
+        assert!(!organization.verified);
         assert!(organization.verified_timestamp.is_none());
     }
 
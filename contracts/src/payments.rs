@@ -1,15 +1,17 @@
+//! **Dispute evidence (beyond `Symbol` limits).**
+//!
+//! Soroban `Symbol` values are capped (~32 characters) and cannot carry full IPFS CIDs,
+//! long URLs, or rich text. Disputes therefore store:
+//! - [`Dispute::reason`]: human-readable explanation as Soroban [`String`].
+//! - [`Dispute::evidence_digest`]: a fixed-size fingerprint (typically 32 bytes, e.g. SHA-256)
+//!   over the canonical evidence payload agreed off-chain.
+//! - [`Dispute::evidence_ref_chunks`]: optional ordered segments. If a single `String` is not
+//!   enough for a CID/URL, split off-chain, submit each piece in order, and reassemble off-chain
+//!   for display. Verifiers must check the reconstructed reference against `evidence_digest`.
+
 use soroban_sdk::{contracttype, Address, Bytes, String, Symbol, Vec};
 
-/// **Dispute evidence (beyond `Symbol` limits).**
-///
-/// Soroban `Symbol` values are capped (~32 characters) and cannot carry full IPFS CIDs,
-/// long URLs, or rich text. Disputes therefore store:
-/// - [`Dispute::reason`]: human-readable explanation as Soroban [`String`].
-/// - [`Dispute::evidence_digest`]: a fixed-size fingerprint (typically 32 bytes, e.g. SHA-256)
-///   over the canonical evidence payload agreed off-chain.
-/// - [`Dispute::evidence_ref_chunks`]: optional ordered segments. If a single `String` is not
-///   enough for a CID/URL, split off-chain, submit each piece in order, and reassemble off-chain
-///   for display. Verifiers must check the reconstructed reference against `evidence_digest`.
+use crate::RequestStatus;
 
 /// Represents the current state of a payment in its lifecycle
 #[contracttype]
@@ -27,6 +29,8 @@ pub enum PaymentStatus {
     Completed,
     /// Payment refunded to payer
     Refunded,
+    /// Escrow split between payee and payer by a dispute settlement
+    Settled,
     /// Payment cancelled before escrow
     Cancelled,
 }
@@ -41,8 +45,23 @@ pub enum DisputeStatus {
     ResolvedInFavorOfPayer,
     /// Dispute resolved in favor of the payee (payout)
     ResolvedInFavorOfPayee,
-    /// Dispute dismissed without change
+    /// Dispute dismissed; the payment goes back to escrow and the request to
+    /// the status it had when the dispute was raised
     Dismissed,
+    /// Dispute settled by splitting the escrow between payee and payer
+    PartialSettlement,
+}
+
+/// How a partial settlement divides a payment's net amount
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisputeSplit {
+    /// Payee receives this many basis points (1/100 of a percent) of the net amount
+    BasisPoints(u32),
+    /// Payee receives this absolute amount of the net amount
+    Amount(i128),
+    /// Payee share follows how much of its own contribution it delivered
+    ProRata,
 }
 
 /// Dispute record for delivery issues
@@ -67,6 +86,89 @@ pub struct Dispute {
     pub raised_at: u64,
     /// Timestamp when dispute was resolved
     pub resolved_at: Option<u64>,
+    /// Amount paid to the payee on resolution (net of fees)
+    pub payee_amount: i128,
+    /// Amount returned to the payer on resolution
+    pub payer_amount: i128,
+    /// Evidence may be submitted until this timestamp
+    pub response_deadline: u64,
+    /// Whether the payer has submitted evidence
+    pub payer_submitted: bool,
+    /// Whether the payee has submitted evidence
+    pub payee_submitted: bool,
+    /// Arbitration vote deadline; 0 when the dispute is resolved by the admin
+    pub vote_deadline: u64,
+    /// Arbiter votes cast so far, in order
+    pub votes: Vec<ArbiterVote>,
+    /// Arbiters of the panel the dispute was raised under; empty when the
+    /// dispute is resolved by the admin
+    pub arbiters: Vec<Address>,
+    /// Matching votes that panel needed to resolve the dispute
+    pub vote_threshold: u32,
+    /// Outcome that panel applies if the vote deadline passes
+    pub default_outcome: DisputeStatus,
+    /// Status of the payment's request when the dispute was raised, restored
+    /// if the dispute is dismissed
+    pub request_status: RequestStatus,
+}
+
+/// One entry in a dispute's append-only evidence log
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisputeEvidence {
+    pub dispute_id: u64,
+    /// 0-based position in the log; entry 0 is the raiser's evidence
+    pub index: u64,
+    pub party: Address,
+    /// Digest of the canonical evidence payload (see module docs)
+    pub digest: Bytes,
+    /// Optional URI/CID fragments; concatenate off-chain in order
+    pub chunks: Vec<String>,
+    pub submitted_at: u64,
+}
+
+/// One page of a dispute's evidence log
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisputeEvidencePage {
+    pub items: Vec<DisputeEvidence>,
+    /// Pass as `cursor` to fetch the next page; `None` when exhausted
+    pub next_cursor: Option<u64>,
+}
+
+impl Dispute {
+    /// Whether evidence collection is over: the response window has closed or
+    /// both payer and payee have submitted
+    pub fn evidence_closed(&self, current_timestamp: u64) -> bool {
+        current_timestamp >= self.response_deadline
+            || (self.payer_submitted && self.payee_submitted)
+    }
+}
+
+/// One arbiter's vote on a dispute
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArbiterVote {
+    pub arbiter: Address,
+    pub outcome: DisputeStatus,
+    /// Payee share in basis points for a `PartialSettlement` vote, else 0
+    pub split_bps: u32,
+    /// Digest of the arbiter's off-chain written rationale
+    pub rationale_digest: Bytes,
+    pub voted_at: u64,
+}
+
+/// Arbiters resolving disputes by N-of-M vote
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArbitrationPanel {
+    pub arbiters: Vec<Address>,
+    /// Matching votes needed to resolve a dispute
+    pub threshold: u32,
+    /// Seconds after a dispute is raised that arbiters may vote
+    pub vote_window: u64,
+    /// Outcome applied when the window passes without reaching the threshold
+    pub default_outcome: DisputeStatus,
 }
 
 /// Proof bundle attached to a payment for escrow release
@@ -104,6 +206,9 @@ pub struct ReleaseConditions {
 }
 
 /// Core payment transaction structure
+///
+/// The delivery proof attached with `attach_proof_bundle` is kept under
+/// `DataKey::ProofBundle(payment_id)` rather than on the payment itself.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Payment {
@@ -125,8 +230,6 @@ pub struct Payment {
     pub status: PaymentStatus,
     /// Timestamp when escrow was released (if applicable)
     pub escrow_released_at: Option<u64>,
-    /// Proof bundle attached for escrow release (if any)
-    pub proof_bundle: Option<ProofBundle>,
 }
 
 /// Escrow account holding locked funds
@@ -208,16 +311,19 @@ impl Payment {
             (PaymentStatus::Escrowed, PaymentStatus::Refunded) => true,
             (PaymentStatus::Escrowed, PaymentStatus::Disputed) => true,
 
-            // Disputed can go to Resolved
+            // Disputed can go to Resolved, or back to Escrowed when dismissed
             (PaymentStatus::Disputed, PaymentStatus::Resolved) => true,
+            (PaymentStatus::Disputed, PaymentStatus::Escrowed) => true,
 
-            // Resolved can go to Completed or Refunded
+            // Resolved can go to Completed, Refunded or Settled
             (PaymentStatus::Resolved, PaymentStatus::Completed) => true,
             (PaymentStatus::Resolved, PaymentStatus::Refunded) => true,
+            (PaymentStatus::Resolved, PaymentStatus::Settled) => true,
 
             // Terminal states cannot transition
             (PaymentStatus::Completed, _) => false,
             (PaymentStatus::Refunded, _) => false,
+            (PaymentStatus::Settled, _) => false,
             (PaymentStatus::Cancelled, _) => false,
 
             // All other transitions are invalid
@@ -229,7 +335,10 @@ impl Payment {
     pub fn is_terminal(&self) -> bool {
         matches!(
             self.status,
-            PaymentStatus::Completed | PaymentStatus::Refunded | PaymentStatus::Cancelled
+            PaymentStatus::Completed
+                | PaymentStatus::Refunded
+                | PaymentStatus::Settled
+                | PaymentStatus::Cancelled
         )
     }
 }
@@ -245,24 +354,31 @@ impl EscrowAccount {
 
     /// Checks if release conditions are satisfied
     pub fn can_release(&self, current_timestamp: u64, approver: Option<&Address>, proof_bundle: Option<&ProofBundle>) -> bool {
+        self.check_release(current_timestamp, approver, proof_bundle)
+            .is_ok()
+    }
+
+    /// Checks release conditions, reporting the first one that is unmet
+    pub fn check_release(
+        &self,
+        current_timestamp: u64,
+        approver: Option<&Address>,
+        proof_bundle: Option<&ProofBundle>,
+    ) -> Result<(), PaymentError> {
         // Check timestamp condition
         if current_timestamp < self.release_conditions.min_timestamp {
-            return false;
+            return Err(PaymentError::ReleaseTooEarly);
         }
 
         // Check medical records verification
         if !self.release_conditions.medical_records_verified {
-            return false;
+            return Err(PaymentError::MedicalRecordsUnverified);
         }
 
         // Check approver if required
         if let Some(required_approver) = &self.release_conditions.authorized_approver {
-            if let Some(provided_approver) = approver {
-                if required_approver != provided_approver {
-                    return false;
-                }
-            } else {
-                return false;
+            if approver != Some(required_approver) {
+                return Err(PaymentError::ApproverMismatch);
             }
         }
 
@@ -270,11 +386,12 @@ impl EscrowAccount {
         if self.release_conditions.require_proof_bundle {
             match proof_bundle {
                 Some(bundle) if bundle.validated => {}
-                _ => return false,
+                Some(_) => return Err(PaymentError::ProofBundleInvalid),
+                None => return Err(PaymentError::ProofBundleMissing),
             }
         }
 
-        true
+        Ok(())
     }
 }
 
@@ -306,6 +423,32 @@ impl FeeStructure {
     }
 }
 
+impl DisputeSplit {
+    /// Payee share of `net_amount` for an explicit split.
+    ///
+    /// `ProRata` must be converted to basis points by the caller first.
+    pub fn payee_amount(&self, net_amount: i128) -> Result<i128, PaymentError> {
+        let payee_amount = match self {
+            DisputeSplit::BasisPoints(bps) => {
+                if *bps > 10_000 {
+                    return Err(PaymentError::InvalidAmount);
+                }
+                net_amount
+                    .checked_mul(*bps as i128)
+                    .ok_or(PaymentError::InvalidAmount)?
+                    / 10_000
+            }
+            DisputeSplit::Amount(amount) => *amount,
+            DisputeSplit::ProRata => return Err(PaymentError::InvalidAmount),
+        };
+
+        if payee_amount < 0 || payee_amount > net_amount {
+            return Err(PaymentError::InvalidAmount);
+        }
+        Ok(payee_amount)
+    }
+}
+
 /// Error types for payment operations
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -321,4 +464,20 @@ pub enum PaymentError {
     ProofBundleMissing,
     /// Proof bundle exists but has not been validated
     ProofBundleInvalid,
+    /// Escrow release attempted before `min_timestamp`
+    ReleaseTooEarly,
+    /// Escrow release requires verified medical records
+    MedicalRecordsUnverified,
+    /// Release approver is missing or not the authorized approver
+    ApproverMismatch,
+}
+
+impl ProofBundle {
+    /// Checks that every anchored artifact hash is a full 32-byte digest
+    pub fn hashes_valid(&self) -> bool {
+        self.delivery_hash.len() == 32
+            && self.signature_hash.len() == 32
+            && self.photo_hash.len() == 32
+            && self.medical_hash.len() == 32
+    }
 }
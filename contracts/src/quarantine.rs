@@ -0,0 +1,194 @@
+//! # quarantine
+//!
+//! Persisted quarantine cases backing `quarantine_blood`, `finalize_quarantine`,
+//! `get_open_quarantines` and `escalate_overdue_quarantines`.
+//!
+//! Every unit placed in quarantine gets a [`QuarantineCase`] under
+//! `DataKey::QuarantineCase(unit_id)` recording why it was held, the evidence
+//! digest, the assigned reviewer and a decision deadline. Open cases are also
+//! listed in the `UnitIndex::OpenQuarantine` index, bucketed by unit ID like
+//! the registry's other unit indexes, so QA can page through what is
+//! currently held without replaying the event stream.
+//! Only the case's reviewer, or the admin as an override, may decide it.
+//!
+//! A case stays stored after it closes; only its status and decision fields
+//! change. Quarantining the same unit again replaces it with a new case.
+
+use soroban_sdk::{contracttype, symbol_short, vec, Address, Bytes, Env, Vec};
+
+use crate::{
+    constants::QUARANTINE_REVIEW_SECONDS,
+    record_status_change,
+    registry_read::{index_ids, page_limit, UnitIndex},
+    registry_write, BloodStatus, BloodUnit, DataKey, QuarantineLifecycleEvent, QuarantineReason,
+};
+
+/// Lifecycle of a quarantine case
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuarantineCaseStatus {
+    Open,
+    Released,
+    Discarded,
+    /// Discarded automatically after the decision deadline passed
+    Escalated,
+}
+
+/// Quarantine case opened whenever a unit enters `Quarantined`
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuarantineCase {
+    pub unit_id: u64,
+    pub reason: QuarantineReason,
+    /// Fingerprint of the off-chain evidence (e.g. SHA-256); empty when the
+    /// quarantine was raised automatically
+    pub evidence_digest: Bytes,
+    pub reviewer: Address,
+    pub opened_by: Address,
+    pub opened_at: u64,
+    /// Unreviewed cases are escalated to discard from this timestamp
+    pub decision_deadline: u64,
+    pub status: QuarantineCaseStatus,
+    pub decided_by: Option<Address>,
+    pub decided_at: Option<u64>,
+}
+
+/// One page of open quarantine cases
+#[contracttype]
+#[derive(Clone)]
+pub struct QuarantineCasePage {
+    pub items: Vec<QuarantineCase>,
+    /// Pass as `cursor` to fetch the next page; `None` when exhausted
+    pub next_cursor: Option<u64>,
+}
+
+/// One batch of `escalate_overdue_quarantines`
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuarantineEscalationPage {
+    /// Units discarded in this batch
+    pub escalated: Vec<u64>,
+    /// Pass as `cursor` to check the next batch; `None` when exhausted
+    pub next_cursor: Option<u64>,
+}
+
+/// Seconds reviewers have to decide a case (defaults to [`QUARANTINE_REVIEW_SECONDS`])
+pub fn review_window(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::QuarantineReviewWindow)
+        .unwrap_or(QUARANTINE_REVIEW_SECONDS)
+}
+
+/// Load the latest quarantine case for `unit_id`, if any
+pub fn get_case(env: &Env, unit_id: u64) -> Option<QuarantineCase> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::QuarantineCase(unit_id))
+}
+
+/// Move `unit` into `Quarantined` and open its case.
+///
+/// Persists the unit, records the status change and publishes the
+/// `("quar", "place")` lifecycle event. The caller is responsible for any
+/// custody hop the unit was part of.
+pub fn place(
+    env: &Env,
+    unit: &mut BloodUnit,
+    actor: Address,
+    reason: QuarantineReason,
+    evidence_digest: Bytes,
+    reviewer: Address,
+) -> QuarantineCase {
+    let current_time = env.ledger().timestamp();
+    let old_status = unit.status;
+
+    unit.status = BloodStatus::Quarantined;
+    registry_write::put_unit(env, unit);
+    record_status_change(
+        env,
+        unit.id,
+        old_status,
+        BloodStatus::Quarantined,
+        actor.clone(),
+    );
+
+    let case = QuarantineCase {
+        unit_id: unit.id,
+        reason,
+        evidence_digest,
+        reviewer,
+        opened_by: actor.clone(),
+        opened_at: current_time,
+        decision_deadline: current_time.saturating_add(review_window(env)),
+        status: QuarantineCaseStatus::Open,
+        decided_by: None,
+        decided_at: None,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::QuarantineCase(unit.id), &case);
+
+    registry_write::index_insert(env, &UnitIndex::OpenQuarantine, unit.id);
+
+    env.events().publish(
+        (symbol_short!("quar"), symbol_short!("place")),
+        QuarantineLifecycleEvent {
+            blood_unit_id: unit.id,
+            old_status,
+            new_status: BloodStatus::Quarantined,
+            actor,
+            reason,
+            disposition_code: 0,
+            timestamp: current_time,
+        },
+    );
+
+    case
+}
+
+/// Record the decision on `unit_id`'s open case and drop it from the open index.
+///
+/// Units quarantined before cases were persisted have no case; `None` is
+/// returned for them.
+pub fn close(
+    env: &Env,
+    unit_id: u64,
+    status: QuarantineCaseStatus,
+    decided_by: Address,
+) -> Option<QuarantineCase> {
+    let mut case = get_case(env, unit_id)?;
+    case.status = status;
+    case.decided_by = Some(decided_by);
+    case.decided_at = Some(env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .set(&DataKey::QuarantineCase(unit_id), &case);
+
+    registry_write::index_remove(env, &UnitIndex::OpenQuarantine, unit_id);
+
+    Some(case)
+}
+
+/// Up to `limit` (at least 1) open case IDs strictly greater than `cursor`,
+/// ascending, and the cursor to resume from, as [`index_ids`] returns them.
+pub fn open_case_ids_after(env: &Env, cursor: Option<u64>, limit: u32) -> (Vec<u64>, Option<u64>) {
+    index_ids(env, &UnitIndex::OpenQuarantine, cursor, limit.max(1))
+}
+
+/// Page through open cases in ascending unit ID order.
+///
+/// Returns cases for unit IDs strictly greater than `cursor`, up to
+/// [`page_limit`]`(limit)` of them.
+pub fn get_open_quarantines(env: &Env, cursor: Option<u64>, limit: u32) -> QuarantineCasePage {
+    let (ids, next_cursor) = open_case_ids_after(env, cursor, page_limit(limit));
+
+    let mut items = vec![env];
+    for unit_id in ids.iter() {
+        if let Some(case) = get_case(env, unit_id) {
+            items.push_back(case);
+        }
+    }
+
+    QuarantineCasePage { items, next_cursor }
+}
@@ -0,0 +1,211 @@
+//! # records
+//!
+//! Patient health record registry backing `store_record`, `get_record`,
+//! `grant_access`, `revoke_access` and `verify_access`.
+//!
+//! Only record **hashes** live on-chain; the records themselves stay off-chain.
+//! Each patient has an append-only list of versions stored one entry per
+//! version under `DataKey::PatientRecord(patient, version)`, with the latest
+//! version number under `DataKey::PatientRecordCount(patient)`.
+//!
+//! Access is patient-controlled: a patient grants a provider access (optionally
+//! until an expiry timestamp) and may revoke it at any time. `verify_access`
+//! enforces those grants and publishes an access-log event for every check.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env};
+
+use crate::{DataKey, Error};
+
+/// One version of a patient's health record
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatientRecord {
+    pub patient: Address,
+    /// 1-based version number, incremented on every store
+    pub version: u32,
+    /// SHA-256 hash of the off-chain record payload
+    pub record_hash: BytesN<32>,
+    /// Patient or granted provider who stored this version
+    pub author: Address,
+    pub created_at: u64,
+}
+
+/// Provider access granted by a patient
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessGrant {
+    pub patient: Address,
+    pub provider: Address,
+    pub granted_at: u64,
+    /// Access lapses at this timestamp; `None` means until revoked
+    pub expires_at: Option<u64>,
+}
+
+/// Event data for every `verify_access` check
+#[contracttype]
+#[derive(Clone)]
+pub struct AccessLogEvent {
+    pub patient: Address,
+    pub provider: Address,
+    pub granted: bool,
+    pub timestamp: u64,
+}
+
+/// Return whether `provider` currently holds an unexpired grant from `patient`.
+///
+/// A patient always has access to their own records.
+pub fn has_access(env: &Env, patient: &Address, provider: &Address) -> bool {
+    if patient == provider {
+        return true;
+    }
+
+    let grant: Option<AccessGrant> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessGrant(patient.clone(), provider.clone()));
+
+    match grant {
+        Some(grant) => match grant.expires_at {
+            Some(expires_at) => env.ledger().timestamp() < expires_at,
+            None => true,
+        },
+        None => false,
+    }
+}
+
+/// Append a new record version for `patient` and return its version number.
+///
+/// `author` must be the patient or a provider with an active grant.
+pub fn store_record(
+    env: &Env,
+    author: Address,
+    patient: Address,
+    record_hash: BytesN<32>,
+) -> Result<u32, Error> {
+    author.require_auth();
+
+    if !has_access(env, &patient, &author) {
+        return Err(Error::Unauthorized);
+    }
+
+    let count_key = DataKey::PatientRecordCount(patient.clone());
+    let version: u32 = env
+        .storage()
+        .persistent()
+        .get::<_, u32>(&count_key)
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or(Error::ArithmeticError)?;
+
+    let record = PatientRecord {
+        patient: patient.clone(),
+        version,
+        record_hash,
+        author,
+        created_at: env.ledger().timestamp(),
+    };
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::PatientRecord(patient.clone(), version), &record);
+    env.storage().persistent().set(&count_key, &version);
+
+    env.events()
+        .publish((symbol_short!("record"), symbol_short!("stored")), record);
+
+    Ok(version)
+}
+
+/// Load a specific record version, or the latest when `version` is `None`.
+pub fn get_record(
+    env: &Env,
+    patient: Address,
+    version: Option<u32>,
+) -> Result<PatientRecord, Error> {
+    let version = match version {
+        Some(version) => version,
+        None => env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientRecordCount(patient.clone()))
+            .ok_or(Error::RecordNotFound)?,
+    };
+
+    env.storage()
+        .persistent()
+        .get(&DataKey::PatientRecord(patient, version))
+        .ok_or(Error::RecordNotFound)
+}
+
+/// Grant (or replace the grant for) `provider` access to `patient`'s records.
+pub fn grant_access(
+    env: &Env,
+    patient: Address,
+    provider: Address,
+    expires_at: Option<u64>,
+) -> Result<(), Error> {
+    patient.require_auth();
+
+    let now = env.ledger().timestamp();
+    if let Some(expires_at) = expires_at {
+        if expires_at <= now {
+            return Err(Error::InvalidExpiration);
+        }
+    }
+
+    let grant = AccessGrant {
+        patient: patient.clone(),
+        provider: provider.clone(),
+        granted_at: now,
+        expires_at,
+    };
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::AccessGrant(patient, provider), &grant);
+
+    env.events()
+        .publish((symbol_short!("access"), symbol_short!("grant")), grant);
+
+    Ok(())
+}
+
+/// Revoke a provider's access to `patient`'s records.
+pub fn revoke_access(env: &Env, patient: Address, provider: Address) -> Result<(), Error> {
+    patient.require_auth();
+
+    let key = DataKey::AccessGrant(patient.clone(), provider.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::AccessGrantNotFound);
+    }
+    env.storage().persistent().remove(&key);
+
+    env.events().publish(
+        (symbol_short!("access"), symbol_short!("revoke")),
+        (patient, provider, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// Check `provider`'s access to `patient`'s records and log the attempt.
+///
+/// The provider must authorize the call so the access log cannot be spoofed
+/// on their behalf.
+pub fn verify_access(env: &Env, patient: Address, provider: Address) -> bool {
+    provider.require_auth();
+
+    let granted = has_access(env, &patient, &provider);
+
+    env.events().publish(
+        (symbol_short!("access"), symbol_short!("log")),
+        AccessLogEvent {
+            patient,
+            provider,
+            granted,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    granted
+}
@@ -8,9 +8,17 @@
 //! Every function performs **only** storage reads (`get`) and pure computation.
 //! The public contract entry-points in `lib.rs` delegate to these free functions.
 
-use soroban_sdk::{symbol_short, vec, Address, Env, Map, Symbol, Vec};
+use soroban_sdk::{vec, Address, Env, Symbol, Vec};
 
-use crate::{BloodStatus, BloodUnit, Error, BLOOD_UNITS};
+use crate::{
+    constants::{
+        CRYO_MIN_QUANTITY_ML, FROZEN_MAX_SHELF_LIFE_DAYS, INDEX_BUCKET_SIZE,
+        MAX_INDEX_BUCKETS_PER_PAGE, MAX_QUANTITY_ML, MAX_SHELF_LIFE_DAYS, MIN_QUANTITY_ML,
+        MIN_SHELF_LIFE_DAYS, PLATELET_MAX_SHELF_LIFE_DAYS, SECONDS_PER_DAY,
+    },
+    BloodComponent, BloodStatus, BloodType, BloodUnit, ComponentPolicy, DataKey, Error,
+    StorageTempClass, UnitPage, MAX_PAGE_SIZE, NEXT_ID,
+};
 
 // ── READ ──────────────────────────────────────────────────────────────────────
 
@@ -18,67 +26,295 @@ use crate::{BloodStatus, BloodUnit, Error, BLOOD_UNITS};
 ///
 /// Returns `Err(Error::UnitNotFound)` when the ID does not exist in storage.
 pub fn get_unit(env: &Env, unit_id: u64) -> Result<BloodUnit, Error> {
-    let units: Map<u64, BloodUnit> = env
-        .storage()
+    env.storage()
         .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
+        .get(&DataKey::BloodUnit(unit_id))
+        .ok_or(Error::UnitNotFound)
+}
+
+/// Built-in registration rules for a component when no admin override exists.
+pub fn default_component_policy(component: BloodComponent) -> ComponentPolicy {
+    let (max_shelf_life_days, min_volume_ml, storage_temp) = match component {
+        BloodComponent::WholeBlood | BloodComponent::RedBloodCells => (
+            MAX_SHELF_LIFE_DAYS,
+            MIN_QUANTITY_ML,
+            StorageTempClass::Refrigerated,
+        ),
+        BloodComponent::Platelets => (
+            PLATELET_MAX_SHELF_LIFE_DAYS,
+            MIN_QUANTITY_ML,
+            StorageTempClass::RoomTemperature,
+        ),
+        BloodComponent::Plasma => (
+            FROZEN_MAX_SHELF_LIFE_DAYS,
+            MIN_QUANTITY_ML,
+            StorageTempClass::Frozen,
+        ),
+        BloodComponent::Cryoprecipitate => (
+            FROZEN_MAX_SHELF_LIFE_DAYS,
+            CRYO_MIN_QUANTITY_ML,
+            StorageTempClass::Frozen,
+        ),
+    };
 
-    units.get(unit_id).ok_or(Error::UnitNotFound)
+    ComponentPolicy {
+        min_shelf_life_days: MIN_SHELF_LIFE_DAYS,
+        max_shelf_life_days,
+        min_volume_ml,
+        max_volume_ml: MAX_QUANTITY_ML,
+        storage_temp,
+    }
 }
 
-/// Return all blood units registered by a specific blood bank.
+/// Return the registration rules in force for a component.
 ///
-/// Performs a full-scan of the units map and filters by `bank_id`.
-pub fn get_units_by_bank(env: &Env, bank_id: Address) -> Vec<BloodUnit> {
-    let units: Map<u64, BloodUnit> = env
-        .storage()
+/// An admin override under `DataKey::ComponentPolicy` wins over the defaults.
+pub fn get_component_policy(env: &Env, component: BloodComponent) -> ComponentPolicy {
+    env.storage()
         .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
+        .get(&DataKey::ComponentPolicy(component))
+        .unwrap_or_else(|| default_component_policy(component))
+}
 
-    let mut bank_units = vec![env];
+/// A secondary index of unit IDs maintained by [`crate::registry_write::put_unit`],
+/// except `OpenQuarantine`, which [`crate::quarantine`] keeps.
+///
+/// Each index is sharded into buckets of [`INDEX_BUCKET_SIZE`] consecutive
+/// unit IDs, one ledger entry per non-empty bucket, so no entry grows with
+/// the size of the registry.
+#[derive(Clone)]
+pub enum UnitIndex {
+    Status(BloodStatus),
+    Bank(Address),
+    Hospital(Address),
+    Donor(Address, Symbol),
+    DonorAll(Symbol),
+    /// Units with an open quarantine case
+    OpenQuarantine,
+}
 
-    for (_, unit) in units.iter() {
-        if unit.bank_id == bank_id {
-            bank_units.push_back(unit);
+impl UnitIndex {
+    /// Storage key of bucket number `bucket`
+    pub fn bucket_key(&self, bucket: u64) -> DataKey {
+        match self {
+            UnitIndex::Status(status) => DataKey::StatusUnits(*status, bucket),
+            UnitIndex::Bank(bank_id) => DataKey::BankUnits(bank_id.clone(), bucket),
+            UnitIndex::Hospital(hospital) => DataKey::HospitalUnits(hospital.clone(), bucket),
+            UnitIndex::Donor(bank_id, donor_id) => {
+                DataKey::DonorUnits(bank_id.clone(), donor_id.clone(), bucket)
+            }
+            UnitIndex::DonorAll(donor_id) => DataKey::DonorAllUnits(donor_id.clone(), bucket),
+            UnitIndex::OpenQuarantine => DataKey::OpenQuarantines(bucket),
         }
     }
 
-    bank_units
+    /// Storage key of the bucket holding `unit_id`
+    pub fn key_for(&self, unit_id: u64) -> DataKey {
+        self.bucket_key(unit_id / INDEX_BUCKET_SIZE)
+    }
 }
 
-/// Return `true` when the blood unit's expiration date is in the past.
-///
-/// Returns `Err(Error::UnitNotFound)` when the unit does not exist.
-pub fn is_expired(env: &Env, unit_id: u64) -> Result<bool, Error> {
-    let unit = get_unit(env, unit_id)?;
-    let current_time = env.ledger().timestamp();
-    Ok(unit.expiration_date <= current_time || unit.status == BloodStatus::Expired)
+/// Highest unit ID handed out so far, if any.
+fn last_unit_id(env: &Env) -> Option<u64> {
+    let next_id: u64 = env.storage().persistent().get(&NEXT_ID).unwrap_or(1);
+    next_id.checked_sub(1).filter(|id| *id > 0)
 }
 
-/// Return all blood units donated by the given `donor_id` symbol.
+/// Return up to `limit` ascending IDs from `index` strictly greater than
+/// `cursor`, and the cursor to resume from.
 ///
-/// Performs a full-scan of the units map and filters by `donor_id` field.
-pub fn get_units_by_donor(env: &Env, donor_id: Symbol) -> Vec<BloodUnit> {
-    let units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
+/// Reads at most [`MAX_INDEX_BUCKETS_PER_PAGE`] buckets. The returned cursor
+/// is `None` once the index is exhausted; otherwise it is the last returned
+/// ID, or the end of the last bucket read when the bucket budget ran out
+/// first, so a page may come back short (even empty) with a cursor.
+pub fn index_ids(
+    env: &Env,
+    index: &UnitIndex,
+    cursor: Option<u64>,
+    limit: u32,
+) -> (Vec<u64>, Option<u64>) {
+    let mut ids = Vec::new(env);
+    let first = match cursor {
+        None => 0,
+        Some(after) => match after.checked_add(1) {
+            Some(first) => first,
+            None => return (ids, None),
+        },
+    };
+    let Some(last) = last_unit_id(env) else {
+        return (ids, None);
+    };
 
-    let mut donor_units = vec![env];
+    let mut bucket = first / INDEX_BUCKET_SIZE;
+    let mut buckets_read = 0;
+    while bucket <= last / INDEX_BUCKET_SIZE {
+        if buckets_read == MAX_INDEX_BUCKETS_PER_PAGE {
+            return (ids, Some(bucket * INDEX_BUCKET_SIZE - 1));
+        }
+        buckets_read += 1;
 
-    for (_, unit) in units.iter() {
-        if unit.donor_id == donor_id {
-            // Exclude a generic "ANON" donor from per-donor queries unless
-            // the caller explicitly asks for it (i.e. passes symbol_short!("ANON")).
-            if unit.donor_id == symbol_short!("ANON") && donor_id != symbol_short!("ANON") {
+        let bucket_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index.bucket_key(bucket))
+            .unwrap_or(Vec::new(env));
+        for unit_id in bucket_ids.iter() {
+            if unit_id < first {
                 continue;
             }
-            donor_units.push_back(unit);
+            if ids.len() == limit {
+                let next_cursor = ids.last();
+                return (ids, next_cursor);
+            }
+            ids.push_back(unit_id);
+        }
+        bucket += 1;
+    }
+
+    (ids, None)
+}
+
+/// Day bucket of the expiry index holding units expiring at `expiration`.
+pub fn expiry_day(expiration: u64) -> u64 {
+    expiration / SECONDS_PER_DAY
+}
+
+/// Days with `Available` units of `blood_type` in the expiry index, ascending.
+pub fn get_expiry_days(env: &Env, blood_type: BloodType) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AvailableExpiryDays(blood_type))
+        .unwrap_or(Vec::new(env))
+}
+
+/// `(expiration, unit_id)` position in the `Available` expiry index
+pub type ExpiryEntry = (u64, u64);
+
+/// Return up to `limit` `(expiration, unit_id)` entries of the `Available`
+/// index for a blood type, earliest first, that expire strictly after
+/// `not_before` and sort strictly after `after`, plus the entry to resume
+/// from (`None` once the index is exhausted).
+///
+/// The index is split into one bucket per expiry day; only non-empty days
+/// are listed, so at most `limit + 1` buckets are read and FIFO readers never
+/// sort on-chain.
+pub fn expiry_entries(
+    env: &Env,
+    blood_type: BloodType,
+    not_before: u64,
+    after: Option<ExpiryEntry>,
+    limit: u32,
+) -> (Vec<ExpiryEntry>, Option<ExpiryEntry>) {
+    let floor = (not_before, u64::MAX);
+    let after = match after {
+        Some(after) if after > floor => after,
+        _ => floor,
+    };
+
+    let days = get_expiry_days(env, blood_type);
+    let first_day = match days.binary_search(expiry_day(after.0)) {
+        Ok(pos) | Err(pos) => pos,
+    };
+
+    let mut entries = Vec::new(env);
+    for day in days.slice(first_day..days.len()).iter() {
+        let bucket: Vec<ExpiryEntry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AvailableByExpiry(blood_type, day))
+            .unwrap_or(Vec::new(env));
+        let start = match bucket.binary_search(after) {
+            Ok(pos) => pos + 1,
+            Err(pos) => pos,
+        };
+        for entry in bucket.slice(start..bucket.len()).iter() {
+            if entries.len() == limit {
+                let resume = entries.last();
+                return (entries, resume);
+            }
+            entries.push_back(entry);
         }
     }
 
-    donor_units
+    (entries, None)
+}
+
+/// Visit the `Available` entries of a blood type in FIFO order, as
+/// [`expiry_entries`] returns them, until `visit` returns `false`.
+pub fn scan_available(
+    env: &Env,
+    blood_type: BloodType,
+    not_before: u64,
+    mut after: Option<ExpiryEntry>,
+    mut visit: impl FnMut(u64, u64) -> bool,
+) {
+    loop {
+        let (entries, resume) = expiry_entries(env, blood_type, not_before, after, MAX_PAGE_SIZE);
+        for (expiration, unit_id) in entries.iter() {
+            if !visit(expiration, unit_id) {
+                return;
+            }
+        }
+        match resume {
+            Some(position) => after = Some(position),
+            None => return,
+        }
+    }
+}
+
+/// Clamp a caller-supplied page size to `1..=MAX_PAGE_SIZE`.
+///
+/// A `limit` of 0 selects the maximum page size.
+pub fn page_limit(limit: u32) -> u32 {
+    if limit == 0 {
+        MAX_PAGE_SIZE
+    } else {
+        limit.min(MAX_PAGE_SIZE)
+    }
+}
+
+/// Build one page of units from a bucketed ID index.
+///
+/// Returns the units whose IDs are strictly greater than `cursor`, up to
+/// [`page_limit`]`(limit)` of them, with the cursor from [`index_ids`].
+pub fn page_index(env: &Env, index: &UnitIndex, cursor: Option<u64>, limit: u32) -> UnitPage {
+    let (ids, next_cursor) = index_ids(env, index, cursor, page_limit(limit));
+
+    let mut items = vec![env];
+    for unit_id in ids.iter() {
+        if let Ok(unit) = get_unit(env, unit_id) {
+            items.push_back(unit);
+        }
+    }
+
+    UnitPage { items, next_cursor }
+}
+
+/// Return one page of blood units registered by a specific blood bank.
+///
+/// Reads the `BankUnits` index instead of scanning every unit.
+pub fn get_units_by_bank(env: &Env, bank_id: Address, cursor: Option<u64>, limit: u32) -> UnitPage {
+    page_index(env, &UnitIndex::Bank(bank_id), cursor, limit)
+}
+
+/// Return `true` when the blood unit's expiration date is in the past.
+///
+/// Returns `Err(Error::UnitNotFound)` when the unit does not exist.
+pub fn is_expired(env: &Env, unit_id: u64) -> Result<bool, Error> {
+    let unit = get_unit(env, unit_id)?;
+    let current_time = env.ledger().timestamp();
+    Ok(unit.expiration_date <= current_time || unit.status == BloodStatus::Expired)
+}
+
+/// Return one page of blood units donated by the given `donor_id` symbol.
+///
+/// Reads the `DonorAllUnits` index. Anonymous units are only returned when the
+/// caller explicitly asks for `symbol_short!("ANON")`.
+pub fn get_units_by_donor(
+    env: &Env,
+    donor_id: Symbol,
+    cursor: Option<u64>,
+    limit: u32,
+) -> UnitPage {
+    page_index(env, &UnitIndex::DonorAll(donor_id), cursor, limit)
 }
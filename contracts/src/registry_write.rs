@@ -6,47 +6,238 @@
 //! The public contract entry-points in `lib.rs` delegate to these free functions.
 //!
 //! ## Storage Write Audit (PR checklist)
-//! - [x] `register_unit`  — writes BLOOD_UNITS, NEXT_ID
-//! - [x] `update_status`  — writes BLOOD_UNITS
-//! - [x] `expire_unit`    — writes BLOOD_UNITS
+//! - [x] `put_unit`       — writes DataKey::BloodUnit(id) and the affected bucket of every
+//!   index (StatusUnits, BankUnits, HospitalUnits, DonorUnits, DonorAllUnits,
+//!   AvailableByExpiry, AvailableExpiryDays)
+//! - [x] `register_unit`  — writes DataKey::BloodUnit(id) via `put_unit`, NEXT_ID
+//! - [x] `update_status`  — writes DataKey::BloodUnit(id) via `put_unit`
+//! - [x] `expire_unit`    — writes DataKey::BloodUnit(id) via `put_unit`
 //! - [x] `check_and_expire_batch` — delegates to `expire_unit`
+//! - [x] `split_unit`     — writes children via `register_unit`, parent via `put_unit`,
+//!   DataKey::UnitParent(child), DataKey::UnitChildren(parent), children's trail pages
+//! - [x] `migrate_legacy_units` — writes DataKey::BloodUnit(id), rewrites/removes BLOOD_UNITS
 
 use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
 use crate::{
-    constants::{
-        MAX_BATCH_EXPIRY_SIZE, MAX_QUANTITY_ML, MAX_SHELF_LIFE_DAYS, MIN_QUANTITY_ML,
-        MIN_SHELF_LIFE_DAYS, SECONDS_PER_DAY,
-    },
-    get_next_id, record_status_change, BloodComponent, BloodRegisteredEvent, BloodStatus,
-    BloodType, BloodUnit, Error, BLOOD_UNITS,
+    append_to_custody_trail,
+    constants::{MAX_BATCH_EXPIRY_SIZE, MAX_SPLIT_CHILDREN, SECONDS_PER_DAY},
+    get_next_id, record_status_change,
+    registry_read::{self, UnitIndex},
+    BloodComponent, BloodRegisteredEvent, BloodStatus, BloodType, BloodUnit, DataKey, Error,
+    HealthChainContract, BLOOD_UNITS,
 };
 
 // ── WRITE ─────────────────────────────────────────────────────────────────────
 
+/// Persist a blood unit under its own `DataKey::BloodUnit(id)` entry.
+///
+/// Each unit lives in a separate ledger entry so a write only touches the
+/// unit being changed, not the whole inventory. The previously stored version
+/// is diffed against `unit` so the secondary indexes stay in sync no matter
+/// which transition path performed the write.
+pub fn put_unit(env: &Env, unit: &BloodUnit) {
+    let previous = registry_read::get_unit(env, unit.id).ok();
+    env.storage()
+        .persistent()
+        .set(&DataKey::BloodUnit(unit.id), unit);
+    update_indexes(env, previous.as_ref(), unit);
+}
+
+// ── INDEXES ───────────────────────────────────────────────────────────────────
+
+/// Move `unit` between index buckets according to what changed since `previous`.
+fn update_indexes(env: &Env, previous: Option<&BloodUnit>, unit: &BloodUnit) {
+    let id = unit.id;
+
+    if previous.map(|p| p.status) != Some(unit.status) {
+        if let Some(p) = previous {
+            index_remove(env, &UnitIndex::Status(p.status), id);
+        }
+        index_insert(env, &UnitIndex::Status(unit.status), id);
+    }
+
+    if previous.map(|p| &p.bank_id) != Some(&unit.bank_id) {
+        if let Some(p) = previous {
+            index_remove(env, &UnitIndex::Bank(p.bank_id.clone()), id);
+        }
+        index_insert(env, &UnitIndex::Bank(unit.bank_id.clone()), id);
+    }
+
+    if previous.map(|p| (&p.bank_id, &p.donor_id)) != Some((&unit.bank_id, &unit.donor_id)) {
+        if let Some(p) = previous {
+            index_remove(
+                env,
+                &UnitIndex::Donor(p.bank_id.clone(), p.donor_id.clone()),
+                id,
+            );
+            index_remove(env, &UnitIndex::DonorAll(p.donor_id.clone()), id);
+        }
+        index_insert(
+            env,
+            &UnitIndex::Donor(unit.bank_id.clone(), unit.donor_id.clone()),
+            id,
+        );
+        index_insert(env, &UnitIndex::DonorAll(unit.donor_id.clone()), id);
+    }
+
+    let previous_hospital = previous.and_then(|p| p.recipient_hospital.clone());
+    if previous_hospital != unit.recipient_hospital {
+        if let Some(hospital) = previous_hospital {
+            index_remove(env, &UnitIndex::Hospital(hospital), id);
+        }
+        if let Some(hospital) = unit.recipient_hospital.clone() {
+            index_insert(env, &UnitIndex::Hospital(hospital), id);
+        }
+    }
+
+    let previous_expiry = previous
+        .filter(|p| p.status == BloodStatus::Available)
+        .map(|p| (p.blood_type, p.expiration_date));
+    let current_expiry = Some((unit.blood_type, unit.expiration_date))
+        .filter(|_| unit.status == BloodStatus::Available);
+    if previous_expiry != current_expiry {
+        if let Some((blood_type, expiration)) = previous_expiry {
+            expiry_index_remove(env, blood_type, expiration, id);
+        }
+        if let Some((blood_type, expiration)) = current_expiry {
+            expiry_index_insert(env, blood_type, expiration, id);
+        }
+    }
+}
+
+/// Insert `unit_id` into its bucket of an ascending ID index, ignoring duplicates.
+pub(crate) fn index_insert(env: &Env, index: &UnitIndex, unit_id: u64) {
+    let key = index.key_for(unit_id);
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    if let Err(pos) = ids.binary_search(unit_id) {
+        ids.insert(pos, unit_id);
+        env.storage().persistent().set(&key, &ids);
+    }
+}
+
+/// Remove `unit_id` from its bucket of an ascending ID index; empty buckets
+/// are deleted.
+pub(crate) fn index_remove(env: &Env, index: &UnitIndex, unit_id: u64) {
+    let key = index.key_for(unit_id);
+    let Some(mut ids) = env.storage().persistent().get::<DataKey, Vec<u64>>(&key) else {
+        return;
+    };
+    if let Ok(pos) = ids.binary_search(unit_id) {
+        ids.remove(pos);
+        if ids.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &ids);
+        }
+    }
+}
+
+/// Insert an `Available` unit into its blood type's bucket for its expiry
+/// day, listing the day when its bucket is created.
+fn expiry_index_insert(env: &Env, blood_type: BloodType, expiration: u64, unit_id: u64) {
+    let day = registry_read::expiry_day(expiration);
+    let key = DataKey::AvailableByExpiry(blood_type, day);
+    let mut entries: Vec<(u64, u64)> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let Err(pos) = entries.binary_search((expiration, unit_id)) else {
+        return;
+    };
+    entries.insert(pos, (expiration, unit_id));
+    env.storage().persistent().set(&key, &entries);
+
+    if entries.len() == 1 {
+        let mut days = registry_read::get_expiry_days(env, blood_type);
+        if let Err(pos) = days.binary_search(day) {
+            days.insert(pos, day);
+            env.storage()
+                .persistent()
+                .set(&DataKey::AvailableExpiryDays(blood_type), &days);
+        }
+    }
+}
+
+/// Remove a unit from its blood type's expiry bucket, unlisting the day once
+/// its bucket is empty.
+fn expiry_index_remove(env: &Env, blood_type: BloodType, expiration: u64, unit_id: u64) {
+    let day = registry_read::expiry_day(expiration);
+    let key = DataKey::AvailableByExpiry(blood_type, day);
+    let Some(mut entries) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, Vec<(u64, u64)>>(&key)
+    else {
+        return;
+    };
+    let Ok(pos) = entries.binary_search((expiration, unit_id)) else {
+        return;
+    };
+    entries.remove(pos);
+    if !entries.is_empty() {
+        env.storage().persistent().set(&key, &entries);
+        return;
+    }
+
+    env.storage().persistent().remove(&key);
+    let days_key = DataKey::AvailableExpiryDays(blood_type);
+    let mut days = registry_read::get_expiry_days(env, blood_type);
+    if let Ok(pos) = days.binary_search(day) {
+        days.remove(pos);
+        if days.is_empty() {
+            env.storage().persistent().remove(&days_key);
+        } else {
+            env.storage().persistent().set(&days_key, &days);
+        }
+    }
+}
+
 /// Register a new blood unit into the inventory.
 ///
 /// Validates quantity and expiration window, then persists a fresh [`BloodUnit`]
 /// with `status = Available`.  Emits a `blood/register` event and returns the
-/// new unit ID.
+/// new unit ID.  `actor` is the bank or the delegate registering on its behalf.
+#[allow(clippy::too_many_arguments)]
 pub fn register_unit(
     env: &Env,
     bank_id: Address,
+    actor: Address,
     blood_type: BloodType,
     component: BloodComponent,
     quantity_ml: u32,
     expiration_timestamp: u64,
     donor_id: Option<Symbol>,
 ) -> Result<u64, Error> {
+    // Flagged donors are deferred from further donations
+    if let Some(donor) = donor_id.clone() {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::DonorDeferral(bank_id.clone(), donor))
+        {
+            return Err(Error::DonorDeferred);
+        }
+    }
+
+    let policy = registry_read::get_component_policy(env, component);
+
     // Validate quantity
-    if !(MIN_QUANTITY_ML..=MAX_QUANTITY_ML).contains(&quantity_ml) {
+    if !(policy.min_volume_ml..=policy.max_volume_ml).contains(&quantity_ml) {
         return Err(Error::InvalidQuantity);
     }
 
     // Validate expiration
     let current_time = env.ledger().timestamp();
-    let min_expiration = current_time + (MIN_SHELF_LIFE_DAYS * SECONDS_PER_DAY);
-    let max_expiration = current_time + (MAX_SHELF_LIFE_DAYS * SECONDS_PER_DAY);
+    let min_expiration =
+        current_time.saturating_add(policy.min_shelf_life_days.saturating_mul(SECONDS_PER_DAY));
+    let max_expiration =
+        current_time.saturating_add(policy.max_shelf_life_days.saturating_mul(SECONDS_PER_DAY));
 
     if expiration_timestamp <= current_time || expiration_timestamp < min_expiration {
         return Err(Error::InvalidExpiration);
@@ -74,14 +265,7 @@ pub fn register_unit(
         delivery_timestamp: None,
     };
 
-    let mut units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
-    units.set(unit_id, blood_unit);
-    env.storage().persistent().set(&BLOOD_UNITS, &units);
+    put_unit(env, &blood_unit);
 
     // Record initial status
     record_status_change(
@@ -89,7 +273,7 @@ pub fn register_unit(
         unit_id,
         BloodStatus::Available, // "Old" status doesn't exist for new units, use current
         BloodStatus::Available,
-        bank_id.clone(),
+        actor,
     );
 
     // Emit registration event
@@ -121,33 +305,128 @@ pub fn update_status(
     new_status: BloodStatus,
     actor: Address,
 ) -> Result<(), Error> {
-    let mut units: Map<u64, BloodUnit> = env
-        .storage()
-        .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
-
-    let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    let mut unit = registry_read::get_unit(env, unit_id)?;
     let old_status = unit.status;
 
     unit.status = new_status;
-    units.set(unit_id, unit);
-    env.storage().persistent().set(&BLOOD_UNITS, &units);
+    put_unit(env, &unit);
 
     record_status_change(env, unit_id, old_status, new_status, actor);
 
     Ok(())
 }
 
-/// Force mark a blood unit as expired.
-pub fn expire_unit(env: &Env, unit_id: u64) -> Result<(), Error> {
-    let mut units: Map<u64, BloodUnit> = env
-        .storage()
+/// Split an `Available` whole-blood unit into component child units.
+///
+/// The parent is discarded. Children are registered through [`register_unit`]
+/// (so each is validated against its component policy), keep the parent's
+/// donor, are linked via `UnitParent` / `UnitChildren`, and start with a copy
+/// of the parent's custody trail. The children's combined volume may not
+/// exceed the parent's. `actor` is the bank or the delegate splitting on its
+/// behalf.
+pub fn split_unit(
+    env: &Env,
+    bank_id: Address,
+    actor: Address,
+    parent_id: u64,
+    children: Vec<(BloodComponent, u32, u64)>,
+) -> Result<Vec<u64>, Error> {
+    let mut parent = registry_read::get_unit(env, parent_id)?;
+
+    if parent.bank_id != bank_id {
+        return Err(Error::NotCurrentCustodian);
+    }
+    if parent.component != BloodComponent::WholeBlood {
+        return Err(Error::InvalidComponent);
+    }
+    if parent.status != BloodStatus::Available {
+        return Err(Error::InvalidStatus);
+    }
+    if parent.expiration_date <= env.ledger().timestamp() {
+        return Err(Error::UnitExpired);
+    }
+    if children.is_empty() {
+        return Err(Error::InvalidQuantity);
+    }
+    if children.len() > MAX_SPLIT_CHILDREN {
+        return Err(Error::BatchSizeExceeded);
+    }
+
+    let mut total_ml: u32 = 0;
+    for (component, quantity_ml, _) in children.iter() {
+        if component == BloodComponent::WholeBlood {
+            return Err(Error::InvalidComponent);
+        }
+        total_ml = total_ml
+            .checked_add(quantity_ml)
+            .ok_or(Error::ArithmeticError)?;
+    }
+    if total_ml > parent.quantity {
+        return Err(Error::InvalidQuantity);
+    }
+
+    // Discard the parent first so it leaves the Available indexes before the
+    // children join them.
+    let old_status = parent.status;
+    parent.status = BloodStatus::Discarded;
+    put_unit(env, &parent);
+    record_status_change(
+        env,
+        parent_id,
+        old_status,
+        BloodStatus::Discarded,
+        actor.clone(),
+    );
+
+    let donor_id = if parent.donor_id == symbol_short!("ANON") {
+        None
+    } else {
+        Some(parent.donor_id.clone())
+    };
+    let parent_trail = HealthChainContract::get_custody_trail_metadata(env.clone(), parent_id);
+
+    let mut child_ids = Vec::new(env);
+    for (component, quantity_ml, expiration_timestamp) in children.iter() {
+        let child_id = register_unit(
+            env,
+            bank_id.clone(),
+            actor.clone(),
+            parent.blood_type,
+            component,
+            quantity_ml,
+            expiration_timestamp,
+            donor_id.clone(),
+        )?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::UnitParent(child_id), &parent_id);
+
+        for page_number in 0..parent_trail.total_pages {
+            let page = HealthChainContract::get_custody_trail(env.clone(), parent_id, page_number)?;
+            for event_id in page.iter() {
+                append_to_custody_trail(env, child_id, event_id);
+            }
+        }
+
+        child_ids.push_back(child_id);
+    }
+
+    env.storage()
         .persistent()
-        .get(&BLOOD_UNITS)
-        .unwrap_or(Map::new(env));
+        .set(&DataKey::UnitChildren(parent_id), &child_ids);
+
+    env.events().publish(
+        (symbol_short!("blood"), symbol_short!("split")),
+        (parent_id, child_ids.clone()),
+    );
 
-    let mut unit = units.get(unit_id).ok_or(Error::UnitNotFound)?;
+    Ok(child_ids)
+}
+
+/// Force mark a blood unit as expired.
+pub fn expire_unit(env: &Env, unit_id: u64) -> Result<(), Error> {
+    let mut unit = registry_read::get_unit(env, unit_id)?;
 
     let current_time = env.ledger().timestamp();
     if current_time < unit.expiration_date {
@@ -161,8 +440,7 @@ pub fn expire_unit(env: &Env, unit_id: u64) -> Result<(), Error> {
     let old_status = unit.status;
     unit.status = BloodStatus::Expired;
 
-    units.set(unit_id, unit);
-    env.storage().persistent().set(&BLOOD_UNITS, &units);
+    put_unit(env, &unit);
 
     // Record in history
     record_status_change(
@@ -192,3 +470,34 @@ pub fn check_and_expire_batch(env: &Env, unit_ids: Vec<u64>) -> Result<Vec<u64>,
 
     Ok(expired_ids)
 }
+
+/// Move up to `max_units` entries out of the legacy `BLOOD_UNITS` map into
+/// per-unit `DataKey::BloodUnit(id)` entries.
+///
+/// Migrated entries are removed from the legacy map; the map key itself is
+/// deleted once it is empty. An entry that already exists under the new key
+/// is never overwritten. Returns the number of units still left to migrate.
+pub fn migrate_legacy_units(env: &Env, max_units: u32) -> u32 {
+    let mut legacy: Map<u64, BloodUnit> = match env.storage().persistent().get(&BLOOD_UNITS) {
+        Some(units) => units,
+        None => return 0,
+    };
+
+    let batch = legacy.keys().slice(0..max_units.min(legacy.len()));
+    for unit_id in batch.iter() {
+        if let Some(unit) = legacy.get(unit_id) {
+            if !env.storage().persistent().has(&DataKey::BloodUnit(unit_id)) {
+                put_unit(env, &unit);
+            }
+        }
+        legacy.remove(unit_id);
+    }
+
+    if legacy.is_empty() {
+        env.storage().persistent().remove(&BLOOD_UNITS);
+    } else {
+        env.storage().persistent().set(&BLOOD_UNITS, &legacy);
+    }
+
+    legacy.len()
+}
@@ -0,0 +1,173 @@
+//! # request_queue
+//!
+//! Priority index of open blood requests backing `get_request_queue`.
+//!
+//! Every `Pending`, `Approved` or `InProgress` request has a
+//! [`RequestQueueEntry`] in the `Queue::Requests` bucket queue, ordered by
+//! urgency (Critical first), then `required_by`, then `created_at`, with the
+//! request ID as a final tie-break. Buckets hold one urgency level and one
+//! hour of deadlines each. Status changes call [`sync`] so requests leave the
+//! queue as soon as they are fulfilled, cancelled, rejected, disputed or
+//! expired.
+//!
+//! The same requests are also kept as `(required_by, request_id)` pairs in
+//! the `Queue::Deadlines` bucket queue, one bucket per deadline hour, so
+//! `sweep_expired_requests` reads only the overdue front through [`due`].
+
+use soroban_sdk::{contracttype, Env, Map, Vec};
+
+use crate::{
+    bucket_queue::{self, Queue},
+    compatibility,
+    constants::QUEUE_BUCKET_SECONDS,
+    registry_read::page_limit,
+    BloodRequest, BloodType, RequestStatus, UrgencyLevel, REQUESTS,
+};
+
+/// Position of an open request in the queue; ordering is field order
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RequestQueueEntry {
+    /// 0 for Critical up to 5 for Low
+    pub urgency_rank: u32,
+    pub required_by: u64,
+    pub created_at: u64,
+    pub request_id: u64,
+}
+
+impl RequestQueueEntry {
+    pub fn for_request(request: &BloodRequest) -> Self {
+        let urgency_rank = match request.urgency {
+            UrgencyLevel::Critical => 0,
+            UrgencyLevel::Urgent => 1,
+            UrgencyLevel::High => 2,
+            UrgencyLevel::Routine => 3,
+            UrgencyLevel::Medium => 4,
+            UrgencyLevel::Low => 5,
+        };
+        RequestQueueEntry {
+            urgency_rank,
+            required_by: request.required_by,
+            created_at: request.created_at,
+            request_id: request.id,
+        }
+    }
+}
+
+/// One page of the request queue, highest priority first
+#[contracttype]
+#[derive(Clone)]
+pub struct RequestQueuePage {
+    pub items: Vec<BloodRequest>,
+    /// Request ID to pass as `cursor` for the next page; `None` when exhausted
+    pub next_cursor: Option<u64>,
+}
+
+/// Whether a request in `status` can still be served
+pub(crate) fn is_open(status: RequestStatus) -> bool {
+    matches!(
+        status,
+        RequestStatus::Pending | RequestStatus::Approved | RequestStatus::InProgress
+    )
+}
+
+/// Last deadline hour with a bucket of its own
+const MAX_DEADLINE_HOUR: u64 = (1 << 40) - 1;
+
+/// Bucket of `entry` in the priority queue: the urgency rank in the high
+/// bits, then the `required_by` hour, so bucket order follows entry order.
+/// Deadlines past the 40-bit hour range share the last bucket of their rank.
+fn priority_bucket(entry: &RequestQueueEntry) -> u64 {
+    let hour = (entry.required_by / QUEUE_BUCKET_SECONDS).min(MAX_DEADLINE_HOUR);
+    (u64::from(entry.urgency_rank) << 40) | hour
+}
+
+/// Up to `limit` open request IDs whose `required_by` is at or before `now`,
+/// earliest deadline first
+pub fn due(env: &Env, now: u64, limit: u32) -> Vec<u64> {
+    let mut request_ids = Vec::new(env);
+    bucket_queue::scan(
+        env,
+        Queue::Deadlines,
+        None,
+        |(required_by, request_id): (u64, u64)| {
+            if required_by > now || request_ids.len() >= limit {
+                return false;
+            }
+            request_ids.push_back(request_id);
+            true
+        },
+    );
+    request_ids
+}
+
+/// Add or remove `request` from the priority and deadline queues according
+/// to its status
+pub fn sync(env: &Env, request: &BloodRequest) {
+    let entry = RequestQueueEntry::for_request(request);
+    let bucket = priority_bucket(&entry);
+    let deadline = (request.required_by, request.id);
+    let deadline_bucket = request.required_by / QUEUE_BUCKET_SECONDS;
+
+    if is_open(request.status) {
+        bucket_queue::insert(env, Queue::Requests, bucket, entry);
+        bucket_queue::insert(env, Queue::Deadlines, deadline_bucket, deadline);
+    } else {
+        bucket_queue::remove(env, Queue::Requests, bucket, &entry);
+        bucket_queue::remove(env, Queue::Deadlines, deadline_bucket, &deadline);
+    }
+}
+
+/// Page through open requests in priority order.
+///
+/// With `blood_type`, only requests whose recipients can receive that type
+/// are returned. `cursor` is the last request ID of the previous page.
+pub fn page(
+    env: &Env,
+    blood_type: Option<BloodType>,
+    cursor: Option<u64>,
+    limit: u32,
+) -> RequestQueuePage {
+    let requests: Map<u64, BloodRequest> = env
+        .storage()
+        .persistent()
+        .get(&REQUESTS)
+        .unwrap_or(Map::new(env));
+
+    // Resume after the cursor's position even if it has since left the queue
+    let after = match cursor.map(|id| requests.get(id)) {
+        Some(Some(last)) => {
+            let entry = RequestQueueEntry::for_request(&last);
+            Some((priority_bucket(&entry), entry))
+        }
+        Some(None) => {
+            return RequestQueuePage {
+                items: Vec::new(env),
+                next_cursor: None,
+            }
+        }
+        None => None,
+    };
+
+    let limit = page_limit(limit);
+    let mut items = Vec::new(env);
+    let mut next_cursor = None;
+    bucket_queue::scan(env, Queue::Requests, after, |entry: RequestQueueEntry| {
+        let request = match requests.get(entry.request_id) {
+            Some(request) => request,
+            None => return true,
+        };
+        if blood_type.is_some_and(|donor| !compatibility::can_donate_to(donor, request.blood_type))
+        {
+            return true;
+        }
+        if items.len() == limit {
+            next_cursor = items.last().map(|last: BloodRequest| last.id);
+            return false;
+        }
+        items.push_back(request);
+        true
+    });
+
+    RequestQueuePage { items, next_cursor }
+}
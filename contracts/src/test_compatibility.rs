@@ -0,0 +1,269 @@
+#![cfg(test)]
+
+use crate::compatibility::is_compatible;
+use crate::{
+    BloodComponent, BloodType, HealthChainContract, HealthChainContractClient,
+    RequestApprovedEvent, UrgencyLevel,
+};
+
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    vec, Address, Env, IntoVal, String,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup(env: &Env) -> (HealthChainContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let bank = Address::generate(env);
+    let hospital = Address::generate(env);
+    client.register_blood_bank(&bank);
+    client.register_hospital(&hospital);
+
+    (client, bank, hospital)
+}
+
+fn register_unit(
+    env: &Env,
+    client: &HealthChainContractClient<'_>,
+    bank: &Address,
+    blood_type: BloodType,
+    component: BloodComponent,
+    shelf_days: u64,
+) -> u64 {
+    client.register_blood(
+        bank,
+        &blood_type,
+        &component,
+        &300,
+        &(env.ledger().timestamp() + shelf_days * DAY),
+        &None,
+    )
+}
+
+fn request(
+    env: &Env,
+    client: &HealthChainContractClient<'_>,
+    hospital: &Address,
+    blood_type: BloodType,
+    quantity_ml: u32,
+) -> u64 {
+    client.create_request(
+        hospital,
+        &blood_type,
+        &quantity_ml,
+        &UrgencyLevel::Urgent,
+        &(env.ledger().timestamp() + DAY),
+        &String::from_str(env, "Ward D"),
+    )
+}
+
+// ======================================================
+// Compatibility-Aware Allocation Tests
+// ======================================================
+
+#[test]
+fn approve_flags_compatible_substitutes() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let exact = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::APositive,
+        BloodComponent::WholeBlood,
+        7,
+    );
+    let universal = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::ONegative,
+        BloodComponent::RedBloodCells,
+        7,
+    );
+    let request_id = request(&env, &client, &hospital, BloodType::APositive, 600);
+
+    client.approve_request(&bank, &request_id, &vec![&env, exact, universal]);
+
+    let event: RequestApprovedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(event.assigned_unit_ids, vec![&env, exact, universal]);
+    assert_eq!(event.substitute_unit_ids, vec![&env, universal]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn approve_rejects_incompatible_blood_type() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let unit_id = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::APositive,
+        BloodComponent::RedBloodCells,
+        7,
+    );
+    let request_id = request(&env, &client, &hospital, BloodType::ONegative, 300);
+
+    client.approve_request(&bank, &request_id, &vec![&env, unit_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn approve_rejects_unsuitable_component() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let unit_id = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::APositive,
+        BloodComponent::Plasma,
+        30,
+    );
+    let request_id = request(&env, &client, &hospital, BloodType::APositive, 300);
+
+    client.approve_request(&bank, &request_id, &vec![&env, unit_id]);
+}
+
+#[test]
+fn requested_component_selects_plasma_rules() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let ab_plasma = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::ABPositive,
+        BloodComponent::Plasma,
+        30,
+    );
+    let request_id = request(&env, &client, &hospital, BloodType::ONegative, 300);
+    assert_eq!(
+        client.get_request_component(&request_id),
+        BloodComponent::WholeBlood
+    );
+
+    client.set_request_component(&request_id, &BloodComponent::Plasma);
+    client.approve_request(&bank, &request_id, &vec![&env, ab_plasma]);
+
+    assert!(client
+        .try_set_request_component(&request_id, &BloodComponent::Platelets)
+        .is_err());
+}
+
+#[test]
+fn proposal_prefers_exact_matches_then_earliest_expiry() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let universal = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::ONegative,
+        BloodComponent::RedBloodCells,
+        2,
+    );
+    let exact_late = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::APositive,
+        BloodComponent::WholeBlood,
+        20,
+    );
+    let exact_soon = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::APositive,
+        BloodComponent::WholeBlood,
+        5,
+    );
+    register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::BPositive,
+        BloodComponent::WholeBlood,
+        1,
+    );
+    let other_bank = Address::generate(&env);
+    client.register_blood_bank(&other_bank);
+    register_unit(
+        &env,
+        &client,
+        &other_bank,
+        BloodType::APositive,
+        BloodComponent::WholeBlood,
+        1,
+    );
+    let request_id = request(&env, &client, &hospital, BloodType::APositive, 900);
+
+    let proposal = client.propose_units(&bank, &request_id);
+    assert_eq!(proposal, vec![&env, exact_soon, exact_late, universal]);
+
+    client.approve_request(&bank, &request_id, &proposal);
+}
+
+#[test]
+fn proposal_stops_once_request_is_covered() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let first = register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::OPositive,
+        BloodComponent::WholeBlood,
+        3,
+    );
+    register_unit(
+        &env,
+        &client,
+        &bank,
+        BloodType::OPositive,
+        BloodComponent::WholeBlood,
+        4,
+    );
+    let request_id = request(&env, &client, &hospital, BloodType::OPositive, 250);
+
+    assert_eq!(client.propose_units(&bank, &request_id), vec![&env, first]);
+}
+
+#[test]
+fn component_compatibility_rules() {
+    // Whole blood must be ABO-identical
+    assert!(!is_compatible(
+        BloodComponent::WholeBlood,
+        BloodType::ONegative,
+        BloodType::APositive
+    ));
+    assert!(is_compatible(
+        BloodComponent::WholeBlood,
+        BloodType::ANegative,
+        BloodType::APositive
+    ));
+    // AB plasma serves everyone; O plasma only serves O
+    assert!(is_compatible(
+        BloodComponent::Plasma,
+        BloodType::ABPositive,
+        BloodType::ONegative
+    ));
+    assert!(!is_compatible(
+        BloodComponent::Plasma,
+        BloodType::OPositive,
+        BloodType::APositive
+    ));
+    // Platelets keep the red-cell Rh rule
+    assert!(!is_compatible(
+        BloodComponent::Platelets,
+        BloodType::ABPositive,
+        BloodType::ANegative
+    ));
+}
@@ -0,0 +1,351 @@
+#![cfg(test)]
+
+use crate::constants::DISPUTE_RESPONSE_SECONDS;
+use crate::payments::{DisputeSplit, DisputeStatus, PaymentStatus, ReleaseConditions};
+use crate::{
+    BloodComponent, BloodRequest, BloodStatus, BloodType, HealthChainContract,
+    HealthChainContractClient, RequestStatus, UrgencyLevel, REQUESTS,
+};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Bytes, Env, Map, String,
+};
+
+struct Setup<'a> {
+    client: HealthChainContractClient<'a>,
+    token: TokenClient<'a>,
+    hospital: Address,
+    bank_a: Address,
+    bank_b: Address,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let hospital = Address::generate(env);
+    let bank_a = Address::generate(env);
+    let bank_b = Address::generate(env);
+    client.initialize(&admin);
+    client.register_hospital(&hospital);
+    client.register_blood_bank(&bank_a);
+    client.register_blood_bank(&bank_b);
+
+    let asset = env.register_stellar_asset_contract_v2(admin);
+    StellarAssetClient::new(env, &asset.address()).mint(&hospital, &10_000);
+
+    Setup {
+        client,
+        token: TokenClient::new(env, &asset.address()),
+        hospital,
+        bank_a,
+        bank_b,
+    }
+}
+
+fn register_unit(env: &Env, s: &Setup, bank: &Address) -> u64 {
+    s.client.register_blood(
+        bank,
+        &BloodType::OPositive,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    )
+}
+
+/// Critical 900 ml O+ request, enough for one unit from each bank
+fn trauma_request(env: &Env, s: &Setup) -> u64 {
+    s.client.create_request(
+        &s.hospital,
+        &BloodType::OPositive,
+        &900,
+        &UrgencyLevel::Critical,
+        &(env.ledger().timestamp() + 3_600),
+        &String::from_str(env, "Trauma bay 2"),
+    )
+}
+
+fn get_request(env: &Env, s: &Setup, request_id: u64) -> BloodRequest {
+    env.as_contract(&s.client.address, || {
+        let requests: Map<u64, BloodRequest> = env.storage().persistent().get(&REQUESTS).unwrap();
+        requests.get(request_id).unwrap()
+    })
+}
+
+fn pay(s: &Setup, request_id: u64, bank: &Address) -> u64 {
+    s.client.create_payment(
+        &request_id,
+        &s.hospital,
+        bank,
+        &1_000,
+        &s.token.address,
+        &ReleaseConditions {
+            medical_records_verified: true,
+            min_timestamp: 0,
+            authorized_approver: None,
+            require_proof_bundle: false,
+        },
+    )
+}
+
+// ======================================================
+// Multi-Bank Contribution Tests
+// ======================================================
+
+#[test]
+fn request_is_fulfilled_once_every_bank_delivers() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+    let unit_a = register_unit(&env, &s, &s.bank_a);
+    let unit_b = register_unit(&env, &s, &s.bank_b);
+
+    s.client
+        .approve_request(&s.bank_a, &request_id, &vec![&env, unit_a]);
+    let request = get_request(&env, &s, request_id);
+    assert_eq!(request.status, RequestStatus::InProgress);
+    assert_eq!(request.fulfilled_quantity_ml, 450);
+
+    s.client
+        .approve_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+    let request = get_request(&env, &s, request_id);
+    assert_eq!(request.status, RequestStatus::Approved);
+    assert_eq!(request.fulfilled_quantity_ml, 900);
+    assert_eq!(request.reserved_unit_ids, vec![&env, unit_a, unit_b]);
+
+    s.client
+        .fulfill_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+    assert_eq!(
+        get_request(&env, &s, request_id).status,
+        RequestStatus::Approved
+    );
+    assert_eq!(
+        s.client.get_blood_unit(&unit_b).status,
+        BloodStatus::Delivered
+    );
+
+    s.client
+        .fulfill_request(&s.bank_a, &request_id, &vec![&env, unit_a]);
+    let request = get_request(&env, &s, request_id);
+    assert_eq!(request.status, RequestStatus::Fulfilled);
+    assert_eq!(request.fulfilled_quantity_ml, 900);
+
+    let contributions = s.client.get_request_contributions(&request_id);
+    assert_eq!(contributions.len(), 2);
+    let first = contributions.get(0).unwrap();
+    assert_eq!(first.bank_id, s.bank_a);
+    assert_eq!(first.unit_ids, vec![&env, unit_a]);
+    assert_eq!(first.delivered_quantity_ml, 450);
+    assert!(contributions.iter().all(|c| c.delivered_at.is_some()));
+}
+
+#[test]
+fn each_bank_is_paid_on_its_own_delivery() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+    let unit_a = register_unit(&env, &s, &s.bank_a);
+    let unit_b = register_unit(&env, &s, &s.bank_b);
+    s.client
+        .approve_request(&s.bank_a, &request_id, &vec![&env, unit_a]);
+    s.client
+        .approve_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+
+    let payment_a = pay(&s, request_id, &s.bank_a);
+    let payment_b = pay(&s, request_id, &s.bank_b);
+
+    s.client
+        .fulfill_request(&s.bank_a, &request_id, &vec![&env, unit_a]);
+    assert_eq!(s.token.balance(&s.bank_a), 1_000);
+    assert_eq!(s.token.balance(&s.bank_b), 0);
+    assert_eq!(
+        s.client.get_payment(&payment_b).status,
+        PaymentStatus::Escrowed
+    );
+
+    s.client
+        .fulfill_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+    assert_eq!(s.token.balance(&s.bank_b), 1_000);
+    assert_eq!(
+        s.client.get_payment(&payment_a).status,
+        PaymentStatus::Completed
+    );
+    assert_eq!(
+        s.client.get_payment(&payment_b).status,
+        PaymentStatus::Completed
+    );
+}
+
+#[test]
+fn dispute_with_one_bank_leaves_shared_request_open() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+    let unit_a = register_unit(&env, &s, &s.bank_a);
+    let unit_b = register_unit(&env, &s, &s.bank_b);
+    s.client
+        .approve_request(&s.bank_a, &request_id, &vec![&env, unit_a]);
+    s.client
+        .approve_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+    let payment_a = pay(&s, request_id, &s.bank_a);
+    pay(&s, request_id, &s.bank_b);
+
+    let dispute_id = s.client.raise_dispute(
+        &payment_a,
+        &s.hospital,
+        &String::from_str(&env, "Units arrived warm"),
+        &Bytes::from_array(&env, &[1u8; 32]),
+        &vec![&env],
+    );
+    assert_eq!(
+        get_request(&env, &s, request_id).status,
+        RequestStatus::Approved
+    );
+
+    // Bank B's share is unaffected by the dispute over bank A's payment
+    s.client
+        .fulfill_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+    assert_eq!(s.token.balance(&s.bank_b), 1_000);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += DISPUTE_RESPONSE_SECONDS);
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::ResolvedInFavorOfPayer, &None);
+    assert_eq!(
+        get_request(&env, &s, request_id).status,
+        RequestStatus::Approved
+    );
+    assert_eq!(s.token.balance(&s.hospital), 9_000);
+}
+
+#[test]
+fn pro_rata_split_ignores_other_banks_deliveries() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+    let unit_a = register_unit(&env, &s, &s.bank_a);
+    let unit_b = register_unit(&env, &s, &s.bank_b);
+    s.client
+        .approve_request(&s.bank_a, &request_id, &vec![&env, unit_a]);
+    s.client
+        .approve_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+    let payment_a = pay(&s, request_id, &s.bank_a);
+    pay(&s, request_id, &s.bank_b);
+    s.client
+        .fulfill_request(&s.bank_b, &request_id, &vec![&env, unit_b]);
+
+    let dispute_id = s.client.raise_dispute(
+        &payment_a,
+        &s.hospital,
+        &String::from_str(&env, "Units never arrived"),
+        &Bytes::from_array(&env, &[1u8; 32]),
+        &vec![&env],
+    );
+
+    // Bank B's delivery earns bank A nothing
+    assert_eq!(
+        s.client.suggest_dispute_split(&dispute_id),
+        DisputeSplit::BasisPoints(0)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #34)")]
+fn one_payment_per_bank_per_request() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+
+    pay(&s, request_id, &s.bank_a);
+    pay(&s, request_id, &s.bank_a);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn bank_must_deliver_the_units_it_committed() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+    let first = register_unit(&env, &s, &s.bank_a);
+    let second = register_unit(&env, &s, &s.bank_a);
+    s.client
+        .approve_request(&s.bank_a, &request_id, &vec![&env, first, second]);
+
+    s.client
+        .fulfill_request(&s.bank_a, &request_id, &vec![&env, first]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn bank_cannot_commit_another_banks_units() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+    let unit_a = register_unit(&env, &s, &s.bank_a);
+
+    s.client
+        .approve_request(&s.bank_b, &request_id, &vec![&env, unit_a]);
+}
+
+#[test]
+fn commitment_must_list_distinct_units() {
+    let env = Env::default();
+    let s = setup(&env);
+    let request_id = trauma_request(&env, &s);
+    let unit_a = register_unit(&env, &s, &s.bank_a);
+
+    assert!(s
+        .client
+        .try_approve_request(&s.bank_a, &request_id, &vec![&env, unit_a, unit_a])
+        .is_err());
+    assert!(s
+        .client
+        .try_approve_request(&s.bank_a, &request_id, &vec![&env])
+        .is_err());
+    assert!(s.client.get_request_contributions(&request_id).is_empty());
+    assert_eq!(
+        get_request(&env, &s, request_id).status,
+        RequestStatus::Pending
+    );
+}
+
+/// Request approved through `update_request_status`, with no contributions
+fn uncommitted_request(env: &Env, s: &Setup) -> u64 {
+    let request_id = trauma_request(env, s);
+    s.client
+        .update_request_status(&request_id, &RequestStatus::Approved);
+    request_id
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn uncommitted_delivery_requires_own_units() {
+    let env = Env::default();
+    let s = setup(&env);
+    let unit_b = register_unit(&env, &s, &s.bank_b);
+    s.client.allocate_blood(&s.bank_b, &unit_b, &s.hospital);
+    let request_id = uncommitted_request(&env, &s);
+
+    s.client
+        .fulfill_request(&s.bank_a, &request_id, &vec![&env, unit_b]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn uncommitted_delivery_rejects_delivered_units() {
+    let env = Env::default();
+    let s = setup(&env);
+    let unit_a = register_unit(&env, &s, &s.bank_a);
+    s.client.allocate_blood(&s.bank_a, &unit_a, &s.hospital);
+    let event_id = s.client.initiate_transfer(&s.bank_a, &unit_a);
+    s.client.confirm_transfer(&s.hospital, &event_id);
+    let request_id = uncommitted_request(&env, &s);
+
+    s.client
+        .fulfill_request(&s.bank_a, &request_id, &vec![&env, unit_a]);
+}
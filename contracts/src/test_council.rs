@@ -0,0 +1,200 @@
+#![cfg(test)]
+
+use crate::council::CouncilAction;
+use crate::crossmatch::CrossmatchPolicy;
+use crate::institutions::SuspensionPolicy;
+use crate::payments::{DisputeStatus, FeeStructure};
+use crate::{
+    BloodComponent, ComponentPolicy, Error, HealthChainContract, HealthChainContractClient,
+    StaleTransferPolicy, StorageTempClass,
+};
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Vec,
+};
+
+fn setup(env: &Env) -> (HealthChainContractClient<'_>, Vec<Address>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let members = vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    client.set_admin_council(&members, &2, &86_400);
+
+    (client, members)
+}
+
+// ======================================================
+// Admin Council Tests
+// ======================================================
+
+#[test]
+fn proposal_executes_at_threshold() {
+    let env = Env::default();
+    let (client, members) = setup(&env);
+    let hospital = Address::generate(&env);
+
+    let proposal_id = client.propose_action(
+        &members.get(0).unwrap(),
+        &CouncilAction::RegisterHospital(hospital.clone()),
+    );
+    assert!(!client.is_hospital(&hospital));
+    assert!(!client.get_proposal(&proposal_id).executed);
+
+    assert!(client.approve_proposal(&members.get(2).unwrap(), &proposal_id));
+    assert!(client.is_hospital(&hospital));
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert!(proposal.executed);
+    assert_eq!(proposal.approvals.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn admin_key_cannot_register_while_council_governs() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+
+    client.register_blood_bank(&Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn expired_proposal_cannot_be_approved() {
+    let env = Env::default();
+    let (client, members) = setup(&env);
+
+    let proposal_id = client.propose_action(
+        &members.get(0).unwrap(),
+        &CouncilAction::RegisterBloodBank(Address::generate(&env)),
+    );
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.approve_proposal(&members.get(1).unwrap(), &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")]
+fn member_cannot_approve_twice() {
+    let env = Env::default();
+    let (client, members) = setup(&env);
+    let proposer = members.get(0).unwrap();
+
+    let proposal_id = client.propose_action(
+        &proposer,
+        &CouncilAction::RegisterBloodBank(Address::generate(&env)),
+    );
+    client.approve_proposal(&proposer, &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn outsider_cannot_propose() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+
+    client.propose_action(
+        &Address::generate(&env),
+        &CouncilAction::RegisterBloodBank(Address::generate(&env)),
+    );
+}
+
+#[test]
+fn council_can_dissolve_itself() {
+    let env = Env::default();
+    let (client, members) = setup(&env);
+
+    let proposal_id = client.propose_action(
+        &members.get(0).unwrap(),
+        &CouncilAction::SetCouncil(vec![&env], 0, 0),
+    );
+    client.approve_proposal(&members.get(1).unwrap(), &proposal_id);
+
+    assert!(client.get_admin_council().is_none());
+    let bank = Address::generate(&env);
+    client.register_blood_bank(&bank);
+    assert!(client.is_blood_bank(&bank));
+}
+
+#[test]
+fn admin_key_cannot_change_policies_while_council_governs() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let denied = Err(Ok(Error::CouncilApprovalRequired));
+    let someone = Address::generate(&env);
+
+    let fees = FeeStructure {
+        policy_id: symbol_short!("std"),
+        service_fee: 0,
+        network_fee: 0,
+        performance_bonus: 0,
+        fixed_fee: 0,
+    };
+    assert_eq!(client.try_set_fee_policy(&fees, &someone), denied);
+    assert_eq!(
+        client.try_set_arbitration_panel(
+            &vec![&env, someone.clone()],
+            &1,
+            &86_400,
+            &DisputeStatus::ResolvedInFavorOfPayer,
+        ),
+        denied
+    );
+    assert_eq!(client.try_set_dispute_response_window(&3_600), denied);
+    assert_eq!(
+        client.try_set_crossmatch_policy(&CrossmatchPolicy::default()),
+        denied
+    );
+    assert_eq!(
+        client.try_set_suspension_policy(&SuspensionPolicy::AllowInFlight),
+        denied
+    );
+    let policy = ComponentPolicy {
+        min_shelf_life_days: 1,
+        max_shelf_life_days: 5,
+        min_volume_ml: 100,
+        max_volume_ml: 300,
+        storage_temp: StorageTempClass::RoomTemperature,
+    };
+    assert_eq!(
+        client.try_set_component_policy(&BloodComponent::Platelets, &policy),
+        denied
+    );
+    assert_eq!(
+        client.try_set_stale_transfer_policy(&StaleTransferPolicy::Quarantine),
+        denied
+    );
+    assert_eq!(client.try_set_quarantine_review_window(&3_600), denied);
+    assert_eq!(client.try_register_courier(&someone), denied);
+    assert_eq!(client.try_nominate_super_admin(&someone), denied);
+}
+
+#[test]
+fn council_changes_policies_by_proposal() {
+    let env = Env::default();
+    let (client, members) = setup(&env);
+    let courier = Address::generate(&env);
+
+    for action in [
+        CouncilAction::SetStaleTransferPolicy(StaleTransferPolicy::Quarantine),
+        CouncilAction::SetQuarantineReviewWindow(3_600),
+        CouncilAction::RegisterCourier(courier.clone()),
+    ] {
+        let proposal_id = client.propose_action(&members.get(0).unwrap(), &action);
+        assert!(client.approve_proposal(&members.get(1).unwrap(), &proposal_id));
+    }
+
+    assert_eq!(
+        client.get_stale_transfer_policy(),
+        StaleTransferPolicy::Quarantine
+    );
+    assert_eq!(client.get_quarantine_review_window(), 3_600);
+    assert!(client.is_courier(&courier));
+}
@@ -0,0 +1,228 @@
+#![cfg(test)]
+
+use crate::crossmatch::{CrossmatchPolicy, CrossmatchResult};
+use crate::delegates::DelegatePermission;
+use crate::{
+    BloodComponent, BloodRequest, BloodStatus, BloodType, HealthChainContract,
+    HealthChainContractClient, RequestStatus, UrgencyLevel, REQUESTS,
+};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, Map, String,
+};
+
+struct Setup<'a> {
+    client: HealthChainContractClient<'a>,
+    bank: Address,
+    hospital: Address,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let bank = Address::generate(env);
+    let hospital = Address::generate(env);
+    client.register_blood_bank(&bank);
+    client.register_hospital(&hospital);
+
+    Setup {
+        client,
+        bank,
+        hospital,
+    }
+}
+
+/// Request for 450 ml B+ approved with one reserved unit; returns
+/// (request_id, unit_id)
+fn approved_request(env: &Env, s: &Setup, urgency: UrgencyLevel) -> (u64, u64) {
+    let unit_id = s.client.register_blood(
+        &s.bank,
+        &BloodType::BPositive,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    );
+    let request_id = s.client.create_request(
+        &s.hospital,
+        &BloodType::BPositive,
+        &450,
+        &urgency,
+        &(env.ledger().timestamp() + 3_600),
+        &String::from_str(env, "Theatre 3"),
+    );
+    s.client
+        .approve_request(&s.bank, &request_id, &vec![env, unit_id]);
+    (request_id, unit_id)
+}
+
+fn attest(env: &Env, s: &Setup, request_id: u64, unit_id: u64, result: CrossmatchResult) {
+    s.client.attest_crossmatch(
+        &s.hospital,
+        &request_id,
+        &unit_id,
+        &result,
+        &(env.ledger().timestamp() - 600),
+        &BytesN::from_array(env, &[7u8; 32]),
+    );
+}
+
+fn get_request(env: &Env, s: &Setup, request_id: u64) -> BloodRequest {
+    env.as_contract(&s.client.address, || {
+        let requests: Map<u64, BloodRequest> = env.storage().persistent().get(&REQUESTS).unwrap();
+        requests.get(request_id).unwrap()
+    })
+}
+
+fn require_everywhere(s: &Setup) {
+    s.client.set_crossmatch_policy(&CrossmatchPolicy {
+        require_on_transfer: true,
+        require_on_fulfillment: true,
+    });
+}
+
+// ======================================================
+// Crossmatch Attestation Tests
+// ======================================================
+
+#[test]
+fn incompatible_crossmatch_returns_unit_to_stock() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (request_id, unit_id) = approved_request(&env, &s, UrgencyLevel::Critical);
+    assert_eq!(
+        get_request(&env, &s, request_id).status,
+        RequestStatus::Approved
+    );
+
+    attest(
+        &env,
+        &s,
+        request_id,
+        unit_id,
+        CrossmatchResult::Incompatible,
+    );
+
+    let unit = s.client.get_blood_unit(&unit_id);
+    assert_eq!(unit.status, BloodStatus::Available);
+    assert_eq!(unit.recipient_hospital, None);
+
+    let request = get_request(&env, &s, request_id);
+    assert_eq!(request.status, RequestStatus::InProgress);
+    assert_eq!(request.fulfilled_quantity_ml, 0);
+    assert!(request.reserved_unit_ids.is_empty());
+    assert!(s.client.get_request_contributions(&request_id).is_empty());
+
+    let attestation = s.client.get_crossmatch(&request_id, &unit_id).unwrap();
+    assert_eq!(attestation.result, CrossmatchResult::Incompatible);
+    assert_eq!(attestation.lab, s.hospital);
+    assert_eq!(attestation.tested_at, 9_400);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #50)")]
+fn fulfillment_requires_compatible_crossmatch_when_configured() {
+    let env = Env::default();
+    let s = setup(&env);
+    require_everywhere(&s);
+    let (request_id, unit_id) = approved_request(&env, &s, UrgencyLevel::Urgent);
+
+    s.client
+        .fulfill_request(&s.bank, &request_id, &vec![&env, unit_id]);
+}
+
+#[test]
+fn compatible_crossmatch_allows_fulfillment() {
+    let env = Env::default();
+    let s = setup(&env);
+    require_everywhere(&s);
+    let (request_id, unit_id) = approved_request(&env, &s, UrgencyLevel::Critical);
+
+    attest(&env, &s, request_id, unit_id, CrossmatchResult::Compatible);
+    s.client
+        .fulfill_request(&s.bank, &request_id, &vec![&env, unit_id]);
+
+    assert_eq!(
+        get_request(&env, &s, request_id).status,
+        RequestStatus::Fulfilled
+    );
+}
+
+#[test]
+fn lower_urgency_requests_skip_crossmatch() {
+    let env = Env::default();
+    let s = setup(&env);
+    require_everywhere(&s);
+    let (request_id, unit_id) = approved_request(&env, &s, UrgencyLevel::High);
+
+    s.client
+        .fulfill_request(&s.bank, &request_id, &vec![&env, unit_id]);
+
+    assert_eq!(
+        s.client.get_blood_unit(&unit_id).status,
+        BloodStatus::Delivered
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #50)")]
+fn transfer_confirmation_requires_crossmatch_when_configured() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.set_crossmatch_policy(&CrossmatchPolicy {
+        require_on_transfer: true,
+        require_on_fulfillment: false,
+    });
+    let (_, unit_id) = approved_request(&env, &s, UrgencyLevel::Critical);
+
+    let event_id = s.client.initiate_transfer(&s.bank, &unit_id);
+    s.client.confirm_transfer(&s.hospital, &event_id);
+}
+
+#[test]
+fn lab_delegate_attests_for_its_hospital() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (request_id, unit_id) = approved_request(&env, &s, UrgencyLevel::Critical);
+
+    let lab = Address::generate(&env);
+    s.client.add_delegate(
+        &s.hospital,
+        &lab,
+        &vec![&env, DelegatePermission::AttestCrossmatch],
+        &None,
+    );
+    s.client.attest_crossmatch(
+        &lab,
+        &request_id,
+        &unit_id,
+        &CrossmatchResult::Compatible,
+        &env.ledger().timestamp(),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert_eq!(
+        s.client.get_crossmatch(&request_id, &unit_id).unwrap().lab,
+        lab
+    );
+
+    let other_hospital = Address::generate(&env);
+    s.client.register_hospital(&other_hospital);
+    let result = s.client.try_attest_crossmatch(
+        &other_hospital,
+        &request_id,
+        &unit_id,
+        &CrossmatchResult::Incompatible,
+        &env.ledger().timestamp(),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        s.client.get_blood_unit(&unit_id).status,
+        BloodStatus::Reserved
+    );
+}
@@ -0,0 +1,220 @@
+#![cfg(test)]
+
+use crate::delegates::DelegatePermission;
+use crate::{
+    BloodComponent, BloodStatus, BloodType, Error, HealthChainContract, HealthChainContractClient,
+};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+fn setup(env: &Env) -> (HealthChainContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let bank = Address::generate(env);
+    let hospital = Address::generate(env);
+    client.register_blood_bank(&bank);
+    client.register_hospital(&hospital);
+
+    (client, bank, hospital)
+}
+
+fn register_unit(env: &Env, client: &HealthChainContractClient<'_>, caller: &Address) -> u64 {
+    client.register_blood(
+        caller,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    )
+}
+
+// ======================================================
+// Delegate Tests
+// ======================================================
+
+#[test]
+fn delegate_registers_and_allocates_for_bank() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let tech = Address::generate(&env);
+    client.add_delegate(
+        &bank,
+        &tech,
+        &vec![
+            &env,
+            DelegatePermission::RegisterUnits,
+            DelegatePermission::Allocate,
+        ],
+        &None,
+    );
+
+    let unit_id = register_unit(&env, &client, &tech);
+    client.allocate_blood(&tech, &unit_id, &hospital);
+
+    let unit = client.get_blood_unit(&unit_id);
+    assert_eq!(unit.bank_id, bank);
+    assert_eq!(unit.status, BloodStatus::Reserved);
+
+    let history = client.get_transfer_history(&unit_id, &None, &10).items;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().actor, tech);
+}
+
+#[test]
+fn hospital_delegate_confirms_delivery() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let nurse = Address::generate(&env);
+    client.add_delegate(
+        &hospital,
+        &nurse,
+        &vec![&env, DelegatePermission::ConfirmDelivery],
+        &None,
+    );
+
+    let unit_id = register_unit(&env, &client, &bank);
+    client.allocate_blood(&bank, &unit_id, &hospital);
+    let event_id = client.initiate_transfer(&bank, &unit_id);
+    client.confirm_transfer(&nurse, &event_id);
+
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Delivered
+    );
+    let history = client.get_transfer_history(&unit_id, &None, &10).items;
+    assert_eq!(history.last().unwrap().actor, nurse);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn delegate_without_permission_is_rejected() {
+    let env = Env::default();
+    let (client, bank, _) = setup(&env);
+    let tech = Address::generate(&env);
+    client.add_delegate(
+        &bank,
+        &tech,
+        &vec![&env, DelegatePermission::Allocate],
+        &None,
+    );
+
+    register_unit(&env, &client, &tech);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn expired_delegate_is_rejected() {
+    let env = Env::default();
+    let (client, bank, _) = setup(&env);
+    let tech = Address::generate(&env);
+    let expires_at = env.ledger().timestamp() + 3_600;
+    client.add_delegate(
+        &bank,
+        &tech,
+        &vec![&env, DelegatePermission::RegisterUnits],
+        &Some(expires_at),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = expires_at);
+    register_unit(&env, &client, &tech);
+}
+
+#[test]
+fn revoked_delegate_loses_access() {
+    let env = Env::default();
+    let (client, bank, _) = setup(&env);
+    let tech = Address::generate(&env);
+    client.add_delegate(
+        &bank,
+        &tech,
+        &vec![&env, DelegatePermission::RegisterUnits],
+        &None,
+    );
+    assert_eq!(client.get_delegates(&bank), vec![&env, tech.clone()]);
+
+    client.revoke_delegate(&bank, &tech);
+
+    assert!(client.get_delegate(&tech).is_none());
+    assert!(client.get_delegates(&bank).is_empty());
+    let result = client.try_register_blood(
+        &tech,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn delegate_cannot_serve_two_institutions() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let staff = Address::generate(&env);
+    client.add_delegate(
+        &bank,
+        &staff,
+        &vec![&env, DelegatePermission::Allocate],
+        &None,
+    );
+
+    client.add_delegate(
+        &hospital,
+        &staff,
+        &vec![&env, DelegatePermission::ConfirmDelivery],
+        &None,
+    );
+}
+
+#[test]
+fn registered_institution_cannot_be_a_delegate() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+
+    let result = client.try_add_delegate(
+        &bank,
+        &hospital,
+        &vec![&env, DelegatePermission::Allocate],
+        &None,
+    );
+
+    assert_eq!(result, Err(Ok(Error::DuplicateRegistration)));
+    assert!(client.get_delegate(&hospital).is_none());
+}
+
+#[test]
+fn registering_a_delegate_revokes_its_delegation() {
+    let env = Env::default();
+    let (client, bank, _) = setup(&env);
+    let staff = Address::generate(&env);
+    client.add_delegate(
+        &bank,
+        &staff,
+        &vec![&env, DelegatePermission::RegisterUnits],
+        &None,
+    );
+
+    client.register_hospital(&staff);
+
+    assert!(client.get_delegate(&staff).is_none());
+    assert!(client.get_delegates(&bank).is_empty());
+    // The new hospital no longer registers units into the bank's inventory
+    let result = client.try_register_blood(
+        &staff,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,1159 @@
+#![cfg(test)]
+
+use crate::constants::DISPUTE_RESPONSE_SECONDS;
+use crate::delegates::DelegatePermission;
+use crate::payments::{
+    DisputeSplit, DisputeStatus, FeeStructure, PaymentStatus, ProofBundle, ReleaseConditions,
+};
+use crate::{
+    BloodComponent, BloodStatus, BloodType, Error, HealthChainContract, HealthChainContractClient,
+    RequestStatus, StatusChangeEvent, UrgencyLevel, HISTORY,
+};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Bytes, Env, String, Symbol, Vec,
+};
+
+struct Setup<'a> {
+    client: HealthChainContractClient<'a>,
+    token: TokenClient<'a>,
+    hospital: Address,
+    bank: Address,
+    /// Open request from `hospital` that payments can be made against
+    request_id: u64,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let hospital = Address::generate(env);
+    let bank = Address::generate(env);
+    client.initialize(&admin);
+    client.register_hospital(&hospital);
+    client.register_blood_bank(&bank);
+
+    let asset = env.register_stellar_asset_contract_v2(admin);
+    StellarAssetClient::new(env, &asset.address()).mint(&hospital, &10_000);
+
+    let request_id = client.create_request(
+        &hospital,
+        &BloodType::ONegative,
+        &900,
+        &UrgencyLevel::Routine,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(env, "Ward A"),
+    );
+
+    Setup {
+        client,
+        token: TokenClient::new(env, &asset.address()),
+        hospital,
+        bank,
+        request_id,
+    }
+}
+
+fn fees(env: &Env, service_fee: i128) -> FeeStructure {
+    FeeStructure {
+        policy_id: Symbol::new(env, "standard"),
+        service_fee,
+        network_fee: 0,
+        performance_bonus: 0,
+        fixed_fee: 0,
+    }
+}
+
+fn open_conditions() -> ReleaseConditions {
+    ReleaseConditions {
+        medical_records_verified: true,
+        min_timestamp: 0,
+        authorized_approver: None,
+        require_proof_bundle: false,
+    }
+}
+
+fn proof_bundle(env: &Env, submitted_by: &Address, hash_len: u32) -> ProofBundle {
+    let hash = Bytes::from_slice(env, &[9u8; 32][..hash_len as usize]);
+    ProofBundle {
+        delivery_hash: hash.clone(),
+        signature_hash: hash.clone(),
+        photo_hash: hash.clone(),
+        medical_hash: hash,
+        submitted_by: submitted_by.clone(),
+        submitted_at: 0,
+        validated: false,
+    }
+}
+
+/// Approved request for one reserved 450 ml A+ unit; returns (request_id, unit_id)
+fn approved_request(env: &Env, s: &Setup) -> (u64, u64) {
+    approved_request_for(env, s, 450)
+}
+
+fn approved_request_for(env: &Env, s: &Setup, quantity_ml: u32) -> (u64, u64) {
+    let now = env.ledger().timestamp();
+    let unit_id = s.client.register_blood(
+        &s.bank,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(now + 7 * 86400),
+        &None,
+    );
+    s.client.allocate_blood(&s.bank, &unit_id, &s.hospital);
+    let request_id = s.client.create_request(
+        &s.hospital,
+        &BloodType::APositive,
+        &quantity_ml,
+        &UrgencyLevel::Urgent,
+        &(now + 3_600),
+        &String::from_str(env, "Ward B"),
+    );
+    s.client
+        .update_request_status(&request_id, &RequestStatus::Approved);
+    (request_id, unit_id)
+}
+
+/// Hospital raises a dispute and the bank answers it, closing evidence
+fn raise_dispute(env: &Env, s: &Setup, payment_id: u64) -> u64 {
+    let dispute_id = raise_unanswered_dispute(env, s, payment_id);
+    s.client.submit_dispute_evidence(
+        &dispute_id,
+        &s.bank,
+        &Bytes::from_array(env, &[2u8; 32]),
+        &vec![env],
+    );
+    dispute_id
+}
+
+fn raise_unanswered_dispute(env: &Env, s: &Setup, payment_id: u64) -> u64 {
+    s.client.raise_dispute(
+        &payment_id,
+        &s.hospital,
+        &String::from_str(env, "Units arrived warm"),
+        &Bytes::from_array(env, &[1u8; 32]),
+        &vec![env],
+    )
+}
+
+// ======================================================
+// Escrow Lock Tests
+// ======================================================
+
+#[test]
+fn create_payment_moves_funds_into_escrow() {
+    let env = Env::default();
+    let s = setup(&env);
+    let treasury = Address::generate(&env);
+    s.client.set_fee_policy(&fees(&env, 50), &treasury);
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+
+    let payment = s.client.get_payment(&payment_id);
+    assert_eq!(payment.status, PaymentStatus::Escrowed);
+    assert_eq!(payment.amount, 950);
+    assert_eq!(s.client.get_escrow(&payment_id).locked_amount, 1_000);
+    assert_eq!(s.token.balance(&s.hospital), 9_000);
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #35)")]
+fn create_payment_rejects_fees_above_amount() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client
+        .set_fee_policy(&fees(&env, 2_000), &Address::generate(&env));
+
+    s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #34)")]
+fn create_payment_rejects_second_payment_for_request() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+}
+
+#[test]
+fn create_payment_checks_request_parties_and_amount() {
+    let env = Env::default();
+    let s = setup(&env);
+    let pay = |request_id: u64, payer: &Address, payee: &Address, amount: i128| {
+        s.client.try_create_payment(
+            &request_id,
+            payer,
+            payee,
+            &amount,
+            &s.token.address,
+            &open_conditions(),
+        )
+    };
+
+    assert_eq!(
+        pay(99, &s.hospital, &s.bank, 1_000),
+        Err(Ok(Error::UnitNotFound))
+    );
+    assert_eq!(
+        pay(s.request_id, &s.hospital, &s.bank, 0),
+        Err(Ok(Error::InvalidQuantity))
+    );
+
+    let other_hospital = Address::generate(&env);
+    s.client.register_hospital(&other_hospital);
+    assert_eq!(
+        pay(s.request_id, &other_hospital, &s.bank, 1_000),
+        Err(Ok(Error::UnauthorizedHospital))
+    );
+    assert_eq!(
+        pay(s.request_id, &s.hospital, &Address::generate(&env), 1_000),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    s.client
+        .cancel_request(&s.request_id, &String::from_str(&env, "No longer needed"));
+    assert_eq!(
+        pay(s.request_id, &s.hospital, &s.bank, 1_000),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(s.token.balance(&s.hospital), 10_000);
+}
+
+// ======================================================
+// Escrow Release Tests
+// ======================================================
+
+#[test]
+fn dispute_for_payee_pays_net_and_fees() {
+    let env = Env::default();
+    let s = setup(&env);
+    let treasury = Address::generate(&env);
+    s.client.set_fee_policy(&fees(&env, 50), &treasury);
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    let dispute_id = raise_dispute(&env, &s, payment_id);
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::ResolvedInFavorOfPayee, &None);
+
+    assert_eq!(s.token.balance(&s.bank), 950);
+    assert_eq!(s.token.balance(&treasury), 50);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Completed
+    );
+}
+
+#[test]
+fn dispute_for_payer_refunds_everything() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client
+        .set_fee_policy(&fees(&env, 50), &Address::generate(&env));
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    let dispute_id = raise_dispute(&env, &s, payment_id);
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::ResolvedInFavorOfPayer, &None);
+
+    assert_eq!(s.token.balance(&s.hospital), 10_000);
+    assert_eq!(s.client.get_escrow(&payment_id).locked_amount, 0);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Refunded
+    );
+}
+
+#[test]
+fn dismissed_dispute_returns_payment_to_escrow() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    let (payment_id, dispute_id) = disputed_payment(&env, &s, s.request_id);
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::Dismissed, &None);
+
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Escrowed
+    );
+    let queue = s.client.get_request_queue(&None, &None, &10);
+    assert_eq!(queue.items.get(0).unwrap().status, RequestStatus::Pending);
+
+    s.client.release_escrow(&payment_id, &s.hospital);
+    assert_eq!(s.token.balance(&s.bank), 1_000);
+    assert_eq!(s.client.get_escrow(&payment_id).locked_amount, 0);
+}
+
+#[test]
+fn fulfill_request_pays_out_escrow() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (request_id, unit_id) = approved_request(&env, &s);
+
+    let payment_id = s.client.create_payment(
+        &request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    s.client
+        .fulfill_request(&s.bank, &request_id, &vec![&env, unit_id]);
+
+    assert_eq!(s.token.balance(&s.bank), 1_000);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Completed
+    );
+}
+
+#[test]
+fn expired_request_refunds_escrowed_payment() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client
+        .set_fee_policy(&fees(&env, 50), &Address::generate(&env));
+    let (request_id, _) = approved_request(&env, &s);
+
+    let payment_id = s.client.create_payment(
+        &request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    s.client.sweep_expired_requests(&10);
+
+    assert_eq!(s.token.balance(&s.hospital), 10_000);
+    assert_eq!(s.client.get_escrow(&payment_id).locked_amount, 0);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Refunded
+    );
+}
+
+#[test]
+fn cancelled_request_refunds_escrowed_payment() {
+    let env = Env::default();
+    let s = setup(&env);
+    let unit_id = s.client.register_blood(
+        &s.bank,
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    );
+    s.client
+        .approve_request(&s.bank, &s.request_id, &vec![&env, unit_id]);
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+
+    s.client
+        .cancel_request(&s.request_id, &String::from_str(&env, "Patient discharged"));
+
+    assert_eq!(s.token.balance(&s.hospital), 10_000);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Refunded
+    );
+    let history: Vec<StatusChangeEvent> = env.as_contract(&s.client.address, || {
+        env.storage().persistent().get(&(HISTORY, unit_id)).unwrap()
+    });
+    let last = history.last().unwrap();
+    assert_eq!(last.old_status, BloodStatus::Reserved);
+    assert_eq!(last.new_status, BloodStatus::Available);
+}
+
+// ======================================================
+// Release Condition Tests
+// ======================================================
+
+fn strict_conditions(approver: &Address, min_timestamp: u64) -> ReleaseConditions {
+    ReleaseConditions {
+        medical_records_verified: false,
+        min_timestamp,
+        authorized_approver: Some(approver.clone()),
+        require_proof_bundle: true,
+    }
+}
+
+#[test]
+fn release_escrow_after_valid_proof_bundle() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 0),
+    );
+    s.client
+        .attach_proof_bundle(&payment_id, &proof_bundle(&env, &s.hospital, 32));
+
+    let bundle = s.client.get_proof_bundle(&payment_id).unwrap();
+    assert!(bundle.validated);
+    assert!(
+        s.client
+            .get_escrow(&payment_id)
+            .release_conditions
+            .medical_records_verified
+    );
+
+    s.client.release_escrow(&payment_id, &approver);
+    assert_eq!(s.token.balance(&s.bank), 1_000);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Completed
+    );
+}
+
+#[test]
+fn payee_cannot_attest_its_own_delivery() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 0),
+    );
+    assert!(s
+        .client
+        .try_attach_proof_bundle(&payment_id, &proof_bundle(&env, &s.bank, 32))
+        .is_err());
+
+    assert!(s.client.get_proof_bundle(&payment_id).is_none());
+    assert!(s.client.try_release_escrow(&payment_id, &approver).is_err());
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Escrowed
+    );
+    assert_eq!(s.token.balance(&s.bank), 0);
+}
+
+#[test]
+fn approver_and_payer_verifier_may_attest() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    s.client.add_delegate(
+        &s.hospital,
+        &verifier,
+        &vec![&env, DelegatePermission::ConfirmDelivery],
+        &None,
+    );
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 0),
+    );
+    s.client
+        .attach_proof_bundle(&payment_id, &proof_bundle(&env, &verifier, 32));
+    assert_eq!(
+        s.client.get_proof_bundle(&payment_id).unwrap().submitted_by,
+        verifier
+    );
+    s.client
+        .attach_proof_bundle(&payment_id, &proof_bundle(&env, &approver, 32));
+
+    s.client.release_escrow(&payment_id, &approver);
+    assert_eq!(s.token.balance(&s.bank), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #36)")]
+fn release_escrow_before_min_timestamp_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 5_000),
+    );
+    s.client.release_escrow(&payment_id, &approver);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #37)")]
+fn release_escrow_without_medical_verification_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 0),
+    );
+    s.client.release_escrow(&payment_id, &approver);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #38)")]
+fn release_escrow_by_wrong_approver_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 0),
+    );
+    s.client
+        .attach_proof_bundle(&payment_id, &proof_bundle(&env, &s.hospital, 32));
+    s.client.release_escrow(&payment_id, &s.bank);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #39)")]
+fn release_escrow_without_proof_bundle_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+
+    let mut conditions = strict_conditions(&approver, 0);
+    conditions.medical_records_verified = true;
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &conditions,
+    );
+    s.client.release_escrow(&payment_id, &approver);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #40)")]
+fn release_escrow_with_truncated_hashes_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+
+    let mut conditions = strict_conditions(&approver, 0);
+    conditions.medical_records_verified = true;
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &conditions,
+    );
+    s.client
+        .attach_proof_bundle(&payment_id, &proof_bundle(&env, &s.hospital, 16));
+    s.client.release_escrow(&payment_id, &approver);
+}
+
+#[test]
+fn fulfill_request_leaves_gated_escrow_locked() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+    let (request_id, unit_id) = approved_request(&env, &s);
+
+    let payment_id = s.client.create_payment(
+        &request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 0),
+    );
+    s.client
+        .fulfill_request(&s.bank, &request_id, &vec![&env, unit_id]);
+
+    assert_eq!(s.token.balance(&s.bank), 0);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Escrowed
+    );
+}
+
+// ======================================================
+// Dispute Split Tests
+// ======================================================
+
+fn disputed_payment(env: &Env, s: &Setup, request_id: u64) -> (u64, u64) {
+    let payment_id = s.client.create_payment(
+        &request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    (payment_id, raise_dispute(env, s, payment_id))
+}
+
+#[test]
+fn basis_point_split_pays_both_sides_and_prorates_fees() {
+    let env = Env::default();
+    let s = setup(&env);
+    let treasury = Address::generate(&env);
+    s.client.set_fee_policy(&fees(&env, 100), &treasury);
+    let (payment_id, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    // Net is 900; payee gets 40% of it and 40% of the fees
+    s.client.resolve_dispute(
+        &dispute_id,
+        &DisputeStatus::PartialSettlement,
+        &Some(DisputeSplit::BasisPoints(4_000)),
+    );
+
+    assert_eq!(s.token.balance(&s.bank), 360);
+    assert_eq!(s.token.balance(&treasury), 40);
+    assert_eq!(s.token.balance(&s.hospital), 9_600);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let dispute = s.client.get_dispute(&dispute_id);
+    assert_eq!(dispute.payee_amount, 360);
+    assert_eq!(dispute.payer_amount, 600);
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Settled
+    );
+}
+
+#[test]
+fn absolute_split_pays_fixed_amount_to_payee() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client.resolve_dispute(
+        &dispute_id,
+        &DisputeStatus::PartialSettlement,
+        &Some(DisputeSplit::Amount(250)),
+    );
+
+    assert_eq!(s.token.balance(&s.bank), 250);
+    assert_eq!(s.token.balance(&s.hospital), 9_750);
+}
+
+#[test]
+fn pro_rata_split_follows_payee_delivery() {
+    let env = Env::default();
+    let s = setup(&env);
+    let approver = Address::generate(&env);
+    let (request_id, unit_id) = approved_request_for(&env, &s, 900);
+
+    let payment_id = s.client.create_payment(
+        &request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &strict_conditions(&approver, 0),
+    );
+    s.client
+        .fulfill_request(&s.bank, &request_id, &vec![&env, unit_id]);
+    let dispute_id = raise_dispute(&env, &s, payment_id);
+
+    // The bank delivered everything it committed, though the request is
+    // only half filled
+    assert_eq!(
+        s.client.suggest_dispute_split(&dispute_id),
+        DisputeSplit::BasisPoints(10_000)
+    );
+
+    s.client.resolve_dispute(
+        &dispute_id,
+        &DisputeStatus::PartialSettlement,
+        &Some(DisputeSplit::ProRata),
+    );
+    assert_eq!(s.token.balance(&s.bank), 1_000);
+    assert_eq!(s.token.balance(&s.hospital), 9_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn split_above_net_amount_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client.resolve_dispute(
+        &dispute_id,
+        &DisputeStatus::PartialSettlement,
+        &Some(DisputeSplit::Amount(1_001)),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn partial_settlement_without_split_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::PartialSettlement, &None);
+}
+
+// ======================================================
+// Arbitration Panel Tests
+// ======================================================
+
+fn panel(env: &Env, s: &Setup, threshold: u32) -> Vec<Address> {
+    let arbiters = vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    s.client.set_arbitration_panel(
+        &arbiters,
+        &threshold,
+        &86_400,
+        &DisputeStatus::ResolvedInFavorOfPayer,
+    );
+    arbiters
+}
+
+fn rationale(env: &Env, byte: u8) -> Bytes {
+    Bytes::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn panel_resolves_dispute_at_threshold() {
+    let env = Env::default();
+    let s = setup(&env);
+    let arbiters = panel(&env, &s, 2);
+    let (payment_id, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client.vote_on_dispute(
+        &dispute_id,
+        &arbiters.get(0).unwrap(),
+        &DisputeStatus::ResolvedInFavorOfPayee,
+        &0,
+        &rationale(&env, 1),
+    );
+    assert_eq!(
+        s.client.get_dispute(&dispute_id).status,
+        DisputeStatus::Open
+    );
+
+    s.client.vote_on_dispute(
+        &dispute_id,
+        &arbiters.get(1).unwrap(),
+        &DisputeStatus::ResolvedInFavorOfPayee,
+        &0,
+        &rationale(&env, 2),
+    );
+
+    let dispute = s.client.get_dispute(&dispute_id);
+    assert_eq!(dispute.status, DisputeStatus::ResolvedInFavorOfPayee);
+    assert_eq!(dispute.votes.len(), 2);
+    assert_eq!(
+        dispute.votes.get(1).unwrap().rationale_digest,
+        rationale(&env, 2)
+    );
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Completed
+    );
+}
+
+#[test]
+fn split_votes_only_match_on_same_share() {
+    let env = Env::default();
+    let s = setup(&env);
+    let arbiters = panel(&env, &s, 2);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    for (i, bps) in [(0u32, 4_000u32), (1, 6_000), (2, 4_000)] {
+        s.client.vote_on_dispute(
+            &dispute_id,
+            &arbiters.get(i).unwrap(),
+            &DisputeStatus::PartialSettlement,
+            &bps,
+            &rationale(&env, i as u8),
+        );
+    }
+
+    let dispute = s.client.get_dispute(&dispute_id);
+    assert_eq!(dispute.status, DisputeStatus::PartialSettlement);
+    assert_eq!(dispute.payee_amount, 400);
+    assert_eq!(s.token.balance(&s.bank), 400);
+}
+
+#[test]
+fn default_outcome_applies_after_vote_deadline() {
+    let env = Env::default();
+    let s = setup(&env);
+    let arbiters = panel(&env, &s, 2);
+    let (payment_id, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client.vote_on_dispute(
+        &dispute_id,
+        &arbiters.get(0).unwrap(),
+        &DisputeStatus::ResolvedInFavorOfPayee,
+        &0,
+        &rationale(&env, 1),
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp += DISPUTE_RESPONSE_SECONDS + 86_400);
+    s.client.finalize_dispute_vote(&dispute_id);
+
+    assert_eq!(
+        s.client.get_dispute(&dispute_id).status,
+        DisputeStatus::ResolvedInFavorOfPayer
+    );
+    assert_eq!(
+        s.client.get_payment(&payment_id).status,
+        PaymentStatus::Refunded
+    );
+}
+
+#[test]
+fn unvoted_dismissal_leaves_funds_releasable() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.set_arbitration_panel(
+        &vec![&env, Address::generate(&env), Address::generate(&env)],
+        &2,
+        &86_400,
+        &DisputeStatus::Dismissed,
+    );
+    let (payment_id, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += DISPUTE_RESPONSE_SECONDS + 86_400);
+    s.client.finalize_dispute_vote(&dispute_id);
+
+    assert_eq!(
+        s.client.get_dispute(&dispute_id).status,
+        DisputeStatus::Dismissed
+    );
+    s.client.release_escrow(&payment_id, &s.hospital);
+    assert_eq!(s.token.balance(&s.bank), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #44)")]
+fn finalize_before_vote_deadline_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    panel(&env, &s, 2);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client.finalize_dispute_vote(&dispute_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")]
+fn arbiter_cannot_vote_twice() {
+    let env = Env::default();
+    let s = setup(&env);
+    let arbiters = panel(&env, &s, 3);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+    let arbiter = arbiters.get(0).unwrap();
+
+    for _ in 0..2 {
+        s.client.vote_on_dispute(
+            &dispute_id,
+            &arbiter,
+            &DisputeStatus::ResolvedInFavorOfPayee,
+            &0,
+            &rationale(&env, 1),
+        );
+    }
+}
+
+#[test]
+fn dispute_keeps_the_panel_it_was_raised_under() {
+    let env = Env::default();
+    let s = setup(&env);
+    let arbiters = panel(&env, &s, 2);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    // Replacing the panel does not change who decides an open dispute
+    let newcomer = Address::generate(&env);
+    s.client.set_arbitration_panel(
+        &vec![&env, newcomer.clone()],
+        &1,
+        &86_400,
+        &DisputeStatus::Dismissed,
+    );
+    assert_eq!(s.client.get_dispute(&dispute_id).arbiters, arbiters);
+
+    assert!(s
+        .client
+        .try_vote_on_dispute(
+            &dispute_id,
+            &newcomer,
+            &DisputeStatus::ResolvedInFavorOfPayee,
+            &0,
+            &rationale(&env, 9),
+        )
+        .is_err());
+
+    // The original quorum of 2 still applies
+    s.client.vote_on_dispute(
+        &dispute_id,
+        &arbiters.get(0).unwrap(),
+        &DisputeStatus::ResolvedInFavorOfPayee,
+        &0,
+        &rationale(&env, 1),
+    );
+    assert_eq!(
+        s.client.get_dispute(&dispute_id).status,
+        DisputeStatus::Open
+    );
+
+    // And so does the original default outcome
+    env.ledger()
+        .with_mut(|li| li.timestamp += DISPUTE_RESPONSE_SECONDS + 86_400);
+    s.client.finalize_dispute_vote(&dispute_id);
+    assert_eq!(
+        s.client.get_dispute(&dispute_id).status,
+        DisputeStatus::ResolvedInFavorOfPayer
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn admin_cannot_resolve_dispute_under_arbitration() {
+    let env = Env::default();
+    let s = setup(&env);
+    panel(&env, &s, 2);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::ResolvedInFavorOfPayee, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn non_arbiter_cannot_vote() {
+    let env = Env::default();
+    let s = setup(&env);
+    panel(&env, &s, 2);
+    let (_, dispute_id) = disputed_payment(&env, &s, s.request_id);
+
+    s.client.vote_on_dispute(
+        &dispute_id,
+        &s.bank,
+        &DisputeStatus::ResolvedInFavorOfPayee,
+        &0,
+        &rationale(&env, 1),
+    );
+}
+
+// ======================================================
+// Dispute Evidence Tests
+// ======================================================
+
+#[test]
+fn evidence_log_is_append_only_and_paginated() {
+    let env = Env::default();
+    let s = setup(&env);
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    let dispute_id = raise_unanswered_dispute(&env, &s, payment_id);
+
+    for byte in 2..5u8 {
+        s.client.submit_dispute_evidence(
+            &dispute_id,
+            &s.bank,
+            &Bytes::from_array(&env, &[byte; 32]),
+            &vec![&env, String::from_str(&env, "bafyREBUTTAL")],
+        );
+    }
+
+    let first = s.client.get_dispute_evidence(&dispute_id, &None, &3);
+    assert_eq!(first.items.len(), 3);
+    assert_eq!(first.items.get(0).unwrap().party, s.hospital);
+    assert_eq!(first.items.get(1).unwrap().party, s.bank);
+    assert_eq!(first.next_cursor, Some(3));
+
+    let second = s
+        .client
+        .get_dispute_evidence(&dispute_id, &first.next_cursor, &3);
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.items.get(0).unwrap().index, 3);
+    assert_eq!(second.next_cursor, None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn resolution_blocked_until_counterparty_responds() {
+    let env = Env::default();
+    let s = setup(&env);
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    let dispute_id = raise_unanswered_dispute(&env, &s, payment_id);
+
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::ResolvedInFavorOfPayer, &None);
+}
+
+#[test]
+fn resolution_allowed_once_response_window_closes() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.client.set_dispute_response_window(&3_600);
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    let dispute_id = raise_unanswered_dispute(&env, &s, payment_id);
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    s.client
+        .resolve_dispute(&dispute_id, &DisputeStatus::ResolvedInFavorOfPayer, &None);
+
+    assert_eq!(s.token.balance(&s.hospital), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #45)")]
+fn evidence_after_response_window_fails() {
+    let env = Env::default();
+    let s = setup(&env);
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    let dispute_id = raise_unanswered_dispute(&env, &s, payment_id);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += DISPUTE_RESPONSE_SECONDS);
+    s.client.submit_dispute_evidence(
+        &dispute_id,
+        &s.bank,
+        &Bytes::from_array(&env, &[2u8; 32]),
+        &vec![&env],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn outsider_cannot_submit_evidence() {
+    let env = Env::default();
+    let s = setup(&env);
+    let payment_id = s.client.create_payment(
+        &s.request_id,
+        &s.hospital,
+        &s.bank,
+        &1_000,
+        &s.token.address,
+        &open_conditions(),
+    );
+    let dispute_id = raise_unanswered_dispute(&env, &s, payment_id);
+
+    s.client.submit_dispute_evidence(
+        &dispute_id,
+        &Address::generate(&env),
+        &Bytes::from_array(&env, &[2u8; 32]),
+        &vec![&env],
+    );
+}
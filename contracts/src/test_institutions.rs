@@ -0,0 +1,183 @@
+#![cfg(test)]
+
+use crate::institutions::{InstitutionStatus, SuspensionPolicy};
+use crate::{
+    BloodComponent, BloodStatus, BloodType, HealthChainContract, HealthChainContractClient,
+    UrgencyLevel,
+};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+fn setup(env: &Env) -> (HealthChainContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let bank = Address::generate(env);
+    let hospital = Address::generate(env);
+    client.register_blood_bank(&bank);
+    client.register_hospital(&hospital);
+
+    (client, bank, hospital)
+}
+
+fn register_unit(env: &Env, client: &HealthChainContractClient<'_>, bank: &Address) -> u64 {
+    client.register_blood(
+        bank,
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    )
+}
+
+fn reason(env: &Env) -> String {
+    String::from_str(env, "license lapsed")
+}
+
+/// Open a bank-to-hospital hop and return its event ID
+fn in_flight_transfer(
+    env: &Env,
+    client: &HealthChainContractClient<'_>,
+    bank: &Address,
+    hospital: &Address,
+) -> (u64, String) {
+    let unit_id = register_unit(env, client, bank);
+    client.allocate_blood(bank, &unit_id, hospital);
+    (unit_id, client.initiate_transfer(bank, &unit_id))
+}
+
+// ======================================================
+// Institution Standing Tests
+// ======================================================
+
+#[test]
+fn suspension_blocks_bank_until_reinstated() {
+    let env = Env::default();
+    let (client, bank, _) = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+
+    client.suspend_institution(&bank, &reason(&env));
+
+    let standing = client.get_institution_standing(&bank).unwrap();
+    assert_eq!(standing.status, InstitutionStatus::Suspended);
+    assert_eq!(standing.reason, reason(&env));
+    assert_eq!(standing.changed_at, 5_000);
+    assert!(client.is_blood_bank(&bank));
+
+    let result = client.try_register_blood(
+        &bank,
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 7 * 86400),
+        &None,
+    );
+    assert!(result.is_err());
+
+    client.reinstate_institution(&bank, &String::from_str(&env, "license renewed"));
+    assert_eq!(
+        client.get_institution_standing(&bank).unwrap().status,
+        InstitutionStatus::Active
+    );
+    register_unit(&env, &client, &bank);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn suspended_hospital_cannot_create_request() {
+    let env = Env::default();
+    let (client, _, hospital) = setup(&env);
+    client.suspend_institution(&hospital, &reason(&env));
+
+    client.create_request(
+        &hospital,
+        &BloodType::APositive,
+        &500,
+        &UrgencyLevel::High,
+        &(env.ledger().timestamp() + 2 * 86400),
+        &String::from_str(&env, "Ward 7B - ICU"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn suspended_hospital_cannot_receive_allocation() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let unit_id = register_unit(&env, &client, &bank);
+    client.suspend_institution(&hospital, &reason(&env));
+
+    client.allocate_blood(&bank, &unit_id, &hospital);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn freeze_policy_blocks_in_flight_confirmation() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let (_, event_id) = in_flight_transfer(&env, &client, &bank, &hospital);
+    assert_eq!(
+        client.get_suspension_policy(),
+        SuspensionPolicy::FreezeTransfers
+    );
+
+    client.suspend_institution(&bank, &reason(&env));
+    client.confirm_transfer(&hospital, &event_id);
+}
+
+#[test]
+fn allow_policy_lets_in_flight_transfer_finish() {
+    let env = Env::default();
+    let (client, bank, hospital) = setup(&env);
+    let (unit_id, event_id) = in_flight_transfer(&env, &client, &bank, &hospital);
+
+    client.set_suspension_policy(&SuspensionPolicy::AllowInFlight);
+    client.suspend_institution(&hospital, &reason(&env));
+    client.confirm_transfer(&hospital, &event_id);
+
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Delivered
+    );
+}
+
+#[test]
+fn deregistration_removes_institution_until_registered_again() {
+    let env = Env::default();
+    let (client, _, hospital) = setup(&env);
+
+    client.deregister_institution(&hospital, &String::from_str(&env, "closed"));
+    assert!(!client.is_hospital(&hospital));
+    assert_eq!(
+        client.get_institution_standing(&hospital).unwrap().status,
+        InstitutionStatus::Deregistered
+    );
+
+    client.register_hospital(&hospital);
+    assert!(client.is_hospital(&hospital));
+    assert!(client.get_institution_standing(&hospital).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn reinstate_requires_suspension() {
+    let env = Env::default();
+    let (client, bank, _) = setup(&env);
+
+    client.reinstate_institution(&bank, &reason(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn suspend_requires_registered_institution() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+
+    client.suspend_institution(&Address::generate(&env), &reason(&env));
+}
@@ -186,6 +186,7 @@ fn payment_status_allowed_transition_matrix_is_complete() {
         (PaymentStatus::Escrowed, PaymentStatus::Refunded),
         (PaymentStatus::Escrowed, PaymentStatus::Disputed),
         (PaymentStatus::Disputed, PaymentStatus::Resolved),
+        (PaymentStatus::Disputed, PaymentStatus::Escrowed),
         (PaymentStatus::Resolved, PaymentStatus::Completed),
         (PaymentStatus::Resolved, PaymentStatus::Refunded),
     ];
@@ -216,6 +217,7 @@ fn payment_status_forbidden_transition_matrix_is_complete() {
         (PaymentStatus::Escrowed, PaymentStatus::Refunded),
         (PaymentStatus::Escrowed, PaymentStatus::Disputed),
         (PaymentStatus::Disputed, PaymentStatus::Resolved),
+        (PaymentStatus::Disputed, PaymentStatus::Escrowed),
         (PaymentStatus::Resolved, PaymentStatus::Completed),
         (PaymentStatus::Resolved, PaymentStatus::Refunded),
     ];
@@ -235,7 +237,7 @@ fn payment_status_forbidden_transition_matrix_is_complete() {
         }
     }
 
-    assert_eq!(forbidden_checked, 41);
+    assert_eq!(forbidden_checked, 40);
 }
 
 #[test]
@@ -243,6 +245,7 @@ fn dispute_structure_is_valid() {
     let env = Env::default();
     let raiser = Address::generate(&env);
     use crate::payments::{Dispute, DisputeStatus};
+    use crate::RequestStatus;
 
     let mut chunks = vec![&env];
     chunks.push_back(String::from_str(&env, "bafyFIRST"));
@@ -261,6 +264,17 @@ fn dispute_structure_is_valid() {
         evidence_ref_chunks: chunks,
         raised_at: 1000,
         resolved_at: None,
+        payee_amount: 0,
+        payer_amount: 0,
+        response_deadline: 0,
+        payer_submitted: false,
+        payee_submitted: false,
+        vote_deadline: 0,
+        votes: vec![&env],
+        arbiters: vec![&env],
+        vote_threshold: 0,
+        default_outcome: DisputeStatus::Dismissed,
+        request_status: RequestStatus::Approved,
     };
 
     assert_eq!(dispute.status, DisputeStatus::Open);
@@ -276,7 +290,7 @@ fn dispute_evidence_chunk_order_is_stable() {
     assert_eq!(chunks.len(), 2u32);
     let first = chunks.get(0).unwrap();
     let second = chunks.get(1).unwrap();
-    assert!(first.len() > 0 && second.len() > 0);
+    assert!(!first.is_empty() && !second.is_empty());
 }
 
 #[test]
@@ -321,11 +335,12 @@ fn escrow_validates_and_releases_correctly() {
             medical_records_verified: true,
             min_timestamp: 100,
             authorized_approver: Some(approver.clone()),
+            require_proof_bundle: false,
         },
     };
 
     assert!(escrow.validate().is_ok());
-    assert!(escrow.can_release(200, Some(&approver)));
+    assert!(escrow.can_release(200, Some(&approver), None));
 }
 
 #[test]
@@ -337,10 +352,11 @@ fn escrow_fails_release_without_conditions() {
             medical_records_verified: false,
             min_timestamp: 100,
             authorized_approver: None,
+            require_proof_bundle: false,
         },
     };
 
-    assert!(!escrow.can_release(200, None));
+    assert!(!escrow.can_release(200, None, None));
 }
 
 #[test]
@@ -368,17 +384,54 @@ fn escrow_release_integration_rejects_premature_and_unauthorized_attempts() {
             medical_records_verified: true,
             min_timestamp: 1_000,
             authorized_approver: Some(authorized_approver.clone()),
+            require_proof_bundle: false,
         },
     };
 
     // Premature release attempt (before min_timestamp) must fail even if approver is correct.
-    assert!(!escrow.can_release(999, Some(&authorized_approver)));
+    assert!(!escrow.can_release(999, Some(&authorized_approver), None));
 
     // Unauthorized release attempt at/after min_timestamp must fail.
-    assert!(!escrow.can_release(1_000, Some(&unauthorized_approver)));
+    assert!(!escrow.can_release(1_000, Some(&unauthorized_approver), None));
 
     // Missing required approver at/after min_timestamp must fail.
-    assert!(!escrow.can_release(1_000, None));
+    assert!(!escrow.can_release(1_000, None, None));
+}
+
+#[test]
+fn escrow_check_release_reports_first_unmet_condition() {
+    let env = Env::default();
+    let approver = Address::generate(&env);
+
+    let mut escrow = EscrowAccount {
+        payment_id: 1,
+        locked_amount: 1_000,
+        release_conditions: ReleaseConditions {
+            medical_records_verified: false,
+            min_timestamp: 100,
+            authorized_approver: Some(approver.clone()),
+            require_proof_bundle: true,
+        },
+    };
+
+    assert_eq!(
+        escrow.check_release(99, Some(&approver), None),
+        Err(PaymentError::ReleaseTooEarly)
+    );
+    assert_eq!(
+        escrow.check_release(100, Some(&approver), None),
+        Err(PaymentError::MedicalRecordsUnverified)
+    );
+
+    escrow.release_conditions.medical_records_verified = true;
+    assert_eq!(
+        escrow.check_release(100, None, None),
+        Err(PaymentError::ApproverMismatch)
+    );
+    assert_eq!(
+        escrow.check_release(100, Some(&approver), None),
+        Err(PaymentError::ProofBundleMissing)
+    );
 }
 
 #[test]
@@ -405,12 +458,13 @@ fn escrow_release_integration_allows_release_only_when_all_guards_pass() {
             medical_records_verified: true,
             min_timestamp: 2_000,
             authorized_approver: Some(authorized_approver.clone()),
+            require_proof_bundle: false,
         },
     };
 
-    assert!(!escrow.can_release(1_999, Some(&authorized_approver)));
-    assert!(escrow.can_release(2_000, Some(&authorized_approver)));
-    assert!(escrow.can_release(2_001, Some(&authorized_approver)));
+    assert!(!escrow.can_release(1_999, Some(&authorized_approver), None));
+    assert!(escrow.can_release(2_000, Some(&authorized_approver), None));
+    assert!(escrow.can_release(2_001, Some(&authorized_approver), None));
 }
 
 // ======================================================
@@ -0,0 +1,408 @@
+#![cfg(test)]
+
+use crate::constants::{INDEX_BUCKET_SIZE, QUARANTINE_REVIEW_SECONDS};
+use crate::quarantine::QuarantineCaseStatus;
+use crate::{
+    BloodComponent, BloodStatus, BloodType, DataKey, Error, HealthChainContract,
+    HealthChainContractClient, QuarantineDisposition, QuarantineReason, NEXT_ID,
+};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Bytes, Env, Vec,
+};
+
+fn setup(env: &Env) -> (HealthChainContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let bank = Address::generate(env);
+    let reviewer = Address::generate(env);
+    client.initialize(&admin);
+    client.register_blood_bank(&bank);
+    client.register_blood_bank(&reviewer);
+
+    (client, bank, reviewer)
+}
+
+fn register_unit(env: &Env, client: &HealthChainContractClient<'_>, bank: &Address) -> u64 {
+    client.register_blood(
+        bank,
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450,
+        &(env.ledger().timestamp() + 30 * 86400),
+        &None,
+    )
+}
+
+fn digest(env: &Env) -> Bytes {
+    Bytes::from_array(env, &[7u8; 32])
+}
+
+// ======================================================
+// Quarantine Case Tests
+// ======================================================
+
+#[test]
+fn quarantine_blood_opens_case() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let unit_id = register_unit(&env, &client, &bank);
+
+    client.quarantine_blood(
+        &bank,
+        &unit_id,
+        &QuarantineReason::ScreeningFailure,
+        &digest(&env),
+        &reviewer,
+    );
+
+    let case = client.get_quarantine_case(&unit_id).unwrap();
+    assert_eq!(case.reason, QuarantineReason::ScreeningFailure);
+    assert_eq!(case.evidence_digest, digest(&env));
+    assert_eq!(case.reviewer, reviewer);
+    assert_eq!(case.opened_by, bank);
+    assert_eq!(case.opened_at, 1_000);
+    assert_eq!(case.decision_deadline, 1_000 + QUARANTINE_REVIEW_SECONDS);
+    assert_eq!(case.status, QuarantineCaseStatus::Open);
+}
+
+#[test]
+fn finalize_quarantine_closes_case() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+    let unit_id = register_unit(&env, &client, &bank);
+
+    client.quarantine_blood(
+        &bank,
+        &unit_id,
+        &QuarantineReason::TemperatureBreach,
+        &digest(&env),
+        &reviewer,
+    );
+    client.finalize_quarantine(
+        &reviewer,
+        &unit_id,
+        &QuarantineReason::TemperatureBreach,
+        &QuarantineDisposition::Release,
+    );
+
+    let case = client.get_quarantine_case(&unit_id).unwrap();
+    assert_eq!(case.status, QuarantineCaseStatus::Released);
+    assert_eq!(case.decided_by, Some(reviewer));
+    assert!(client.get_open_quarantines(&None, &10).items.is_empty());
+}
+
+#[test]
+fn only_assigned_reviewer_may_finalize() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+    let unit_id = register_unit(&env, &client, &bank);
+
+    client.quarantine_blood(
+        &bank,
+        &unit_id,
+        &QuarantineReason::ScreeningFailure,
+        &digest(&env),
+        &reviewer,
+    );
+
+    // Not even the bank that opened the case may decide it
+    assert!(client
+        .try_finalize_quarantine(
+            &bank,
+            &unit_id,
+            &QuarantineReason::ScreeningFailure,
+            &QuarantineDisposition::Release,
+        )
+        .is_err());
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Quarantined
+    );
+    assert_eq!(
+        client.get_quarantine_case(&unit_id).unwrap().status,
+        QuarantineCaseStatus::Open
+    );
+}
+
+#[test]
+fn admin_may_override_reviewer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let bank = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_blood_bank(&bank);
+    client.register_blood_bank(&reviewer);
+    let unit_id = register_unit(&env, &client, &bank);
+
+    client.quarantine_blood(
+        &bank,
+        &unit_id,
+        &QuarantineReason::ScreeningFailure,
+        &digest(&env),
+        &reviewer,
+    );
+    client.finalize_quarantine(
+        &admin,
+        &unit_id,
+        &QuarantineReason::ScreeningFailure,
+        &QuarantineDisposition::Discard,
+    );
+
+    let case = client.get_quarantine_case(&unit_id).unwrap();
+    assert_eq!(case.status, QuarantineCaseStatus::Discarded);
+    assert_eq!(case.decided_by, Some(admin));
+}
+
+#[test]
+fn reviewer_must_be_another_registered_bank() {
+    let env = Env::default();
+    let (client, bank, _) = setup(&env);
+    let unit_id = register_unit(&env, &client, &bank);
+
+    let quarantine = |reviewer: &Address| {
+        client.try_quarantine_blood(
+            &bank,
+            &unit_id,
+            &QuarantineReason::ScreeningFailure,
+            &digest(&env),
+            reviewer,
+        )
+    };
+    assert_eq!(quarantine(&bank), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        quarantine(&Address::generate(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+fn get_open_quarantines_pages_by_unit_id() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+
+    let mut ids = [0u64; 3];
+    for id in ids.iter_mut() {
+        *id = register_unit(&env, &client, &bank);
+        client.quarantine_blood(
+            &bank,
+            id,
+            &QuarantineReason::ContaminationSuspected,
+            &digest(&env),
+            &reviewer,
+        );
+    }
+
+    let first = client.get_open_quarantines(&None, &2);
+    assert_eq!(first.items.len(), 2);
+    assert_eq!(first.items.get(0).unwrap().unit_id, ids[0]);
+    assert_eq!(first.next_cursor, Some(ids[1]));
+
+    let second = client.get_open_quarantines(&first.next_cursor, &2);
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.items.get(0).unwrap().unit_id, ids[2]);
+    assert_eq!(second.next_cursor, None);
+}
+
+#[test]
+fn open_cases_are_indexed_in_unit_id_buckets() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+
+    let first = register_unit(&env, &client, &bank);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&NEXT_ID, &(INDEX_BUCKET_SIZE * 40));
+    });
+    let second = register_unit(&env, &client, &bank);
+    for unit_id in [first, second] {
+        client.quarantine_blood(
+            &bank,
+            &unit_id,
+            &QuarantineReason::ContaminationSuspected,
+            &digest(&env),
+            &reviewer,
+        );
+    }
+
+    env.as_contract(&client.address, || {
+        let bucket = |unit_id: u64| -> Vec<u64> {
+            env.storage()
+                .persistent()
+                .get(&DataKey::OpenQuarantines(unit_id / INDEX_BUCKET_SIZE))
+                .unwrap()
+        };
+        assert_eq!(bucket(first), vec![&env, first]);
+        assert_eq!(bucket(second), vec![&env, second]);
+    });
+
+    // Paging walks a bounded number of buckets per call
+    let mut seen = Vec::new(&env);
+    let mut cursor = None;
+    loop {
+        let page = client.get_open_quarantines(&cursor, &10);
+        for case in page.items.iter() {
+            seen.push_back(case.unit_id);
+        }
+        match page.next_cursor {
+            Some(_) => cursor = page.next_cursor,
+            None => break,
+        }
+    }
+    assert_eq!(seen, vec![&env, first, second]);
+
+    client.finalize_quarantine(
+        &reviewer,
+        &first,
+        &QuarantineReason::ContaminationSuspected,
+        &QuarantineDisposition::Release,
+    );
+    env.as_contract(&client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::OpenQuarantines(first / INDEX_BUCKET_SIZE)));
+    });
+}
+
+#[test]
+fn escalate_discards_overdue_cases_only() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+    let overdue = register_unit(&env, &client, &bank);
+    client.quarantine_blood(
+        &bank,
+        &overdue,
+        &QuarantineReason::ScreeningFailure,
+        &digest(&env),
+        &reviewer,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    let recent = register_unit(&env, &client, &bank);
+    client.quarantine_blood(
+        &bank,
+        &recent,
+        &QuarantineReason::ScreeningFailure,
+        &digest(&env),
+        &reviewer,
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += QUARANTINE_REVIEW_SECONDS - 3_600);
+    let page = client.escalate_overdue_quarantines(&None, &10);
+
+    assert_eq!(page.escalated, vec![&env, overdue]);
+    assert_eq!(page.next_cursor, None);
+    assert_eq!(
+        client.get_blood_unit(&overdue).status,
+        BloodStatus::Discarded
+    );
+    assert_eq!(
+        client.get_quarantine_case(&overdue).unwrap().status,
+        QuarantineCaseStatus::Escalated
+    );
+    assert_eq!(
+        client.get_blood_unit(&recent).status,
+        BloodStatus::Quarantined
+    );
+    assert_eq!(client.get_open_quarantines(&None, &10).items.len(), 1);
+}
+
+#[test]
+fn review_window_applies_to_new_cases() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+    let unit_id = register_unit(&env, &client, &bank);
+
+    client.set_quarantine_review_window(&600);
+    assert_eq!(client.get_quarantine_review_window(), 600);
+
+    let now = env.ledger().timestamp();
+    client.quarantine_blood(
+        &bank,
+        &unit_id,
+        &QuarantineReason::Other,
+        &digest(&env),
+        &reviewer,
+    );
+
+    assert_eq!(
+        client
+            .get_quarantine_case(&unit_id)
+            .unwrap()
+            .decision_deadline,
+        now + 600
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn escalate_rejects_oversized_batch() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+
+    client.escalate_overdue_quarantines(&None, &1_000);
+}
+
+#[test]
+fn escalate_checks_a_bounded_batch_per_call() {
+    let env = Env::default();
+    let (client, bank, reviewer) = setup(&env);
+
+    let mut ids = [0u64; 3];
+    for id in ids.iter_mut() {
+        *id = register_unit(&env, &client, &bank);
+        client.quarantine_blood(
+            &bank,
+            id,
+            &QuarantineReason::ContaminationSuspected,
+            &digest(&env),
+            &reviewer,
+        );
+    }
+    // Decided cases leave the open index and are not checked again
+    client.finalize_quarantine(
+        &reviewer,
+        &ids[0],
+        &QuarantineReason::ContaminationSuspected,
+        &QuarantineDisposition::Release,
+    );
+    let recent = register_unit(&env, &client, &bank);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += QUARANTINE_REVIEW_SECONDS);
+    client.quarantine_blood(
+        &bank,
+        &recent,
+        &QuarantineReason::ContaminationSuspected,
+        &digest(&env),
+        &reviewer,
+    );
+
+    let first = client.escalate_overdue_quarantines(&None, &2);
+    assert_eq!(first.escalated, vec![&env, ids[1], ids[2]]);
+    assert_eq!(first.next_cursor, Some(ids[2]));
+
+    let second = client.escalate_overdue_quarantines(&first.next_cursor, &2);
+    assert!(second.escalated.is_empty());
+    assert_eq!(second.next_cursor, None);
+    assert_eq!(
+        client.get_blood_unit(&recent).status,
+        BloodStatus::Quarantined
+    );
+}
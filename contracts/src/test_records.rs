@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+use crate::{HealthChainContract, HealthChainContractClient};
+
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env,
+};
+
+fn setup(env: &Env) -> (HealthChainContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+
+    (client, Address::generate(env), Address::generate(env))
+}
+
+fn hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+// ======================================================
+// Record Versioning Tests
+// ======================================================
+
+#[test]
+fn store_record_appends_versions() {
+    let env = Env::default();
+    let (client, patient, _) = setup(&env);
+
+    assert_eq!(client.store_record(&patient, &patient, &hash(&env, 1)), 1);
+    assert_eq!(client.store_record(&patient, &patient, &hash(&env, 2)), 2);
+
+    let latest = client.get_record(&patient, &None);
+    assert_eq!(latest.version, 2);
+    assert_eq!(latest.record_hash, hash(&env, 2));
+
+    let first = client.get_record(&patient, &Some(1));
+    assert_eq!(first.record_hash, hash(&env, 1));
+    assert_eq!(first.author, patient);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn get_record_without_records_fails() {
+    let env = Env::default();
+    let (client, patient, _) = setup(&env);
+
+    client.get_record(&patient, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn get_record_unknown_version_fails() {
+    let env = Env::default();
+    let (client, patient, _) = setup(&env);
+
+    client.store_record(&patient, &patient, &hash(&env, 1));
+    client.get_record(&patient, &Some(2));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn provider_without_grant_cannot_store_record() {
+    let env = Env::default();
+    let (client, patient, provider) = setup(&env);
+
+    client.store_record(&provider, &patient, &hash(&env, 1));
+}
+
+#[test]
+fn granted_provider_can_store_record() {
+    let env = Env::default();
+    let (client, patient, provider) = setup(&env);
+
+    client.grant_access(&patient, &provider, &None);
+    let version = client.store_record(&provider, &patient, &hash(&env, 9));
+
+    let record = client.get_record(&patient, &Some(version));
+    assert_eq!(record.author, provider);
+}
+
+// ======================================================
+// Access Grant Tests
+// ======================================================
+
+#[test]
+fn verify_access_honours_grant_expiry() {
+    let env = Env::default();
+    let (client, patient, provider) = setup(&env);
+
+    let now = env.ledger().timestamp();
+    client.grant_access(&patient, &provider, &Some(now + 3_600));
+    assert!(client.verify_access(&patient, &provider));
+
+    env.ledger().with_mut(|li| li.timestamp = now + 3_600);
+    assert!(!client.verify_access(&patient, &provider));
+}
+
+#[test]
+fn revoke_access_removes_grant() {
+    let env = Env::default();
+    let (client, patient, provider) = setup(&env);
+
+    client.grant_access(&patient, &provider, &None);
+    assert!(client.get_access_grant(&patient, &provider).is_some());
+
+    client.revoke_access(&patient, &provider);
+    assert!(client.get_access_grant(&patient, &provider).is_none());
+    assert!(!client.verify_access(&patient, &provider));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn revoke_access_without_grant_fails() {
+    let env = Env::default();
+    let (client, patient, provider) = setup(&env);
+
+    client.revoke_access(&patient, &provider);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn grant_access_with_past_expiry_fails() {
+    let env = Env::default();
+    let (client, patient, provider) = setup(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.grant_access(&patient, &provider, &Some(1_000));
+}
+
+#[test]
+fn verify_access_emits_access_log_event() {
+    let env = Env::default();
+    let (client, patient, provider) = setup(&env);
+
+    client.verify_access(&patient, &provider);
+    assert_eq!(env.events().all().len(), 1);
+}
@@ -0,0 +1,280 @@
+#![cfg(test)]
+
+use crate::bucket_queue::{Queue, QueueKey};
+use crate::compatibility::can_donate_to;
+use crate::constants::QUEUE_BUCKET_SECONDS;
+use crate::{BloodType, HealthChainContract, HealthChainContractClient, UrgencyLevel};
+
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Vec};
+
+const DAY: u64 = 86_400;
+
+fn setup(env: &Env) -> (HealthChainContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let hospital = Address::generate(env);
+    client.register_hospital(&hospital);
+
+    (client, hospital)
+}
+
+fn request(
+    env: &Env,
+    client: &HealthChainContractClient<'_>,
+    hospital: &Address,
+    blood_type: BloodType,
+    urgency: UrgencyLevel,
+    required_in: u64,
+) -> u64 {
+    client.create_request(
+        hospital,
+        &blood_type,
+        &500,
+        &urgency,
+        &(env.ledger().timestamp() + required_in),
+        &String::from_str(env, "Ward C"),
+    )
+}
+
+fn queue_ids(
+    env: &Env,
+    client: &HealthChainContractClient<'_>,
+    blood_type: Option<BloodType>,
+) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    for request in client
+        .get_request_queue(&blood_type, &None, &0)
+        .items
+        .iter()
+    {
+        ids.push_back(request.id);
+    }
+    ids
+}
+
+// ======================================================
+// Request Queue Tests
+// ======================================================
+
+#[test]
+fn queue_orders_by_urgency_then_deadline() {
+    let env = Env::default();
+    let (client, hospital) = setup(&env);
+
+    let low = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::APositive,
+        UrgencyLevel::Low,
+        DAY,
+    );
+    let critical_late = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::APositive,
+        UrgencyLevel::Critical,
+        2 * DAY,
+    );
+    let urgent = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::APositive,
+        UrgencyLevel::Urgent,
+        DAY,
+    );
+    let critical_soon = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::APositive,
+        UrgencyLevel::Critical,
+        DAY,
+    );
+
+    assert_eq!(
+        queue_ids(&env, &client, None),
+        vec![&env, critical_soon, critical_late, urgent, low]
+    );
+}
+
+#[test]
+fn queue_filters_by_donor_compatibility() {
+    let env = Env::default();
+    let (client, hospital) = setup(&env);
+
+    let ab_pos = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::ABPositive,
+        UrgencyLevel::High,
+        DAY,
+    );
+    let a_neg = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::ANegative,
+        UrgencyLevel::High,
+        2 * DAY,
+    );
+    let o_neg = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::ONegative,
+        UrgencyLevel::High,
+        3 * DAY,
+    );
+
+    assert_eq!(
+        queue_ids(&env, &client, Some(BloodType::ONegative)),
+        vec![&env, ab_pos, a_neg, o_neg]
+    );
+    assert_eq!(
+        queue_ids(&env, &client, Some(BloodType::ANegative)),
+        vec![&env, ab_pos, a_neg]
+    );
+    assert_eq!(
+        queue_ids(&env, &client, Some(BloodType::ABPositive)),
+        vec![&env, ab_pos]
+    );
+}
+
+#[test]
+fn queue_pages_with_cursor() {
+    let env = Env::default();
+    let (client, hospital) = setup(&env);
+
+    let mut ids = Vec::new(&env);
+    for days in 1..=3 {
+        ids.push_back(request(
+            &env,
+            &client,
+            &hospital,
+            BloodType::OPositive,
+            UrgencyLevel::Urgent,
+            days * DAY,
+        ));
+    }
+
+    let first = client.get_request_queue(&None, &None, &2);
+    assert_eq!(first.items.len(), 2);
+    assert_eq!(first.next_cursor, Some(ids.get(1).unwrap()));
+
+    let second = client.get_request_queue(&None, &first.next_cursor, &2);
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.items.get(0).unwrap().id, ids.get(2).unwrap());
+    assert_eq!(second.next_cursor, None);
+}
+
+#[test]
+fn closed_requests_leave_the_queue() {
+    let env = Env::default();
+    let (client, hospital) = setup(&env);
+
+    let cancelled = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::BPositive,
+        UrgencyLevel::Critical,
+        DAY,
+    );
+    let open = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::BPositive,
+        UrgencyLevel::Low,
+        DAY,
+    );
+    client.cancel_request(&cancelled, &String::from_str(&env, "Duplicate order"));
+
+    assert_eq!(queue_ids(&env, &client, None), vec![&env, open]);
+}
+
+#[test]
+fn queue_is_bucketed_by_urgency_and_deadline_hour() {
+    let env = Env::default();
+    let (client, hospital) = setup(&env);
+
+    let first = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::OPositive,
+        UrgencyLevel::Critical,
+        DAY,
+    );
+    let same_hour = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::OPositive,
+        UrgencyLevel::Critical,
+        DAY + 60,
+    );
+    let routine = request(
+        &env,
+        &client,
+        &hospital,
+        BloodType::OPositive,
+        UrgencyLevel::Routine,
+        DAY,
+    );
+    let hour = DAY / QUEUE_BUCKET_SECONDS;
+
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        let priority: Vec<u64> = storage.get(&QueueKey::Buckets(Queue::Requests)).unwrap();
+        assert_eq!(priority, vec![&env, hour, (3 << 40) | hour]);
+        let deadlines: Vec<u64> = storage.get(&QueueKey::Buckets(Queue::Deadlines)).unwrap();
+        assert_eq!(deadlines, vec![&env, hour]);
+        let due: Vec<(u64, u64)> = storage
+            .get(&QueueKey::Bucket(Queue::Deadlines, hour))
+            .unwrap();
+        assert_eq!(
+            due,
+            vec![&env, (DAY, first), (DAY, routine), (DAY + 60, same_hour)]
+        );
+    });
+
+    for id in [first, same_hour, routine] {
+        client.cancel_request(&id, &String::from_str(&env, "Duplicate order"));
+    }
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&QueueKey::Buckets(Queue::Requests)));
+        assert!(!storage.has(&QueueKey::Buckets(Queue::Deadlines)));
+        assert!(!storage.has(&QueueKey::Bucket(Queue::Requests, hour)));
+    });
+}
+
+#[test]
+fn red_cell_compatibility_matrix() {
+    let all = [
+        BloodType::APositive,
+        BloodType::ANegative,
+        BloodType::BPositive,
+        BloodType::BNegative,
+        BloodType::ABPositive,
+        BloodType::ABNegative,
+        BloodType::OPositive,
+        BloodType::ONegative,
+    ];
+    for recipient in all {
+        assert!(can_donate_to(BloodType::ONegative, recipient));
+        assert!(can_donate_to(recipient, BloodType::ABPositive));
+    }
+    assert!(!can_donate_to(BloodType::APositive, BloodType::ANegative));
+    assert!(!can_donate_to(BloodType::BNegative, BloodType::ANegative));
+    assert!(can_donate_to(BloodType::ONegative, BloodType::ONegative));
+    assert!(!can_donate_to(BloodType::OPositive, BloodType::ONegative));
+}
@@ -5,14 +5,14 @@
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
-    Address, Env, Map, Symbol,
+    Address, Env, Map, Symbol, Vec,
 };
 
 use crate::{
-    BloodComponent, BloodStatus, BloodType, BloodUnit, HealthChainContract,
-    HealthChainContractClient, ADMIN, BLOOD_BANKS, BLOOD_UNITS, CUSTODY_EVENTS, DISPUTES, HISTORY,
-    HOSPITALS, NEXT_DISPUTE_ID, NEXT_ID, NEXT_PAYMENT_ID, NEXT_REQUEST_ID, PAYMENTS, REQUESTS,
-    REQUEST_KEYS,
+    constants::INDEX_BUCKET_SIZE, registry_read::expiry_day, BloodComponent, BloodStatus,
+    BloodType, BloodUnit, DataKey, HealthChainContract, HealthChainContractClient, ADMIN,
+    BLOOD_BANKS, BLOOD_UNITS, CUSTODY_EVENTS, DISPUTES, HISTORY, HOSPITALS, NEXT_DISPUTE_ID,
+    NEXT_ID, NEXT_PAYMENT_ID, NEXT_REQUEST_ID, PAYMENTS, REQUESTS, REQUEST_KEYS,
 };
 
 #[test]
@@ -40,14 +40,10 @@ fn test_register_unit_creates_blood_unit_in_persistent_storage() {
 
     // Directly inspect persistent storage
     env.as_contract(&contract_id, || {
-        let units: Map<u64, BloodUnit> = env
+        let unit: BloodUnit = env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
-            .expect("BLOOD_UNITS should exist in persistent storage");
-
-        let unit = units
-            .get(unit_id)
+            .get(&DataKey::BloodUnit(unit_id))
             .expect("BloodUnit entry should exist for unit_id");
 
         assert_eq!(unit.id, unit_id);
@@ -55,7 +51,10 @@ fn test_register_unit_creates_blood_unit_in_persistent_storage() {
         assert_eq!(unit.status, BloodStatus::Available);
 
         // Verify it's NOT in instance storage
-        assert!(!env.storage().instance().has(&BLOOD_UNITS));
+        assert!(!env.storage().instance().has(&DataKey::BloodUnit(unit_id)));
+
+        // Verify the legacy single-map layout is not written
+        assert!(!env.storage().persistent().has(&BLOOD_UNITS));
     });
 }
 
@@ -84,29 +83,20 @@ fn test_register_unit_creates_bank_units_index_in_persistent_storage() {
 
     // Directly inspect persistent storage for BankUnits index
     env.as_contract(&contract_id, || {
-        // Note: The current implementation doesn't maintain a BankUnits index
-        // This test documents the expected behavior per requirements
-        // If BankUnits index is implemented, it should be stored as:
-        // DataKey::BankUnits(bank_id) -> Vec<u64> in persistent storage
-
-        // For now, we verify the unit exists and is associated with the bank
-        let units: Map<u64, BloodUnit> = env
+        let unit: BloodUnit = env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
-            .expect("BLOOD_UNITS should exist");
-
-        let unit = units.get(unit_id).expect("Unit should exist");
+            .get(&DataKey::BloodUnit(unit_id))
+            .expect("Unit should exist");
         assert_eq!(unit.bank_id, bank);
 
-        // When BankUnits index is implemented, uncomment:
-        // let bank_units_key = DataKey::BankUnits(bank.clone());
-        // let bank_units: Vec<u64> = env
-        //     .storage()
-        //     .persistent()
-        //     .get(&bank_units_key)
-        //     .expect("BankUnits index should exist in persistent storage");
-        // assert!(bank_units.contains(&unit_id));
+        let bank_units_key = DataKey::BankUnits(bank.clone(), unit_id / INDEX_BUCKET_SIZE);
+        let bank_units: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&bank_units_key)
+            .expect("BankUnits index should exist in persistent storage");
+        assert!(bank_units.contains(unit_id));
     });
 }
 
@@ -136,29 +126,21 @@ fn test_register_unit_creates_donor_units_index_in_persistent_storage() {
 
     // Directly inspect persistent storage for DonorUnits index
     env.as_contract(&contract_id, || {
-        // Note: The current implementation doesn't maintain a DonorUnits index
-        // This test documents the expected behavior per requirements
-        // DonorUnits should be stored as:
-        // DataKey::DonorUnits(bank_id, donor_id) -> Vec<u64> in persistent storage
-
-        // For now, we verify the unit exists with the donor_id
-        let units: Map<u64, BloodUnit> = env
+        let unit: BloodUnit = env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
-            .expect("BLOOD_UNITS should exist");
-
-        let unit = units.get(unit_id).expect("Unit should exist");
+            .get(&DataKey::BloodUnit(unit_id))
+            .expect("Unit should exist");
         assert_eq!(unit.donor_id, donor_id);
 
-        // When DonorUnits index is implemented, uncomment:
-        // let donor_units_key = DataKey::DonorUnits(bank.clone(), donor_id.clone());
-        // let donor_units: Vec<u64> = env
-        //     .storage()
-        //     .persistent()
-        //     .get(&donor_units_key)
-        //     .expect("DonorUnits index should exist in persistent storage");
-        // assert!(donor_units.contains(&unit_id));
+        let donor_units_key =
+            DataKey::DonorUnits(bank.clone(), donor_id.clone(), unit_id / INDEX_BUCKET_SIZE);
+        let donor_units: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&donor_units_key)
+            .expect("DonorUnits index should exist in persistent storage");
+        assert!(donor_units.contains(unit_id));
     });
 }
 
@@ -213,25 +195,25 @@ fn test_update_status_modifies_existing_entry_no_new_key() {
         &None,
     );
 
-    // Get initial storage state
-    let initial_keys_count = env.as_contract(&contract_id, || {
-        let units: Map<u64, BloodUnit> = env.storage().persistent().get(&BLOOD_UNITS).unwrap();
-        units.len()
-    });
-
     // Allocate blood (changes status to Reserved)
     client.allocate_blood(&bank, &unit_id, &hospital);
 
-    // Verify status changed in-place, no new keys created
+    // Verify status changed in-place, no new unit entries created
     env.as_contract(&contract_id, || {
-        let units: Map<u64, BloodUnit> = env.storage().persistent().get(&BLOOD_UNITS).unwrap();
-
-        // Same number of keys
-        assert_eq!(units.len(), initial_keys_count);
+        let unit: BloodUnit = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BloodUnit(unit_id))
+            .unwrap();
 
         // Status updated
-        let unit = units.get(unit_id).unwrap();
         assert_eq!(unit.status, BloodStatus::Reserved);
+
+        // No neighbouring entry appeared
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::BloodUnit(unit_id + 1)));
     });
 }
 
@@ -268,14 +250,10 @@ fn test_expire_unit_updates_status_field_no_deletion() {
 
     // Verify entry still exists with Expired status
     env.as_contract(&contract_id, || {
-        let units: Map<u64, BloodUnit> = env
+        let unit: BloodUnit = env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
-            .expect("BLOOD_UNITS should still exist");
-
-        let unit = units
-            .get(unit_id)
+            .get(&DataKey::BloodUnit(unit_id))
             .expect("BloodUnit entry should NOT be deleted");
 
         // Status should be Expired (or still Available if not auto-expired)
@@ -318,29 +296,237 @@ fn test_register_two_units_same_bank_creates_two_entries() {
 
     // Verify both units exist in storage
     env.as_contract(&contract_id, || {
-        let units: Map<u64, BloodUnit> = env
+        assert!(env
+            .storage()
+            .persistent()
+            .has(&DataKey::BloodUnit(unit_id_1)));
+        assert!(env
             .storage()
             .persistent()
-            .get(&BLOOD_UNITS)
-            .expect("BLOOD_UNITS should exist");
-
-        assert_eq!(units.len(), 2);
-        assert!(units.get(unit_id_1).is_some());
-        assert!(units.get(unit_id_2).is_some());
-
-        // When BankUnits index is implemented, verify it contains both:
-        // let bank_units_key = DataKey::BankUnits(bank.clone());
-        // let bank_units: Vec<u64> = env
-        //     .storage()
-        //     .persistent()
-        //     .get(&bank_units_key)
-        //     .expect("BankUnits index should exist");
-        // assert_eq!(bank_units.len(), 2);
-        // assert!(bank_units.contains(&unit_id_1));
-        // assert!(bank_units.contains(&unit_id_2));
+            .has(&DataKey::BloodUnit(unit_id_2)));
+
+        // Consecutive IDs share a bucket
+        assert_eq!(unit_id_1 / INDEX_BUCKET_SIZE, unit_id_2 / INDEX_BUCKET_SIZE);
+        let bank_units_key = DataKey::BankUnits(bank.clone(), unit_id_1 / INDEX_BUCKET_SIZE);
+        let bank_units: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&bank_units_key)
+            .expect("BankUnits index should exist");
+        assert_eq!(bank_units.len(), 2);
+        assert!(bank_units.contains(unit_id_1));
+        assert!(bank_units.contains(unit_id_2));
     });
 }
 
+#[test]
+fn test_allocate_moves_unit_between_status_and_hospital_indexes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let bank = Address::generate(&env);
+    let hospital = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.register_blood_bank(&bank);
+    client.register_hospital(&hospital);
+
+    let expiration = env.ledger().timestamp() + 86400 * 10;
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &300,
+        &expiration,
+        &None,
+    );
+    let bucket = unit_id / INDEX_BUCKET_SIZE;
+    let day = expiry_day(expiration);
+
+    client.allocate_blood(&bank, &unit_id, &hospital);
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+
+        // Emptied indexes are removed rather than left as empty vectors
+        assert!(!storage.has(&DataKey::StatusUnits(BloodStatus::Available, bucket)));
+        assert!(!storage.has(&DataKey::AvailableByExpiry(BloodType::APositive, day)));
+        assert!(!storage.has(&DataKey::AvailableExpiryDays(BloodType::APositive)));
+
+        let reserved: Vec<u64> = storage
+            .get(&DataKey::StatusUnits(BloodStatus::Reserved, bucket))
+            .expect("StatusUnits(Reserved) index should exist");
+        assert_eq!(reserved.len(), 1);
+        assert!(reserved.contains(unit_id));
+
+        let hospital_units: Vec<u64> = storage
+            .get(&DataKey::HospitalUnits(hospital.clone(), bucket))
+            .expect("HospitalUnits index should exist");
+        assert!(hospital_units.contains(unit_id));
+    });
+
+    client.cancel_allocation(&bank, &unit_id);
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::StatusUnits(BloodStatus::Reserved, bucket)));
+        assert!(!storage.has(&DataKey::HospitalUnits(hospital.clone(), bucket)));
+
+        let available: Vec<(u64, u64)> = storage
+            .get(&DataKey::AvailableByExpiry(BloodType::APositive, day))
+            .expect("unit should be back in the Available bucket");
+        assert_eq!(available.len(), 1);
+        let days: Vec<u64> = storage
+            .get(&DataKey::AvailableExpiryDays(BloodType::APositive))
+            .expect("expiry day should be listed again");
+        assert_eq!(days, Vec::from_array(&env, [day]));
+    });
+}
+
+#[test]
+fn test_available_by_expiry_days_are_listed_in_expiry_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let bank = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.register_blood_bank(&bank);
+
+    let now = env.ledger().timestamp();
+    let register = |expiration: u64| {
+        client.register_blood(
+            &bank,
+            &BloodType::ONegative,
+            &BloodComponent::WholeBlood,
+            &300,
+            &expiration,
+            &None,
+        )
+    };
+    let late = register(now + 86400 * 30);
+    let early = register(now + 86400 * 5);
+    let middle = register(now + 86400 * 15);
+    let early_twin = register(now + 86400 * 5 + 60);
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        let days: Vec<u64> = storage
+            .get(&DataKey::AvailableExpiryDays(BloodType::ONegative))
+            .expect("AvailableExpiryDays directory should exist");
+        assert_eq!(
+            days,
+            Vec::from_array(
+                &env,
+                [
+                    expiry_day(now + 86400 * 5),
+                    expiry_day(now + 86400 * 15),
+                    expiry_day(now + 86400 * 30),
+                ]
+            )
+        );
+
+        let bucket = |day: u64| -> Vec<u64> {
+            let entries: Vec<(u64, u64)> = storage
+                .get(&DataKey::AvailableByExpiry(BloodType::ONegative, day))
+                .expect("AvailableByExpiry bucket should exist");
+            let mut ids = Vec::new(&env);
+            for (_, unit_id) in entries.iter() {
+                ids.push_back(unit_id);
+            }
+            ids
+        };
+        assert_eq!(
+            bucket(days.get(0).unwrap()),
+            Vec::from_array(&env, [early, early_twin])
+        );
+        assert_eq!(
+            bucket(days.get(1).unwrap()),
+            Vec::from_array(&env, [middle])
+        );
+        assert_eq!(bucket(days.get(2).unwrap()), Vec::from_array(&env, [late]));
+    });
+}
+
+#[test]
+fn test_index_buckets_split_by_unit_id_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let bank = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.register_blood_bank(&bank);
+
+    let expiration = env.ledger().timestamp() + 86400 * 10;
+    let first = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &300,
+        &expiration,
+        &None,
+    );
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&NEXT_ID, &(INDEX_BUCKET_SIZE * 40));
+    });
+    let second = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &300,
+        &expiration,
+        &None,
+    );
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        let first_bucket: Vec<u64> = storage
+            .get(&DataKey::BankUnits(bank.clone(), first / INDEX_BUCKET_SIZE))
+            .expect("first bucket should exist");
+        let second_bucket: Vec<u64> = storage
+            .get(&DataKey::BankUnits(
+                bank.clone(),
+                second / INDEX_BUCKET_SIZE,
+            ))
+            .expect("second bucket should exist");
+        assert_eq!(first_bucket, Vec::from_array(&env, [first]));
+        assert_eq!(second_bucket, Vec::from_array(&env, [second]));
+    });
+
+    // Paging skips the empty buckets in between, a bounded number per call
+    let mut seen = Vec::new(&env);
+    let mut cursor = None;
+    let mut calls = 0;
+    loop {
+        let page = client.query_by_status(&BloodStatus::Available, &cursor, &10);
+        for unit in page.items.iter() {
+            seen.push_back(unit.id);
+        }
+        calls += 1;
+        match page.next_cursor {
+            Some(_) => cursor = page.next_cursor,
+            None => break,
+        }
+    }
+    assert_eq!(seen, Vec::from_array(&env, [first, second]));
+    assert!(calls > 1);
+}
+
 #[test]
 fn test_storage_symbol_keys_match_compatibility_contract() {
     assert_eq!(BLOOD_UNITS, symbol_short!("UNITS"));
@@ -384,3 +570,109 @@ fn test_storage_layout_fingerprint_regression_guard() {
         "Storage layout compatibility changed: duplicate key symbols detected. Add migration guardrails before changing key names."
     );
 }
+
+fn seed_legacy_units(env: &Env, contract_id: &Address, bank: &Address, count: u64) {
+    env.as_contract(contract_id, || {
+        let mut legacy = Map::<u64, BloodUnit>::new(env);
+        for id in 1..=count {
+            legacy.set(
+                id,
+                BloodUnit {
+                    id,
+                    blood_type: BloodType::OPositive,
+                    component: BloodComponent::WholeBlood,
+                    quantity: 450,
+                    expiration_date: env.ledger().timestamp() + 86400 * 10,
+                    donor_id: symbol_short!("ANON"),
+                    location: symbol_short!("BANK"),
+                    bank_id: bank.clone(),
+                    registration_timestamp: env.ledger().timestamp(),
+                    status: BloodStatus::Available,
+                    recipient_hospital: None,
+                    allocation_timestamp: None,
+                    transfer_timestamp: None,
+                    delivery_timestamp: None,
+                },
+            );
+        }
+        env.storage().persistent().set(&BLOOD_UNITS, &legacy);
+        env.storage().persistent().set(&NEXT_ID, &(count + 1));
+    });
+}
+
+#[test]
+fn test_migrate_unit_storage_moves_legacy_map_in_chunks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let bank = Address::generate(&env);
+    client.initialize(&admin);
+
+    seed_legacy_units(&env, &contract_id, &bank, 5);
+
+    assert_eq!(client.migrate_unit_storage(&2), 3);
+    assert_eq!(client.get_blood_unit(&1).id, 1);
+    assert_eq!(client.get_blood_unit(&2).id, 2);
+    assert!(client.try_get_blood_unit(&3).is_err());
+
+    assert_eq!(client.migrate_unit_storage(&10), 0);
+    for id in 1..=5u64 {
+        assert_eq!(client.get_blood_unit(&id).bank_id, bank);
+    }
+
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&BLOOD_UNITS));
+    });
+
+    // Re-running after completion is a no-op
+    assert_eq!(client.migrate_unit_storage(&10), 0);
+    assert_eq!(client.get_units_by_bank(&bank, &None, &0).items.len(), 5);
+}
+
+#[test]
+fn test_migrate_unit_storage_does_not_overwrite_existing_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let bank = Address::generate(&env);
+    let hospital = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_blood_bank(&bank);
+    client.register_hospital(&hospital);
+
+    seed_legacy_units(&env, &contract_id, &bank, 1);
+
+    // Unit 1 already exists in the new layout with newer state
+    env.as_contract(&contract_id, || {
+        let legacy: Map<u64, BloodUnit> = env.storage().persistent().get(&BLOOD_UNITS).unwrap();
+        let mut unit = legacy.get(1).unwrap();
+        unit.status = BloodStatus::Reserved;
+        env.storage()
+            .persistent()
+            .set(&DataKey::BloodUnit(1), &unit);
+    });
+
+    assert_eq!(client.migrate_unit_storage(&10), 0);
+    assert_eq!(client.get_blood_status(&1), BloodStatus::Reserved);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_migrate_unit_storage_rejects_oversized_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HealthChainContract, ());
+    let client = HealthChainContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    client.migrate_unit_storage(&(crate::constants::MAX_BATCH_SIZE + 1));
+}